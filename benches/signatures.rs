@@ -0,0 +1,193 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks comparing key generation, signing, verification and batch verification across the
+//! signature schemes of this crate. Throughput is reported in operations (resp. signatures in a
+//! batch) per second. All inputs are derived from a fixed seed, so that every run benchmarks the
+//! same keys and signatures.
+
+use criterion::{
+    criterion_group, criterion_main, measurement::Measurement, BatchSize, BenchmarkGroup,
+    BenchmarkId, Criterion, Throughput,
+};
+use fastcrypto::{
+    bls12381::{BLS12381AggregateSignature, BLS12381KeyPair, BLS12381Signature},
+    ed25519::Ed25519KeyPair,
+    secp256k1::Secp256k1KeyPair,
+    traits::{AggregateAuthenticator, KeyPair, VerifyingKey},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use signature::{Signer, Verifier};
+
+const SEED: [u8; 32] = [0; 32];
+const MESSAGE: &[u8] = b"Hello, world!";
+const BATCH_SIZES: [usize; 4] = [1, 10, 100, 1000];
+
+fn keypairs<KP: KeyPair>(n: usize) -> Vec<KP> {
+    KP::generate_many(n, &mut StdRng::from_seed(SEED))
+}
+
+fn key_generation<KP: KeyPair, M: Measurement>(name: &str, group: &mut BenchmarkGroup<'_, M>) {
+    let mut rng = StdRng::from_seed(SEED);
+    group.bench_function(name, |b| b.iter(|| KP::generate(&mut rng)));
+}
+
+fn signing<KP: KeyPair, M: Measurement>(name: &str, group: &mut BenchmarkGroup<'_, M>) {
+    let kp = keypairs::<KP>(1).pop().unwrap();
+    group.bench_function(name, |b| b.iter(|| kp.sign(MESSAGE)));
+}
+
+fn verification<KP: KeyPair, M: Measurement>(name: &str, group: &mut BenchmarkGroup<'_, M>) {
+    let kp = keypairs::<KP>(1).pop().unwrap();
+    let signature = kp.sign(MESSAGE);
+    group.bench_function(name, |b| b.iter(|| kp.public().verify(MESSAGE, &signature)));
+}
+
+fn batch_verification<KP: KeyPair, M: Measurement>(name: &str, group: &mut BenchmarkGroup<'_, M>) {
+    for size in BATCH_SIZES {
+        let kps = keypairs::<KP>(size);
+        let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+        let sigs: Vec<_> = kps.iter().map(|kp| kp.sign(MESSAGE)).collect();
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(
+            BenchmarkId::new(name, size),
+            &(pks, sigs),
+            |b, (pks, sigs)| b.iter(|| KP::PubKey::verify_batch_empty_fail(MESSAGE, pks, sigs)),
+        );
+    }
+}
+
+/// Compare reading the cached public key encoding of a key pair with deriving the public key from
+/// the private key and encoding it.
+fn public_key_bytes<KP: KeyPair, M: Measurement>(name: &str, group: &mut BenchmarkGroup<'_, M>) {
+    let kp = keypairs::<KP>(1).pop().unwrap();
+    let sk = keypairs::<KP>(1).pop().unwrap().private();
+    group.bench_function(BenchmarkId::new("cached", name), |b| {
+        b.iter(|| kp.public_key_bytes().len())
+    });
+    group.bench_function(BenchmarkId::new("derived", name), |b| {
+        b.iter(|| KP::PubKey::from(&sk).as_ref().len())
+    });
+}
+
+fn key_generation_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("key_generation");
+    group.throughput(Throughput::Elements(1));
+    key_generation::<Ed25519KeyPair, _>("Ed25519", &mut group);
+    key_generation::<Secp256k1KeyPair, _>("Secp256k1", &mut group);
+    key_generation::<BLS12381KeyPair, _>("BLS12381", &mut group);
+    group.finish();
+}
+
+fn signing_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signing");
+    group.throughput(Throughput::Elements(1));
+    signing::<Ed25519KeyPair, _>("Ed25519", &mut group);
+    signing::<Secp256k1KeyPair, _>("Secp256k1", &mut group);
+    signing::<BLS12381KeyPair, _>("BLS12381", &mut group);
+    group.finish();
+}
+
+fn verification_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verification");
+    group.throughput(Throughput::Elements(1));
+    verification::<Ed25519KeyPair, _>("Ed25519", &mut group);
+    verification::<Secp256k1KeyPair, _>("Secp256k1", &mut group);
+    verification::<BLS12381KeyPair, _>("BLS12381", &mut group);
+    group.finish();
+}
+
+fn public_key_bytes_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("public_key_bytes");
+    public_key_bytes::<Ed25519KeyPair, _>("Ed25519", &mut group);
+    public_key_bytes::<Secp256k1KeyPair, _>("Secp256k1", &mut group);
+    public_key_bytes::<BLS12381KeyPair, _>("BLS12381", &mut group);
+    group.finish();
+}
+
+fn batch_verification_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_verification");
+    batch_verification::<Ed25519KeyPair, _>("Ed25519", &mut group);
+    batch_verification::<Secp256k1KeyPair, _>("Secp256k1", &mut group);
+    batch_verification::<BLS12381KeyPair, _>("BLS12381", &mut group);
+    group.finish();
+}
+
+/// Compare verifying a BLS aggregate over distinct messages, which needs a single final
+/// exponentiation, with verifying each of the aggregated signatures on its own.
+fn aggregate_verification_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bls12381_distinct_messages");
+    for size in BATCH_SIZES {
+        let kps = keypairs::<BLS12381KeyPair>(size);
+        let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+        let msgs: Vec<Vec<u8>> = (0..size).map(|i| i.to_le_bytes().to_vec()).collect();
+        let sigs: Vec<_> = kps
+            .iter()
+            .zip(&msgs)
+            .map(|(kp, msg)| kp.sign(msg))
+            .collect();
+        let aggregate = BLS12381AggregateSignature::aggregate(sigs.clone()).unwrap();
+        let msgs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_slice()).collect();
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(
+            BenchmarkId::new("aggregate", size),
+            &(&pks, &msgs),
+            |b, (pks, msgs)| b.iter(|| aggregate.verify_different_msgs(pks, msgs)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("individual", size),
+            &(&pks, &msgs, &sigs),
+            |b, (pks, msgs, sigs)| {
+                b.iter(|| {
+                    pks.iter()
+                        .zip(msgs.iter())
+                        .zip(sigs.iter())
+                        .all(|((pk, msg), sig)| pk.verify(msg, sig).is_ok())
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Compare the first verification of a deserialized BLS signature, which checks that the
+/// signature is in the prime order subgroup, with subsequent verifications of the same signature,
+/// which skip the check.
+fn repeated_verification_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bls12381_repeated_verification");
+    group.throughput(Throughput::Elements(1));
+    let kp = keypairs::<BLS12381KeyPair>(1).pop().unwrap();
+    let serialized = bincode::serialize(&kp.sign(MESSAGE)).unwrap();
+
+    group.bench_function("first", |b| {
+        b.iter_batched(
+            || bincode::deserialize::<BLS12381Signature>(&serialized).unwrap(),
+            |signature| kp.public().verify(MESSAGE, &signature),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let signature: BLS12381Signature = bincode::deserialize(&serialized).unwrap();
+    kp.public().verify(MESSAGE, &signature).unwrap();
+    group.bench_function("repeated", |b| {
+        b.iter(|| kp.public().verify(MESSAGE, &signature))
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = signature_benches;
+    config = Criterion::default();
+    targets =
+        key_generation_benches,
+        signing_benches,
+        verification_benches,
+        public_key_bytes_benches,
+        batch_verification_benches,
+        aggregate_verification_benches,
+        repeated_verification_benches,
+}
+
+criterion_main!(signature_benches);
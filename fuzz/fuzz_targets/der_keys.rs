@@ -0,0 +1,38 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use fastcrypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey},
+};
+use libfuzzer_sys::fuzz_target;
+
+// Every key parsed from DER encodes to DER which parses to the same key.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(sk) = Secp256k1PrivateKey::from_pkcs8_der(data) {
+        assert_eq!(
+            Secp256k1PrivateKey::from_pkcs8_der(&sk.to_pkcs8_der()).unwrap(),
+            sk
+        );
+    }
+    if let Ok(sk) = Ed25519PrivateKey::from_pkcs8_der(data) {
+        assert_eq!(
+            Ed25519PrivateKey::from_pkcs8_der(&sk.to_pkcs8_der()).unwrap(),
+            sk
+        );
+    }
+    if let Ok(pk) = Secp256k1PublicKey::from_spki_der(data) {
+        assert_eq!(
+            Secp256k1PublicKey::from_spki_der(&pk.to_spki_der()).unwrap(),
+            pk
+        );
+    }
+    if let Ok(pk) = Ed25519PublicKey::from_spki_der(data) {
+        assert_eq!(
+            Ed25519PublicKey::from_spki_der(&pk.to_spki_der()).unwrap(),
+            pk
+        );
+    }
+});
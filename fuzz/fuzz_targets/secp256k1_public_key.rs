@@ -0,0 +1,18 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use fastcrypto::{secp256k1::Secp256k1PublicKey, traits::ToFromBytes};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(pk) = Secp256k1PublicKey::from_bytes(data) {
+        // The compressed encoding is canonical, so it serializes back to the input.
+        assert_eq!(pk.as_ref(), data);
+        assert_eq!(
+            Secp256k1PublicKey::from_uncompressed(&pk.to_uncompressed()).unwrap(),
+            pk
+        );
+    }
+});
@@ -0,0 +1,16 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+
+use fastcrypto::{secp256k1::Secp256k1Signature, traits::ToFromBytes};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(sig) = <Secp256k1Signature as ToFromBytes>::from_bytes(data) {
+        // A parsed signature serializes to bytes which parse to the same signature.
+        let reparsed = <Secp256k1Signature as ToFromBytes>::from_bytes(sig.as_ref())
+            .expect("Serialized signature is valid");
+        assert_eq!(reparsed, sig);
+    }
+});
@@ -0,0 +1,151 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains an implementation of the AES-256-GCM authenticated encryption scheme,
+//! backed by the [aes-gcm](https://docs.rs/aes-gcm) crate.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::aes::{Aes256Gcm, AesKey, Nonce};
+//! use fastcrypto::traits::Aead;
+//! use rand::thread_rng;
+//! # fn main() {
+//!     let key = AesKey::generate(&mut thread_rng());
+//!     let nonce = Nonce::generate(&mut thread_rng());
+//!     let ciphertext = Aes256Gcm::encrypt(&key, &nonce, b"header", b"Hello, world!");
+//!     let plaintext = Aes256Gcm::decrypt(&key, &nonce, b"header", &ciphertext).unwrap();
+//!     assert_eq!(plaintext, b"Hello, world!");
+//! # }
+//! ```
+
+use crate::{
+    error::FastCryptoError,
+    traits::{check_length, Aead, FixedLength, ToFromBytes},
+};
+use aes_gcm::{
+    aead::{generic_array::GenericArray, Aead as _, KeyInit, Payload},
+    Aes256Gcm as ExternalAes256Gcm,
+};
+use rand::{CryptoRng, RngCore};
+use std::fmt::{self, Debug};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub const AES_256_KEY_LENGTH: usize = 32;
+pub const AES_GCM_NONCE_LENGTH: usize = 12;
+pub const AES_GCM_TAG_LENGTH: usize = 16;
+
+/// A symmetric key of `N` bytes. The key material is zeroized on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct AesKey<const N: usize>([u8; N]);
+
+/// A nonce of `N` bytes. A nonce must never be reused with the same key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nonce<const N: usize>([u8; N]);
+
+impl<const N: usize> AesKey<N> {
+    pub fn new(bytes: [u8; N]) -> Self {
+        AesKey(bytes)
+    }
+
+    pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; N];
+        rng.fill_bytes(&mut bytes);
+        AesKey(bytes)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for AesKey<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Debug for AesKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AesKey(<redacted>)")
+    }
+}
+
+impl<const N: usize> FixedLength for AesKey<N> {
+    const LENGTH: usize = N;
+}
+
+impl<const N: usize> ToFromBytes for AesKey<N> {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
+        let mut key = [0u8; N];
+        key.copy_from_slice(bytes);
+        Ok(AesKey(key))
+    }
+}
+
+impl<const N: usize> Nonce<N> {
+    pub fn new(bytes: [u8; N]) -> Self {
+        Nonce(bytes)
+    }
+
+    pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; N];
+        rng.fill_bytes(&mut bytes);
+        Nonce(bytes)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Nonce<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> FixedLength for Nonce<N> {
+    const LENGTH: usize = N;
+}
+
+impl<const N: usize> ToFromBytes for Nonce<N> {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
+        let mut nonce = [0u8; N];
+        nonce.copy_from_slice(bytes);
+        Ok(Nonce(nonce))
+    }
+}
+
+/// AES-256 in Galois/Counter Mode, with a 96 bit nonce and a 128 bit authentication tag.
+pub struct Aes256Gcm;
+
+impl Aead for Aes256Gcm {
+    type Key = AesKey<AES_256_KEY_LENGTH>;
+    type Nonce = Nonce<AES_GCM_NONCE_LENGTH>;
+
+    fn encrypt(key: &Self::Key, nonce: &Self::Nonce, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        ExternalAes256Gcm::new(GenericArray::from_slice(key.as_ref()))
+            .encrypt(
+                GenericArray::from_slice(nonce.as_ref()),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .expect("plaintext is within the length supported by AES-GCM")
+    }
+
+    fn decrypt(
+        key: &Self::Key,
+        nonce: &Self::Nonce,
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, FastCryptoError> {
+        if ciphertext.len() < AES_GCM_TAG_LENGTH {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        ExternalAes256Gcm::new(GenericArray::from_slice(key.as_ref()))
+            .decrypt(
+                GenericArray::from_slice(nonce.as_ref()),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| FastCryptoError::GeneralOpaqueError)
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The minimal-pubkey-size variant of BLS, with 96-byte signatures in G2 and 48-byte public
+//! keys in G1.
+
+use crate::{
+    groups::bls12381::{G1Element, G2Element},
+    serde_helpers::BlsMinPkSignature as BlsSignature,
+};
+use ::blst::{
+    blst_p1, blst_p1_affine, blst_p1_affine_serialize, blst_p1_deserialize, blst_p1_from_affine,
+    blst_p1_to_affine,
+};
+use blst::min_pk as blst;
+
+pub const BLS_PUBLIC_KEY_LENGTH: usize = 48;
+pub const BLS_SIGNATURE_LENGTH: usize = 96;
+pub const BLS_PUBLIC_KEY_UNCOMPRESSED_LENGTH: usize = 96;
+pub const BLS_SIGNATURE_UNCOMPRESSED_LENGTH: usize = 192;
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+type PublicKeyPoint = G1Element;
+type SignaturePoint = G2Element;
+
+/// Convert a public key to a G1 point without checking that it lies in the prime order subgroup.
+fn public_key_to_point(pk: &blst::PublicKey) -> PublicKeyPoint {
+    let mut affine = blst_p1_affine::default();
+    let mut point = blst_p1::default();
+    unsafe {
+        // Deserializing the uncompressed encoding of a valid key cannot fail.
+        blst_p1_deserialize(&mut affine, pk.serialize().as_ptr());
+        blst_p1_from_affine(&mut point, &affine);
+    }
+    G1Element(point)
+}
+
+/// Convert a G1 point to a public key without checking that it lies in the prime order subgroup.
+fn point_to_public_key(point: &PublicKeyPoint) -> blst::PublicKey {
+    let mut affine = blst_p1_affine::default();
+    let mut bytes = [0u8; BLS_PUBLIC_KEY_UNCOMPRESSED_LENGTH];
+    unsafe {
+        blst_p1_to_affine(&mut affine, &point.0);
+        blst_p1_affine_serialize(bytes.as_mut_ptr(), &affine);
+    }
+    blst::PublicKey::deserialize(&bytes).expect("Serialized point is on the curve")
+}
+
+define_bls12381!();
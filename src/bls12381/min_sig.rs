@@ -0,0 +1,49 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The minimal-signature-size variant of BLS, with 48-byte signatures in G1 and 96-byte public
+//! keys in G2.
+
+use crate::{
+    groups::bls12381::{G1Element, G2Element},
+    serde_helpers::BlsSignature,
+};
+use ::blst::{
+    blst_p2, blst_p2_affine, blst_p2_affine_serialize, blst_p2_deserialize, blst_p2_from_affine,
+    blst_p2_to_affine,
+};
+use blst::min_sig as blst;
+
+pub const BLS_PUBLIC_KEY_LENGTH: usize = 96;
+pub const BLS_SIGNATURE_LENGTH: usize = 48;
+pub const BLS_PUBLIC_KEY_UNCOMPRESSED_LENGTH: usize = 192;
+pub const BLS_SIGNATURE_UNCOMPRESSED_LENGTH: usize = 96;
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+type PublicKeyPoint = G2Element;
+type SignaturePoint = G1Element;
+
+/// Convert a public key to a G2 point without checking that it lies in the prime order subgroup.
+fn public_key_to_point(pk: &blst::PublicKey) -> PublicKeyPoint {
+    let mut affine = blst_p2_affine::default();
+    let mut point = blst_p2::default();
+    unsafe {
+        // Deserializing the uncompressed encoding of a valid key cannot fail.
+        blst_p2_deserialize(&mut affine, pk.serialize().as_ptr());
+        blst_p2_from_affine(&mut point, &affine);
+    }
+    G2Element(point)
+}
+
+/// Convert a G2 point to a public key without checking that it lies in the prime order subgroup.
+fn point_to_public_key(point: &PublicKeyPoint) -> blst::PublicKey {
+    let mut affine = blst_p2_affine::default();
+    let mut bytes = [0u8; BLS_PUBLIC_KEY_UNCOMPRESSED_LENGTH];
+    unsafe {
+        blst_p2_to_affine(&mut affine, &point.0);
+        blst_p2_affine_serialize(bytes.as_mut_ptr(), &affine);
+    }
+    blst::PublicKey::deserialize(&bytes).expect("Serialized point is on the curve")
+}
+
+define_bls12381!();
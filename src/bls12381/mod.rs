@@ -0,0 +1,1133 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! BLS signatures over the BLS12-381 curve in the two variants of the
+//! [BLS signature draft](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05):
+//! [min_sig], where signatures are 48-byte G1 points and public keys are 96-byte G2 points, and
+//! [min_pk], where signatures are 96-byte G2 points and public keys are 48-byte G1 points. The
+//! minimal-signature-size variant suits deployments which store or send many signatures, the
+//! minimal-pubkey-size variant those which store many keys, e.g. a large committee.
+//!
+//! Both modules expose the same types and functions. The items of [min_sig] are re-exported here,
+//! so `fastcrypto::bls12381::BLS12381Signature` is a min-sig signature.
+
+/// Define the types of one BLS variant. The module calling this macro must import `blst` as the
+/// corresponding blst module, define the length constants, [DST], the types `PublicKeyPoint` and
+/// `SignaturePoint` of the group elements, and the conversions `public_key_to_point` and
+/// `point_to_public_key` which skip the subgroup check.
+macro_rules! define_bls12381 {
+    () => {
+        use std::{
+            collections::BTreeSet,
+            fmt::{self, Display},
+            mem::MaybeUninit,
+            str::FromStr,
+        };
+
+        use ::blst::{blst_scalar, blst_scalar_from_uint64, BLST_ERROR};
+        use base64ct::{Base64, Encoding};
+
+        use once_cell::sync::OnceCell;
+        use rand_core::{CryptoRng, OsRng, RngCore};
+        use zeroize::Zeroize;
+
+        use crate::{
+            error::FastCryptoError,
+            groups::{bls12381::Scalar, GroupElement},
+            pubkey_bytes::PublicKeyBytes,
+        };
+        #[cfg(feature = "signing")]
+        use crate::{serde_helpers::keypair_decode_base64, traits::KeyPair};
+        use eyre::eyre;
+        use serde::{
+            de::{self},
+            Deserialize, Serialize,
+        };
+        use serde_with::serde_as;
+        use subtle::ConstantTimeEq;
+
+        #[cfg(feature = "signing")]
+        use signature::Signer;
+        use signature::{Signature, Verifier};
+
+        use crate::traits::{
+            check_length, from_base64_str, AggregateAuthenticator, Authenticator,
+            EncodeDecodeBase64, FixedLength, SigningKey, ToFromBytes, VerifyingKey,
+        };
+
+        pub const BLS_PRIVATE_KEY_LENGTH: usize = 32;
+
+        ///
+        /// Define Structs
+        ///
+
+        #[readonly::make]
+        #[derive(Default, Debug, Clone)]
+        pub struct BLS12381PublicKey {
+            pub pubkey: blst::PublicKey,
+            pub bytes: OnceCell<[u8; BLS_PUBLIC_KEY_LENGTH]>,
+        }
+
+        pub type BLS12381PublicKeyBytes = PublicKeyBytes<BLS12381PublicKey, { BLS12381PublicKey::LENGTH }>;
+
+        #[readonly::make]
+        #[derive(Default)]
+        pub struct BLS12381PrivateKey {
+            pub privkey: blst::SecretKey,
+            pub bytes: OnceCell<[u8; BLS_PRIVATE_KEY_LENGTH]>,
+        }
+
+        // There is a strong requirement for this specific impl. in Fab benchmarks
+        #[cfg(feature = "signing")]
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(tag = "type")] // necessary so as not to deser under a != type
+        pub struct BLS12381KeyPair {
+            name: BLS12381PublicKey,
+            secret: BLS12381PrivateKey,
+        }
+
+        #[readonly::make]
+        #[serde_as]
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        pub struct BLS12381Signature {
+            #[serde_as(as = "BlsSignature")]
+            pub sig: blst::Signature,
+            #[serde(skip)]
+            pub bytes: OnceCell<[u8; BLS_SIGNATURE_LENGTH]>,
+            /// Set once the signature is known to lie in the prime order subgroup, after which
+            /// verification skips the subgroup check.
+            #[serde(skip)]
+            pub subgroup_checked: OnceCell<()>,
+        }
+
+        #[readonly::make]
+        #[serde_as]
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        pub struct BLS12381AggregateSignature {
+            #[serde_as(as = "Option<BlsSignature>")]
+            pub sig: Option<blst::Signature>,
+            #[serde(skip)]
+            pub bytes: OnceCell<[u8; BLS_SIGNATURE_LENGTH]>,
+        }
+
+        /// The aggregate of the public keys of a set of members, e.g. a committee, which can be computed
+        /// once and reused to verify many aggregate signatures by the whole set. The aggregate is updated
+        /// incrementally when members are added or removed.
+        #[derive(Debug, Clone)]
+        pub struct BLS12381AggregatePublicKey {
+            members: BTreeSet<BLS12381PublicKey>,
+            point: PublicKeyPoint,
+            // The aggregate as a blst public key, or None if there are no members.
+            pubkey: Option<blst::PublicKey>,
+        }
+
+        ///
+        /// Implement SigningKey
+        ///
+
+        impl AsRef<[u8]> for BLS12381PublicKey {
+            fn as_ref(&self) -> &[u8] {
+                self.bytes
+                    .get_or_try_init::<_, eyre::Report>(|| Ok(self.pubkey.to_bytes()))
+                    .expect("OnceCell invariant violated")
+            }
+        }
+
+        impl ToFromBytes for BLS12381PublicKey {
+            /// Parses a public key and checks that it lies in the prime order subgroup and is not the
+            /// identity. The subgroup check costs roughly one scalar multiplication per key.
+            fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+                check_length::<Self>(bytes)?;
+                let pubkey =
+                    blst::PublicKey::key_validate(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+                Ok(BLS12381PublicKey {
+                    pubkey,
+                    bytes: OnceCell::new(),
+                })
+            }
+        }
+
+        impl BLS12381PublicKey {
+            /// Check whether `bytes` has the right length and encodes a point on the curve. This skips the
+            /// subgroup check, which dominates the cost of [ToFromBytes::from_bytes], so `from_bytes` may
+            /// still fail for bytes accepted here.
+            pub fn is_valid_bytes(bytes: &[u8]) -> bool {
+                bytes.len() == Self::LENGTH && blst::PublicKey::from_bytes(bytes).is_ok()
+            }
+
+            /// Returns true if the key lies in the prime order subgroup and is not the identity.
+            pub fn is_in_correct_subgroup(&self) -> bool {
+                self.pubkey.validate().is_ok()
+            }
+
+            /// Blind the key by multiplying it with `r`. A signature rerandomized with the same `r` using
+            /// [BLS12381Signature::rerandomize] verifies under the blinded key. Fails with
+            /// [FastCryptoError::InvalidInput] if `r` is zero.
+            pub fn blind(&self, r: &Scalar) -> Result<Self, FastCryptoError> {
+                if *r == Scalar::zero() {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                let point = public_key_to_point(&self.pubkey) * *r;
+                Ok(BLS12381PublicKey {
+                    pubkey: point_to_public_key(&point),
+                    bytes: OnceCell::new(),
+                })
+            }
+
+            /// Serializes the key as an uncompressed point following the ZCash serialization spec.
+            pub fn to_uncompressed(&self) -> [u8; BLS_PUBLIC_KEY_UNCOMPRESSED_LENGTH] {
+                self.pubkey.serialize()
+            }
+
+            /// Parses an uncompressed point, performing the same checks as `from_bytes`.
+            pub fn from_uncompressed(bytes: &[u8]) -> Result<Self, signature::Error> {
+                if bytes.len() != BLS_PUBLIC_KEY_UNCOMPRESSED_LENGTH {
+                    return Err(FastCryptoError::InputLengthWrong {
+                        expected: BLS_PUBLIC_KEY_UNCOMPRESSED_LENGTH,
+                        got: bytes.len(),
+                    }
+                    .into());
+                }
+                let pubkey =
+                    blst::PublicKey::key_validate(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+                Ok(BLS12381PublicKey {
+                    pubkey,
+                    bytes: OnceCell::new(),
+                })
+            }
+
+            /// Parses a public key checking only that it is on the curve. Use this only for keys
+            /// coming from a trusted source, e.g. a local database of keys validated on insertion.
+            #[cfg(feature = "trusted_bls_inputs")]
+            pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, signature::Error> {
+                let pubkey =
+                    blst::PublicKey::from_bytes(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+                Ok(BLS12381PublicKey {
+                    pubkey,
+                    bytes: OnceCell::new(),
+                })
+            }
+        }
+
+        impl std::hash::Hash for BLS12381PublicKey {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.as_ref().hash(state);
+            }
+        }
+
+        impl PartialEq for BLS12381PublicKey {
+            fn eq(&self, other: &Self) -> bool {
+                self.pubkey == other.pubkey
+            }
+        }
+
+        impl Eq for BLS12381PublicKey {}
+
+        impl PartialOrd for BLS12381PublicKey {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                self.as_ref().partial_cmp(other.as_ref())
+            }
+        }
+        impl Ord for BLS12381PublicKey {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.as_ref().cmp(other.as_ref())
+            }
+        }
+
+        impl Display for BLS12381PublicKey {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                write!(f, "{}", Base64::encode_string(self.as_ref()))
+            }
+        }
+
+        impl FromStr for BLS12381PublicKey {
+            type Err = FastCryptoError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                from_base64_str(s)
+            }
+        }
+
+        // There is a strong requirement for this specific impl. in Fab benchmarks
+        impl Serialize for BLS12381PublicKey {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(&self.encode_base64())
+            }
+        }
+
+        // There is a strong requirement for this specific impl. in Fab benchmarks
+        impl<'de> Deserialize<'de> for BLS12381PublicKey {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+                let value = Self::decode_base64(&s).map_err(|e| de::Error::custom(e.to_string()))?;
+                Ok(value)
+            }
+        }
+
+        // The inputs to verification are all public, and blst computes the full pairing product before
+        // comparing it with one, so the time taken does not depend on whether the signature is valid.
+        impl Verifier<BLS12381Signature> for BLS12381PublicKey {
+            fn verify(&self, msg: &[u8], signature: &BLS12381Signature) -> Result<(), signature::Error> {
+                let sig_groupcheck = signature.subgroup_checked.get().is_none();
+                let err = signature
+                    .sig
+                    .verify(sig_groupcheck, msg, DST, &[], &self.pubkey, true);
+                if err == BLST_ERROR::BLST_SUCCESS {
+                    // A signature which verifies passed the subgroup check.
+                    let _ = signature.subgroup_checked.set(());
+                    Ok(())
+                } else {
+                    Err(FastCryptoError::InvalidSignature.into())
+                }
+            }
+        }
+
+        impl<'a> From<&'a BLS12381PrivateKey> for BLS12381PublicKey {
+            fn from(secret: &'a BLS12381PrivateKey) -> Self {
+                let inner = &secret.privkey;
+                let pubkey = inner.sk_to_pk();
+                BLS12381PublicKey {
+                    pubkey,
+                    bytes: OnceCell::new(),
+                }
+            }
+        }
+
+        impl VerifyingKey for BLS12381PublicKey {
+            type PrivKey = BLS12381PrivateKey;
+            type Sig = BLS12381Signature;
+
+            fn verify_batch_empty_fail(
+                msg: &[u8],
+                pks: &[Self],
+                sigs: &[Self::Sig],
+            ) -> Result<(), eyre::Report> {
+                Self::verify_batch_empty_fail_with_rng(msg, pks, sigs, &mut OsRng)
+            }
+        }
+
+        impl FixedLength for BLS12381PublicKey {
+            const LENGTH: usize = BLS_PUBLIC_KEY_LENGTH;
+        }
+
+        impl BLS12381PublicKey {
+            /// Same as [VerifyingKey::verify_batch_empty_fail], but the random scalars used to combine
+            /// the signatures are drawn from the given rng.
+            pub fn verify_batch_empty_fail_with_rng<R: CryptoRng + RngCore>(
+                msg: &[u8],
+                pks: &[Self],
+                sigs: &[BLS12381Signature],
+                rng: &mut R,
+            ) -> Result<(), eyre::Report> {
+                let num_sigs = sigs.len();
+                if sigs.is_empty() {
+                    return Err(eyre!("Critical Error! This behavious can signal something dangerous, and that someone may be trying to bypass signature verification through providing empty batches."));
+                }
+                if sigs.len() != pks.len() {
+                    return Err(eyre!(
+                        "Mismatch between number of signatures and public keys provided"
+                    ));
+                }
+                let mut rands: Vec<blst_scalar> = Vec::with_capacity(num_sigs);
+
+                for _i in 0..num_sigs {
+                    let mut vals = [0u64; 4];
+                    vals[0] = rng.next_u64();
+                    while vals[0] == 0 {
+                        // Reject zero as it is used for multiplication.
+                        vals[0] = rng.next_u64();
+                    }
+                    let mut rand_i = MaybeUninit::<blst_scalar>::uninit();
+                    unsafe {
+                        blst_scalar_from_uint64(rand_i.as_mut_ptr(), vals.as_ptr());
+                        rands.push(rand_i.assume_init());
+                    }
+                }
+
+                // TODO: fix this, the identical message opens up a rogue key attack
+                let msgs_refs = (0..num_sigs).map(|_| msg).collect::<Vec<_>>();
+
+                let result = blst::Signature::verify_multiple_aggregate_signatures(
+                    &msgs_refs[..],
+                    DST,
+                    &pks.iter().map(|pk| &pk.pubkey).collect::<Vec<_>>()[..],
+                    false,
+                    &sigs.iter().map(|sig| &sig.sig).collect::<Vec<_>>()[..],
+                    true,
+                    &rands,
+                    64,
+                );
+                if result == BLST_ERROR::BLST_SUCCESS {
+                    Ok(())
+                } else {
+                    Err(eyre!("Verification failed!"))
+                }
+            }
+        }
+
+        ///
+        /// Implement Authenticator
+        ///
+
+        impl AsRef<[u8]> for BLS12381Signature {
+            fn as_ref(&self) -> &[u8] {
+                self.bytes
+                    .get_or_try_init::<_, eyre::Report>(|| Ok(self.sig.to_bytes()))
+                    .expect("OnceCell invariant violated")
+            }
+        }
+
+        impl std::hash::Hash for BLS12381Signature {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.as_ref().hash(state);
+            }
+        }
+
+        impl PartialEq for BLS12381Signature {
+            fn eq(&self, other: &Self) -> bool {
+                self.sig == other.sig
+            }
+        }
+
+        impl Eq for BLS12381Signature {}
+
+        impl Signature for BLS12381Signature {
+            /// Parses a signature and checks that it lies in the prime order subgroup and is not the
+            /// identity. The subgroup check costs roughly one scalar multiplication per signature.
+            fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+                check_length::<Self>(bytes)?;
+                let sig = blst::Signature::sig_validate(bytes, true)
+                    .map_err(|_| FastCryptoError::InvalidInput)?;
+                Ok(BLS12381Signature {
+                    sig,
+                    bytes: OnceCell::new(),
+                    subgroup_checked: subgroup_checked(),
+                })
+            }
+        }
+
+        impl BLS12381Signature {
+            /// Always true, since only the unique compressed encoding of a point is accepted when
+            /// parsing a signature.
+            pub fn is_canonical(&self) -> bool {
+                true
+            }
+
+            /// Does nothing, since all signatures are canonical.
+            pub fn canonicalize(&mut self) {}
+
+            /// Returns true if the signature lies in the prime order subgroup.
+            pub fn is_in_correct_subgroup(&self) -> bool {
+                self.sig.validate(false).is_ok()
+            }
+
+            /// Rerandomize the signature by multiplying it with `r`. The result verifies on the same
+            /// message under the public key blinded with `r` using [BLS12381PublicKey::blind], and for a
+            /// random `r` it cannot be linked to the original signature. Fails with
+            /// [FastCryptoError::InvalidInput] if `r` is zero.
+            pub fn rerandomize(&self, r: &Scalar) -> Result<Self, FastCryptoError> {
+                if *r == Scalar::zero() {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                let point = SignaturePoint::decompress(&self.sig.to_bytes())? * *r;
+                let sig = blst::Signature::from_bytes(&point.compress())
+                    .map_err(|_| FastCryptoError::GeneralOpaqueError)?;
+                Ok(BLS12381Signature {
+                    sig,
+                    bytes: OnceCell::new(),
+                    subgroup_checked: subgroup_checked(),
+                })
+            }
+
+            /// Serializes the signature as an uncompressed point following the ZCash serialization spec.
+            pub fn to_uncompressed(&self) -> [u8; BLS_SIGNATURE_UNCOMPRESSED_LENGTH] {
+                self.sig.serialize()
+            }
+
+            /// Parses an uncompressed point, performing the same checks as `from_bytes`.
+            pub fn from_uncompressed(bytes: &[u8]) -> Result<Self, signature::Error> {
+                if bytes.len() != BLS_SIGNATURE_UNCOMPRESSED_LENGTH {
+                    return Err(FastCryptoError::InputLengthWrong {
+                        expected: BLS_SIGNATURE_UNCOMPRESSED_LENGTH,
+                        got: bytes.len(),
+                    }
+                    .into());
+                }
+                let sig = blst::Signature::sig_validate(bytes, true)
+                    .map_err(|_| FastCryptoError::InvalidInput)?;
+                Ok(BLS12381Signature {
+                    sig,
+                    bytes: OnceCell::new(),
+                    subgroup_checked: subgroup_checked(),
+                })
+            }
+
+            /// Parses a signature checking only that it is on the curve. Use this only for signatures
+            /// coming from a trusted source.
+            #[cfg(feature = "trusted_bls_inputs")]
+            pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, signature::Error> {
+                let sig = blst::Signature::from_bytes(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+                Ok(BLS12381Signature {
+                    sig,
+                    bytes: OnceCell::new(),
+                    subgroup_checked: OnceCell::new(),
+                })
+            }
+        }
+
+        /// The value of [BLS12381Signature::subgroup_checked] for signatures which were validated when
+        /// parsed or were produced by signing.
+        fn subgroup_checked() -> OnceCell<()> {
+            let cell = OnceCell::new();
+            cell.set(()).expect("The cell is empty");
+            cell
+        }
+
+        impl Default for BLS12381Signature {
+            fn default() -> Self {
+                // TODO: improve this!
+                let ikm: [u8; 32] = [
+                    0x93, 0xad, 0x7e, 0x65, 0xde, 0xad, 0x05, 0x2a, 0x08, 0x3a, 0x91, 0x0c, 0x8b, 0x72,
+                    0x85, 0x91, 0x46, 0x4c, 0xca, 0x56, 0x60, 0x5b, 0xb0, 0x56, 0xed, 0xfe, 0x2b, 0x60,
+                    0xa6, 0x3c, 0x48, 0x99,
+                ];
+
+                let sk = blst::SecretKey::key_gen(&ikm, &[]).unwrap();
+
+                let msg = b"hello foo";
+                let sig = sk.sign(msg, DST, &[]);
+                BLS12381Signature {
+                    sig,
+                    bytes: OnceCell::new(),
+                    subgroup_checked: subgroup_checked(),
+                }
+            }
+        }
+
+        impl Display for BLS12381Signature {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                write!(f, "{}", Base64::encode_string(self.as_ref()))
+            }
+        }
+
+        impl FromStr for BLS12381Signature {
+            type Err = FastCryptoError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                from_base64_str(s)
+            }
+        }
+
+        impl Authenticator for BLS12381Signature {
+            type PubKey = BLS12381PublicKey;
+            type PrivKey = BLS12381PrivateKey;
+        }
+
+        impl FixedLength for BLS12381Signature {
+            const LENGTH: usize = BLS_SIGNATURE_LENGTH;
+        }
+
+        ///
+        /// Implement SigningKey
+        ///
+
+        impl AsRef<[u8]> for BLS12381PrivateKey {
+            fn as_ref(&self) -> &[u8] {
+                self.bytes
+                    .get_or_try_init::<_, eyre::Report>(|| Ok(self.privkey.to_bytes()))
+                    .expect("OnceCell invariant violated")
+            }
+        }
+
+        impl fmt::Debug for BLS12381PrivateKey {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "BLS12381PrivateKey(<redacted>)")
+            }
+        }
+
+        impl PartialEq for BLS12381PrivateKey {
+            fn eq(&self, other: &Self) -> bool {
+                self.as_ref().ct_eq(other.as_ref()).into()
+            }
+        }
+
+        impl Eq for BLS12381PrivateKey {}
+
+        impl ToFromBytes for BLS12381PrivateKey {
+            fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+                check_length::<Self>(bytes)?;
+                let privkey =
+                    blst::SecretKey::from_bytes(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+                Ok(BLS12381PrivateKey {
+                    privkey,
+                    bytes: OnceCell::new(),
+                })
+            }
+        }
+
+        impl FromStr for BLS12381PrivateKey {
+            type Err = FastCryptoError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                from_base64_str(s)
+            }
+        }
+
+        // There is a strong requirement for this specific impl. in Fab benchmarks
+        impl Serialize for BLS12381PrivateKey {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(&self.encode_base64())
+            }
+        }
+
+        // There is a strong requirement for this specific impl. in Fab benchmarks
+        impl<'de> Deserialize<'de> for BLS12381PrivateKey {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+                let value = Self::decode_base64(&s).map_err(|e| de::Error::custom(e.to_string()))?;
+                Ok(value)
+            }
+        }
+
+        impl SigningKey for BLS12381PrivateKey {
+            type PubKey = BLS12381PublicKey;
+            type Sig = BLS12381Signature;
+        }
+
+        impl FixedLength for BLS12381PrivateKey {
+            const LENGTH: usize = BLS_PRIVATE_KEY_LENGTH;
+        }
+
+        #[cfg(feature = "signing")]
+        impl Signer<BLS12381Signature> for BLS12381PrivateKey {
+            fn try_sign(&self, msg: &[u8]) -> Result<BLS12381Signature, signature::Error> {
+                let sig = self.privkey.sign(msg, DST, &[]);
+
+                Ok(BLS12381Signature {
+                    sig,
+                    bytes: OnceCell::new(),
+                    subgroup_checked: subgroup_checked(),
+                })
+            }
+        }
+
+        ///
+        /// Implement KeyPair
+        ///
+
+        #[cfg(feature = "signing")]
+        impl From<BLS12381PrivateKey> for BLS12381KeyPair {
+            fn from(secret: BLS12381PrivateKey) -> Self {
+                let name = BLS12381PublicKey::from(&secret);
+                BLS12381KeyPair { name, secret }
+            }
+        }
+
+        #[cfg(feature = "signing")]
+        impl EncodeDecodeBase64 for BLS12381KeyPair {
+            fn decode_base64(value: &str) -> Result<Self, eyre::Report> {
+                keypair_decode_base64(value)
+            }
+
+            fn encode_base64(&self) -> String {
+                let mut bytes: Vec<u8> = Vec::new();
+                bytes.extend_from_slice(self.secret.as_ref());
+                bytes.extend_from_slice(self.name.as_ref());
+                base64ct::Base64::encode_string(&bytes[..])
+            }
+        }
+
+        #[cfg(feature = "signing")]
+        impl KeyPair for BLS12381KeyPair {
+            type PubKey = BLS12381PublicKey;
+            type PrivKey = BLS12381PrivateKey;
+            type Sig = BLS12381Signature;
+
+            #[cfg(feature = "copy_key")]
+            fn copy(&self) -> Self {
+                BLS12381KeyPair {
+                    name: self.name.clone(),
+                    secret: BLS12381PrivateKey::from_bytes(self.secret.as_ref()).unwrap(),
+                }
+            }
+
+            fn public(&'_ self) -> &'_ Self::PubKey {
+                &self.name
+            }
+
+            fn private(self) -> Self::PrivKey {
+                BLS12381PrivateKey::from_bytes(self.secret.as_ref()).unwrap()
+            }
+
+            fn generate<R: rand::CryptoRng + rand::RngCore>(rng: &mut R) -> Self {
+                let mut ikm = [0u8; 32];
+                rng.fill_bytes(&mut ikm);
+                let privkey = blst::SecretKey::key_gen(&ikm, &[]).expect("ikm length should be higher");
+                let pubkey = privkey.sk_to_pk();
+                BLS12381KeyPair {
+                    name: BLS12381PublicKey {
+                        pubkey,
+                        bytes: OnceCell::new(),
+                    },
+                    secret: BLS12381PrivateKey {
+                        privkey,
+                        bytes: OnceCell::new(),
+                    },
+                }
+            }
+        }
+
+        #[cfg(feature = "signing")]
+        impl Signer<BLS12381Signature> for BLS12381KeyPair {
+            fn try_sign(&self, msg: &[u8]) -> Result<BLS12381Signature, signature::Error> {
+                let blst_priv: &blst::SecretKey = &self.secret.privkey;
+                let sig = blst_priv.sign(msg, DST, &[]);
+
+                Ok(BLS12381Signature {
+                    sig,
+                    bytes: OnceCell::new(),
+                    subgroup_checked: subgroup_checked(),
+                })
+            }
+        }
+
+        #[cfg(feature = "signing")]
+        impl FromStr for BLS12381KeyPair {
+            type Err = eyre::Report;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let kp = Self::decode_base64(s).map_err(|e| eyre::eyre!("{}", e.to_string()))?;
+                Ok(kp)
+            }
+        }
+
+        ///
+        /// Implement AggregateAuthenticator
+        ///
+
+        // Don't try to use this externally
+        impl AsRef<[u8]> for BLS12381AggregateSignature {
+            fn as_ref(&self) -> &[u8] {
+                match self.sig {
+                    Some(sig) => self
+                        .bytes
+                        .get_or_try_init::<_, eyre::Report>(|| Ok(sig.to_bytes()))
+                        .expect("OnceCell invariant violated"),
+                    None => &[],
+                }
+            }
+        }
+
+        impl std::hash::Hash for BLS12381AggregateSignature {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.as_ref().hash(state);
+            }
+        }
+
+        impl PartialEq for BLS12381AggregateSignature {
+            fn eq(&self, other: &Self) -> bool {
+                self.sig == other.sig
+            }
+        }
+
+        impl Eq for BLS12381AggregateSignature {}
+
+        impl Display for BLS12381AggregateSignature {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                write!(f, "{}", Base64::encode_string(self.as_ref()))
+            }
+        }
+
+        impl FromStr for BLS12381AggregateSignature {
+            type Err = FastCryptoError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                from_base64_str(s)
+            }
+        }
+
+        // see [#34](https://github.com/MystenLabs/narwhal/issues/34)
+        impl Default for BLS12381AggregateSignature {
+            fn default() -> Self {
+                BLS12381AggregateSignature {
+                    sig: None,
+                    bytes: OnceCell::new(),
+                }
+            }
+        }
+
+        impl AggregateAuthenticator for BLS12381AggregateSignature {
+            type PrivKey = BLS12381PrivateKey;
+            type PubKey = BLS12381PublicKey;
+            type Sig = BLS12381Signature;
+
+            /// Parse a key from its byte representation
+            fn aggregate(signatures: Vec<Self::Sig>) -> Result<Self, signature::Error> {
+                blst::AggregateSignature::aggregate(
+                    &signatures.iter().map(|x| &x.sig).collect::<Vec<_>>()[..],
+                    true,
+                )
+                .map(|sig| BLS12381AggregateSignature {
+                    sig: Some(sig.to_signature()),
+                    bytes: OnceCell::new(),
+                })
+                .map_err(|_| signature::Error::new())
+            }
+
+            fn add_signature(&mut self, signature: Self::Sig) -> Result<(), signature::Error> {
+                match self.sig {
+                    Some(ref mut sig) => {
+                        let mut aggr_sig = blst::AggregateSignature::from_signature(sig);
+                        aggr_sig
+                            .add_signature(&signature.sig, true)
+                            .map_err(|_| signature::Error::new())?;
+                        self.sig = Some(aggr_sig.to_signature());
+                        Ok(())
+                    }
+                    None => {
+                        self.sig = Some(signature.sig);
+                        Ok(())
+                    }
+                }
+            }
+
+            fn add_aggregate(&mut self, signature: Self) -> Result<(), signature::Error> {
+                match self.sig {
+                    Some(ref mut sig) => match signature.sig {
+                        Some(to_add) => {
+                            let result = blst::AggregateSignature::aggregate(&[sig, &to_add], true)
+                                .map_err(|_| signature::Error::new())?
+                                .to_signature();
+                            self.sig = Some(result);
+                            Ok(())
+                        }
+                        None => Ok(()),
+                    },
+                    None => {
+                        self.sig = signature.sig;
+                        Ok(())
+                    }
+                }
+            }
+
+            fn verify(
+                &self,
+                pks: &[<Self::Sig as Authenticator>::PubKey],
+                message: &[u8],
+            ) -> Result<(), signature::Error> {
+                let result = self
+                    .sig
+                    .ok_or_else(signature::Error::new)?
+                    .fast_aggregate_verify(
+                        true,
+                        message,
+                        DST,
+                        &pks.iter().map(|x| &x.pubkey).collect::<Vec<_>>()[..],
+                    );
+                if result != BLST_ERROR::BLST_SUCCESS {
+                    return Err(FastCryptoError::InvalidSignature.into());
+                }
+                Ok(())
+            }
+
+            fn batch_verify<'a>(
+                signatures: &[&Self],
+                pks: Vec<impl Iterator<Item = &'a Self::PubKey>>,
+                messages: &[&[u8]],
+            ) -> Result<(), signature::Error> {
+                if signatures.len() != pks.len() || signatures.len() != messages.len() {
+                    return Err(signature::Error::new());
+                }
+                let mut pk_iter = pks.into_iter();
+                for i in 0..signatures.len() {
+                    let sig = signatures[i].sig;
+                    let result = sig
+                        .ok_or_else(signature::Error::new)?
+                        .fast_aggregate_verify(
+                            true,
+                            messages[i],
+                            DST,
+                            &pk_iter
+                                .next()
+                                .unwrap()
+                                .map(|x| &x.pubkey)
+                                .collect::<Vec<_>>()[..],
+                        );
+                    if result != BLST_ERROR::BLST_SUCCESS {
+                        return Err(FastCryptoError::InvalidSignature.into());
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        impl BLS12381AggregateSignature {
+            /// Remove `signature` from the aggregate by subtracting it, which is cheaper than
+            /// aggregating the remaining signatures again. The caller must make sure that the
+            /// signature was added before, since otherwise the result is not the aggregate of any
+            /// set of signatures. If the result is the identity, e.g. after removing the only
+            /// signature, this becomes the empty aggregate. Fails with
+            /// [FastCryptoError::InvalidInput] if this is the empty aggregate.
+            pub fn remove(&mut self, signature: &BLS12381Signature) -> Result<(), FastCryptoError> {
+                let aggregate = self.sig.ok_or(FastCryptoError::InvalidInput)?;
+                let point = SignaturePoint::decompress(&aggregate.to_bytes())?
+                    - SignaturePoint::decompress(&signature.sig.to_bytes())?;
+                self.sig = if point == SignaturePoint::zero() {
+                    None
+                } else {
+                    Some(
+                        blst::Signature::from_bytes(&point.compress())
+                            .map_err(|_| FastCryptoError::GeneralOpaqueError)?,
+                    )
+                };
+                self.bytes = OnceCell::new();
+                Ok(())
+            }
+
+            /// Verify an aggregate of signatures where the i'th signature is by `pks[i]` on `messages[i]`.
+            ///
+            /// All pairs are checked together: the Miller loops of all pairings are accumulated and a
+            /// single final exponentiation is computed, which is much faster than computing a pairing per
+            /// pair. Fails with [FastCryptoError::InvalidInput] if there are no pairs, if `pks` and
+            /// `messages` differ in length, if a message appears twice or if this is the empty
+            /// aggregate.
+            ///
+            /// The messages must be distinct since the public keys are not required to come with a
+            /// proof of possession: otherwise, a rogue key chosen as a function of the other keys
+            /// would let its owner forge an aggregate on a message shared with honest signers.
+            pub fn verify_different_msgs(
+                &self,
+                pks: &[BLS12381PublicKey],
+                messages: &[&[u8]],
+            ) -> Result<(), FastCryptoError> {
+                if pks.is_empty()
+                    || pks.len() != messages.len()
+                    || messages.iter().collect::<BTreeSet<_>>().len() != messages.len()
+                {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                let result = self
+                    .sig
+                    .ok_or(FastCryptoError::InvalidInput)?
+                    .aggregate_verify(
+                        true,
+                        messages,
+                        DST,
+                        &pks.iter().map(|x| &x.pubkey).collect::<Vec<_>>()[..],
+                        true,
+                    );
+                if result != BLST_ERROR::BLST_SUCCESS {
+                    return Err(FastCryptoError::InvalidSignature);
+                }
+                Ok(())
+            }
+        }
+
+        impl BLS12381AggregatePublicKey {
+            /// Aggregate the public keys of `members`. Fails with [FastCryptoError::InvalidInput] if a key
+            /// is repeated.
+            pub fn new(members: &[BLS12381PublicKey]) -> Result<Self, FastCryptoError> {
+                let mut aggregate = BLS12381AggregatePublicKey {
+                    members: BTreeSet::new(),
+                    point: PublicKeyPoint::zero(),
+                    pubkey: None,
+                };
+                for pk in members {
+                    aggregate.add_point(pk)?;
+                }
+                aggregate.update_pubkey();
+                Ok(aggregate)
+            }
+
+            /// Add `pk` to the set of members. Fails with [FastCryptoError::InvalidInput] if it is
+            /// already a member.
+            pub fn add(&mut self, pk: &BLS12381PublicKey) -> Result<(), FastCryptoError> {
+                self.add_point(pk)?;
+                self.update_pubkey();
+                Ok(())
+            }
+
+            /// Remove `pk` from the set of members. Fails with [FastCryptoError::InvalidInput] if it is
+            /// not a member.
+            pub fn remove(&mut self, pk: &BLS12381PublicKey) -> Result<(), FastCryptoError> {
+                if !self.members.remove(pk) {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                self.point = self.point - public_key_to_point(&pk.pubkey);
+                self.update_pubkey();
+                Ok(())
+            }
+
+            pub fn contains(&self, pk: &BLS12381PublicKey) -> bool {
+                self.members.contains(pk)
+            }
+
+            pub fn len(&self) -> usize {
+                self.members.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.members.is_empty()
+            }
+
+            /// Verify an aggregate signature on `message` by all members. This is equivalent to
+            /// [AggregateAuthenticator::verify] with the public keys of all members, but skips
+            /// aggregating them. Fails with [FastCryptoError::InvalidInput] if there are no members or if
+            /// `signature` is the empty aggregate.
+            pub fn verify(
+                &self,
+                message: &[u8],
+                signature: &BLS12381AggregateSignature,
+            ) -> Result<(), FastCryptoError> {
+                let pubkey = self.pubkey.as_ref().ok_or(FastCryptoError::InvalidInput)?;
+                // The members were validated when their keys were parsed.
+                let result = signature.sig.ok_or(FastCryptoError::InvalidInput)?.verify(
+                    true,
+                    message,
+                    DST,
+                    &[],
+                    pubkey,
+                    false,
+                );
+                if result != BLST_ERROR::BLST_SUCCESS {
+                    return Err(FastCryptoError::InvalidSignature);
+                }
+                Ok(())
+            }
+
+            fn add_point(&mut self, pk: &BLS12381PublicKey) -> Result<(), FastCryptoError> {
+                if !self.members.insert(pk.clone()) {
+                    return Err(FastCryptoError::InvalidInput);
+                }
+                self.point = self.point + public_key_to_point(&pk.pubkey);
+                Ok(())
+            }
+
+            fn update_pubkey(&mut self) {
+                self.pubkey = if self.members.is_empty() {
+                    None
+                } else {
+                    Some(point_to_public_key(&self.point))
+                };
+            }
+        }
+
+        ///
+        /// Implement VerifyingKeyBytes
+        ///
+
+        impl TryFrom<BLS12381PublicKeyBytes> for BLS12381PublicKey {
+            type Error = signature::Error;
+
+            fn try_from(bytes: BLS12381PublicKeyBytes) -> Result<BLS12381PublicKey, Self::Error> {
+                BLS12381PublicKey::from_bytes(bytes.as_ref()).map_err(|_| Self::Error::new())
+            }
+        }
+
+        impl From<&BLS12381PublicKey> for BLS12381PublicKeyBytes {
+            fn from(pk: &BLS12381PublicKey) -> BLS12381PublicKeyBytes {
+                BLS12381PublicKeyBytes::from_bytes(pk.as_ref()).unwrap()
+            }
+        }
+
+        impl TryFrom<&[u8]> for BLS12381PublicKey {
+            type Error = signature::Error;
+
+            fn try_from(bytes: &[u8]) -> Result<BLS12381PublicKey, Self::Error> {
+                <BLS12381PublicKey as ToFromBytes>::from_bytes(bytes)
+            }
+        }
+
+        impl TryFrom<&[u8]> for BLS12381PrivateKey {
+            type Error = signature::Error;
+
+            fn try_from(bytes: &[u8]) -> Result<BLS12381PrivateKey, Self::Error> {
+                <BLS12381PrivateKey as ToFromBytes>::from_bytes(bytes)
+            }
+        }
+
+        impl TryFrom<&[u8]> for BLS12381Signature {
+            type Error = signature::Error;
+
+            fn try_from(bytes: &[u8]) -> Result<BLS12381Signature, Self::Error> {
+                <BLS12381Signature as ToFromBytes>::from_bytes(bytes)
+            }
+        }
+
+        impl TryFrom<&[u8]> for BLS12381AggregateSignature {
+            type Error = signature::Error;
+
+            fn try_from(bytes: &[u8]) -> Result<BLS12381AggregateSignature, Self::Error> {
+                <BLS12381AggregateSignature as ToFromBytes>::from_bytes(bytes)
+            }
+        }
+
+        impl zeroize::Zeroize for BLS12381PrivateKey {
+            fn zeroize(&mut self) {
+                self.bytes.take().zeroize();
+                self.privkey.zeroize();
+            }
+        }
+
+        impl zeroize::ZeroizeOnDrop for BLS12381PrivateKey {}
+
+        impl Drop for BLS12381PrivateKey {
+            fn drop(&mut self) {
+                self.zeroize();
+            }
+        }
+
+        #[cfg(feature = "signing")]
+        impl zeroize::Zeroize for BLS12381KeyPair {
+            fn zeroize(&mut self) {
+                self.secret.zeroize()
+            }
+        }
+
+        #[cfg(feature = "signing")]
+        impl zeroize::ZeroizeOnDrop for BLS12381KeyPair {}
+
+        #[cfg(feature = "signing")]
+        impl Drop for BLS12381KeyPair {
+            fn drop(&mut self) {
+                self.zeroize();
+            }
+        }
+
+        impl FixedLength for BLS12381AggregateSignature {
+            const LENGTH: usize = BLS_SIGNATURE_LENGTH;
+        }
+
+        impl ToFromBytes for BLS12381AggregateSignature {
+            fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+                check_length::<Self>(bytes)?;
+                let sig = blst::Signature::sig_validate(bytes, true)
+                    .map_err(|_| FastCryptoError::InvalidInput)?;
+                Ok(BLS12381AggregateSignature {
+                    sig: Some(sig),
+                    bytes: OnceCell::new(),
+                })
+            }
+        }
+
+    };
+}
+
+pub mod min_pk;
+pub mod min_sig;
+
+pub use min_sig::*;
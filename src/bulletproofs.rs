@@ -9,9 +9,11 @@ use curve25519_dalek_ng::{
 };
 use merlin::Transcript;
 use once_cell::sync::OnceCell;
+#[cfg(feature = "signing")]
+use rand::{thread_rng, CryptoRng, RngCore};
 use serde::{de, Deserialize, Serialize};
 
-use crate::traits::ToFromBytes;
+use crate::traits::{check_length, FixedLength, ToFromBytes};
 
 //
 // Pedersen commitments
@@ -73,11 +75,13 @@ impl AsRef<[u8]> for PedersenCommitment {
     }
 }
 
+impl FixedLength for PedersenCommitment {
+    const LENGTH: usize = PEDERSEN_COMMITMENT_LENGTH;
+}
+
 impl ToFromBytes for PedersenCommitment {
     fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
-        if bytes.len() != PEDERSEN_COMMITMENT_LENGTH {
-            return Err(signature::Error::new());
-        }
+        check_length::<Self>(bytes)?;
         let point = CompressedRistretto::from_slice(bytes);
         let decompressed_point = point.decompress().ok_or_else(signature::Error::new)?;
 
@@ -141,12 +145,26 @@ pub struct BulletproofsRangeProof {
 impl BulletproofsRangeProof {
     /// Prove that the value is an unsigned integer with bit length bits, this is equivalent
     /// to proving that the value is an integer within the range [0, 2^bits)
-    /// Function only works for bits = 8, 16, 32, 64.
+    /// Function only works for bits = 8, 16, 32, 64. Only available with the `signing` feature.
+    #[cfg(feature = "signing")]
     pub fn prove_bit_length(
         value: u64,
         blinding: [u8; 32],
         bits: usize,
         domain: &'static [u8],
+    ) -> Result<(PedersenCommitment, Self), signature::Error> {
+        Self::prove_bit_length_with_rng(value, blinding, bits, domain, &mut thread_rng())
+    }
+
+    /// Same as [BulletproofsRangeProof::prove_bit_length], but the randomness used by the prover
+    /// is drawn from the given rng.
+    #[cfg(feature = "signing")]
+    pub fn prove_bit_length_with_rng<R: CryptoRng + RngCore>(
+        value: u64,
+        blinding: [u8; 32],
+        bits: usize,
+        domain: &'static [u8],
+        rng: &mut R,
     ) -> Result<(PedersenCommitment, Self), signature::Error> {
         // Although this is also checked in the bulletproofs library, we check again
         // to avoid unexpected behaviour in the case of library updates
@@ -160,13 +178,14 @@ impl BulletproofsRangeProof {
         let mut prover_transcript = Transcript::new(domain);
         let blinding = Scalar::from_bits(blinding);
 
-        let (proof, commitment) = RangeProof::prove_single(
+        let (proof, commitment) = RangeProof::prove_single_with_rng(
             &bp_gens,
             &pc_gens,
             &mut prover_transcript,
             value,
             &blinding,
             bits,
+            rng,
         )
         .map_err(|_| signature::Error::new())?;
 
@@ -0,0 +1,120 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains an implementation of the ChaCha20-Poly1305 authenticated encryption
+//! scheme ([RFC 8439](https://www.rfc-editor.org/rfc/rfc8439)), backed by the
+//! [chacha20poly1305](https://docs.rs/chacha20poly1305) crate. It is a drop-in alternative to
+//! [crate::aes::Aes256Gcm] which is faster on hardware without AES instructions.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::aes::Nonce;
+//! use fastcrypto::chacha20poly1305::{ChaCha20Poly1305, ChaCha20Poly1305Key};
+//! use fastcrypto::traits::Aead;
+//! use rand::thread_rng;
+//! # fn main() {
+//!     let key = ChaCha20Poly1305Key::generate(&mut thread_rng());
+//!     let nonce = Nonce::generate(&mut thread_rng());
+//!     let ciphertext = ChaCha20Poly1305::encrypt(&key, &nonce, b"header", b"Hello, world!");
+//!     let plaintext = ChaCha20Poly1305::decrypt(&key, &nonce, b"header", &ciphertext).unwrap();
+//!     assert_eq!(plaintext, b"Hello, world!");
+//! # }
+//! ```
+
+use crate::{
+    aes::Nonce,
+    error::FastCryptoError,
+    traits::{check_length, Aead, FixedLength, ToFromBytes},
+};
+use ::chacha20poly1305::{
+    aead::{generic_array::GenericArray, Aead as _, KeyInit, Payload},
+    ChaCha20Poly1305 as ExternalChaCha20Poly1305,
+};
+use rand::{CryptoRng, RngCore};
+use std::fmt::{self, Debug};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub const CHACHA20_POLY1305_KEY_LENGTH: usize = 32;
+pub const CHACHA20_POLY1305_NONCE_LENGTH: usize = 12;
+pub const CHACHA20_POLY1305_TAG_LENGTH: usize = 16;
+
+/// A 256 bit ChaCha20-Poly1305 key. The key material is zeroized on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct ChaCha20Poly1305Key([u8; CHACHA20_POLY1305_KEY_LENGTH]);
+
+impl ChaCha20Poly1305Key {
+    pub fn new(bytes: [u8; CHACHA20_POLY1305_KEY_LENGTH]) -> Self {
+        ChaCha20Poly1305Key(bytes)
+    }
+
+    pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; CHACHA20_POLY1305_KEY_LENGTH];
+        rng.fill_bytes(&mut bytes);
+        ChaCha20Poly1305Key(bytes)
+    }
+}
+
+impl AsRef<[u8]> for ChaCha20Poly1305Key {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Debug for ChaCha20Poly1305Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ChaCha20Poly1305Key(<redacted>)")
+    }
+}
+
+impl FixedLength for ChaCha20Poly1305Key {
+    const LENGTH: usize = CHACHA20_POLY1305_KEY_LENGTH;
+}
+
+impl ToFromBytes for ChaCha20Poly1305Key {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
+        let mut key = [0u8; CHACHA20_POLY1305_KEY_LENGTH];
+        key.copy_from_slice(bytes);
+        Ok(ChaCha20Poly1305Key(key))
+    }
+}
+
+/// ChaCha20-Poly1305 with a 96 bit nonce and a 128 bit authentication tag.
+pub struct ChaCha20Poly1305;
+
+impl Aead for ChaCha20Poly1305 {
+    type Key = ChaCha20Poly1305Key;
+    type Nonce = Nonce<CHACHA20_POLY1305_NONCE_LENGTH>;
+
+    fn encrypt(key: &Self::Key, nonce: &Self::Nonce, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        ExternalChaCha20Poly1305::new(GenericArray::from_slice(key.as_ref()))
+            .encrypt(
+                GenericArray::from_slice(nonce.as_ref()),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .expect("plaintext is within the length supported by ChaCha20-Poly1305")
+    }
+
+    fn decrypt(
+        key: &Self::Key,
+        nonce: &Self::Nonce,
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, FastCryptoError> {
+        if ciphertext.len() < CHACHA20_POLY1305_TAG_LENGTH {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        ExternalChaCha20Poly1305::new(GenericArray::from_slice(key.as_ref()))
+            .decrypt(
+                GenericArray::from_slice(nonce.as_ref()),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| FastCryptoError::GeneralOpaqueError)
+    }
+}
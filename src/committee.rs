@@ -0,0 +1,81 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a helper for deciding whether a set of signers of a committee holds enough
+//! weight to form a quorum, e.g. before verifying the (aggregate) signature of a certificate.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::bls12381::BLS12381KeyPair;
+//! use fastcrypto::committee::Committee;
+//! use fastcrypto::traits::KeyPair;
+//! use rand::thread_rng;
+//! # fn main() {
+//!     let kps: Vec<_> = (0..4).map(|_| BLS12381KeyPair::generate(&mut thread_rng())).collect();
+//!     let committee = Committee::new(kps.iter().map(|kp| (kp.public().clone(), 1)));
+//!     let signers = kps[..3].iter().map(|kp| kp.public());
+//!     assert!(committee.meets_quorum(signers, committee.quorum_threshold()));
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::traits::VerifyingKey;
+
+pub type Stake = u64;
+
+/// The sum of the weights of several members, which is wide enough to never overflow.
+pub type TotalStake = u128;
+
+/// The members of a committee, each with a weight (stake).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Committee<PK: VerifyingKey> {
+    weights: BTreeMap<PK, Stake>,
+}
+
+impl<PK: VerifyingKey> Committee<PK> {
+    /// Create a committee from its members and their weights. If a member is listed more than
+    /// once, the last weight is used.
+    pub fn new(weights: impl IntoIterator<Item = (PK, Stake)>) -> Self {
+        Self {
+            weights: weights.into_iter().collect(),
+        }
+    }
+
+    /// The weight of a member, or `None` if `pk` is not in the committee.
+    pub fn weight(&self, pk: &PK) -> Option<Stake> {
+        self.weights.get(pk).copied()
+    }
+
+    /// The total weight of all members.
+    pub fn total_weight(&self) -> TotalStake {
+        self.weights.values().map(|w| *w as TotalStake).sum()
+    }
+
+    /// The smallest weight that is more than two thirds of the total weight, i.e. 2f+1 if the
+    /// total weight is 3f+1.
+    pub fn quorum_threshold(&self) -> TotalStake {
+        2 * self.total_weight() / 3 + 1
+    }
+
+    /// The total weight of the given signers. Every member is counted once, even if it appears
+    /// more than once, and signers that are not members of the committee do not count.
+    pub fn present_weight<'a>(&self, signers: impl IntoIterator<Item = &'a PK>) -> TotalStake {
+        signers
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|pk| self.weight(pk))
+            .map(|w| w as TotalStake)
+            .sum()
+    }
+
+    /// Whether the weight of the given signers is at least `threshold`.
+    pub fn meets_quorum<'a>(
+        &self,
+        signers: impl IntoIterator<Item = &'a PK>,
+        threshold: TotalStake,
+    ) -> bool {
+        self.present_weight(signers) >= threshold
+    }
+}
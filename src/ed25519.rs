@@ -1,9 +1,20 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use base64ct::{Base64, Encoding};
+#[cfg(feature = "signing")]
+use curve25519_dalek_ng::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek_ng::{
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+    traits::IsIdentity,
+};
 use ed25519_consensus::{batch, VerificationKeyBytes};
 use eyre::eyre;
 use once_cell::sync::OnceCell;
+use pkcs8::{
+    der::{asn1::OctetStringRef, Decode, Encode},
+    AlgorithmIdentifier, ObjectIdentifier, PrivateKeyInfo,
+};
 use serde::{
     de::{self, MapAccess, SeqAccess, Visitor},
     ser::SerializeStruct,
@@ -11,21 +22,32 @@ use serde::{
 };
 use serde_bytes::{ByteBuf, Bytes};
 use serde_with::serde_as;
-use signature::{rand_core::OsRng, Signature, Signer, Verifier};
+#[cfg(feature = "signing")]
+use signature::Signer;
+use signature::{
+    rand_core::{CryptoRng, OsRng, RngCore},
+    Signature, Verifier,
+};
+use spki::SubjectPublicKeyInfo;
 use std::{
     fmt::{self, Display},
     str::FromStr,
 };
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 use crate::{
+    error::FastCryptoError,
+    hash::{HashFunction, Sha512},
     pubkey_bytes::PublicKeyBytes,
-    serde_helpers::{keypair_decode_base64, Ed25519Signature as Ed25519Sig},
+    serde_helpers::Ed25519Signature as Ed25519Sig,
     traits::{
-        AggregateAuthenticator, Authenticator, EncodeDecodeBase64, KeyPair, SigningKey,
-        ToFromBytes, VerifyingKey,
+        check_length, from_base64_str, AggregateAuthenticator, Authenticator, EncodeDecodeBase64,
+        FixedLength, SigningKey, ToFromBytes, VerifyingKey,
     },
 };
+#[cfg(feature = "signing")]
+use crate::{serde_helpers::keypair_decode_base64, traits::KeyPair};
 
 pub const ED25519_PRIVATE_KEY_LENGTH: usize = 32;
 pub const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
@@ -43,10 +65,14 @@ pub struct Ed25519PublicKey(pub ed25519_consensus::VerificationKey);
 
 pub type Ed25519PublicKeyBytes = PublicKeyBytes<Ed25519PublicKey, { Ed25519PublicKey::LENGTH }>;
 
-#[derive(Debug, Zeroize, ZeroizeOnDrop)]
+#[derive(Zeroize, ZeroizeOnDrop)]
 pub struct Ed25519PrivateKey(pub ed25519_consensus::SigningKey);
 
+/// The algorithm identifier of Ed25519 keys from RFC 8410.
+const ED25519_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+
 // There is a strong requirement for this specific impl. in Fab benchmarks
+#[cfg(feature = "signing")]
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type")] // necessary so as not to deser under a != type
 pub struct Ed25519KeyPair {
@@ -54,7 +80,7 @@ pub struct Ed25519KeyPair {
     secret: Ed25519PrivateKey,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct Ed25519Signature {
     pub sig: ed25519_consensus::Signature,
     // Helps implementing AsRef<[u8]>.
@@ -79,12 +105,28 @@ impl<'a> From<&'a Ed25519PrivateKey> for Ed25519PublicKey {
 impl VerifyingKey for Ed25519PublicKey {
     type PrivKey = Ed25519PrivateKey;
     type Sig = Ed25519Signature;
-    const LENGTH: usize = ED25519_PUBLIC_KEY_LENGTH;
 
     fn verify_batch_empty_fail(
         msg: &[u8],
         pks: &[Self],
         sigs: &[Self::Sig],
+    ) -> Result<(), eyre::Report> {
+        Self::verify_batch_empty_fail_with_rng(msg, pks, sigs, &mut OsRng)
+    }
+}
+
+impl FixedLength for Ed25519PublicKey {
+    const LENGTH: usize = ED25519_PUBLIC_KEY_LENGTH;
+}
+
+impl Ed25519PublicKey {
+    /// Same as [VerifyingKey::verify_batch_empty_fail], but the random scalars used to combine
+    /// the signatures are drawn from the given rng.
+    pub fn verify_batch_empty_fail_with_rng<R: CryptoRng + RngCore>(
+        msg: &[u8],
+        pks: &[Self],
+        sigs: &[Ed25519Signature],
+        rng: &mut R,
     ) -> Result<(), eyre::Report> {
         if sigs.is_empty() {
             return Err(eyre!("Critical Error! This behavious can signal something dangerous, and that someone may be trying to bypass signature verification through providing empty batches."));
@@ -102,26 +144,259 @@ impl VerifyingKey for Ed25519PublicKey {
             batch.queue((vk_bytes, sigs[i].sig, msg))
         }
         batch
-            .verify(&mut OsRng)
+            .verify(rng)
             .map_err(|_| eyre!("Signature verification failed"))
     }
 }
 
+/// Verifies a batch of signatures which are added one at a time, e.g. while streaming them in,
+/// without collecting them into slices first. Each queued signature is folded into the batch
+/// immediately, so neither the messages nor the signatures are kept around.
+#[derive(Default)]
+pub struct Ed25519BatchVerifier {
+    batch: batch::Verifier,
+    size: usize,
+}
+
+impl Ed25519BatchVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a signature on `msg` by `pk` to the batch.
+    pub fn queue(&mut self, pk: &Ed25519PublicKey, msg: &[u8], sig: &Ed25519Signature) {
+        self.batch
+            .queue((VerificationKeyBytes::from(pk.0), sig.sig, msg));
+        self.size += 1;
+    }
+
+    /// The number of signatures queued so far.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Verify all queued signatures at once. As with [VerifyingKey::verify_batch_empty_fail], an
+    /// empty batch is rejected.
+    pub fn verify(self) -> Result<(), FastCryptoError> {
+        self.verify_with_rng(&mut OsRng)
+    }
+
+    /// Same as [Ed25519BatchVerifier::verify], but the random scalars used to combine the
+    /// signatures are drawn from the given rng.
+    pub fn verify_with_rng<R: CryptoRng + RngCore>(
+        self,
+        rng: &mut R,
+    ) -> Result<(), FastCryptoError> {
+        if self.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.batch
+            .verify(rng)
+            .map_err(|_| FastCryptoError::InvalidSignature)
+    }
+}
+
 impl Verifier<Ed25519Signature> for Ed25519PublicKey {
     // Compliant to ZIP215: https://zips.z.cash/protocol/protocol.pdf#concreteed25519
     fn verify(&self, msg: &[u8], signature: &Ed25519Signature) -> Result<(), signature::Error> {
         self.0
             .verify(&signature.sig, msg)
-            .map_err(|_| signature::Error::new())
+            .map_err(|_| FastCryptoError::InvalidSignature.into())
     }
 }
 
 impl ToFromBytes for Ed25519PublicKey {
+    /// Only the canonical encoding of a point is accepted, so that all implementations agree on
+    /// which byte strings are valid keys. Use [Ed25519PublicKey::from_bytes_unchecked] to also
+    /// accept non-canonical encodings.
     fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        let pk = Self::from_bytes_unchecked(bytes)?;
+        if !is_canonical_encoding(bytes) {
+            return Err(FastCryptoError::InvalidInput.into());
+        }
+        Ok(pk)
+    }
+}
+
+impl Ed25519PublicKey {
+    /// Parse a public key, also accepting the non-canonical encodings of points which are rejected
+    /// by [ToFromBytes::from_bytes], i.e., encodings with `y >= p` or with `x = 0` and the sign
+    /// bit set, as ZIP 215 does. Only use this to parse keys which other ZIP 215 implementations
+    /// have already accepted.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
         ed25519_consensus::VerificationKey::try_from(bytes)
             .map(Ed25519PublicKey)
-            .map_err(|_| signature::Error::new())
+            .map_err(|_| FastCryptoError::InvalidInput.into())
+    }
+
+    /// Check whether `bytes` is a valid encoding of a public key, i.e., whether
+    /// [ToFromBytes::from_bytes] would succeed, without constructing the key.
+    pub fn is_valid_bytes(bytes: &[u8]) -> bool {
+        bytes.len() == Self::LENGTH
+            && is_canonical_encoding(bytes)
+            && ed25519_consensus::VerificationKey::try_from(bytes).is_ok()
+    }
+
+    /// Parse a public key from a DER encoded X.509 `SubjectPublicKeyInfo` as specified in
+    /// RFC 8410. Fails with [FastCryptoError::InvalidInput] if the encoding is invalid or if the
+    /// key is not an Ed25519 key.
+    pub fn from_spki_der(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let info =
+            SubjectPublicKeyInfo::from_der(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+        if info.algorithm.oid != ED25519_OID || info.algorithm.parameters.is_some() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Self::from_bytes(info.subject_public_key).map_err(|_| FastCryptoError::InvalidInput)
     }
+
+    /// Encode this key as a DER encoded X.509 `SubjectPublicKeyInfo` as specified in RFC 8410.
+    pub fn to_spki_der(&self) -> Vec<u8> {
+        SubjectPublicKeyInfo {
+            algorithm: AlgorithmIdentifier {
+                oid: ED25519_OID,
+                parameters: None,
+            },
+            subject_public_key: self.as_ref(),
+        }
+        .to_vec()
+        .expect("SubjectPublicKeyInfo is encodable")
+    }
+
+    /// Verify a signature with the strict verification equation of RFC 8032 and reject the
+    /// encodings which permit malleability.
+    ///
+    /// The default [Verifier::verify] follows [ZIP 215](https://zips.z.cash/zip-0215): it uses the
+    /// cofactored equation `[8][s]B = [8]R + [8][k]A` and accepts small order and non-canonical
+    /// encodings of `A` and `R`, which gives a validity criterion that all implementations of ZIP
+    /// 215 agree on, also in batches. This method instead uses the cofactorless equation
+    /// `[s]B = R + [k]A`, and fails with [FastCryptoError::InvalidSignature] if `s` is not
+    /// canonical, if `A` or `R` is not canonically encoded or if either of them has small order.
+    /// Every signature accepted here is also accepted by [Verifier::verify], but not vice versa.
+    pub fn verify_strict(
+        &self,
+        msg: &[u8],
+        signature: &Ed25519Signature,
+    ) -> Result<(), FastCryptoError> {
+        let sig_bytes = signature.sig.to_bytes();
+        let (r_bytes, s_bytes) = sig_bytes.split_at(32);
+        let a = decompress_strict(self.as_ref()).ok_or(FastCryptoError::InvalidSignature)?;
+        let r = decompress_strict(r_bytes).ok_or(FastCryptoError::InvalidSignature)?;
+        let s = Scalar::from_canonical_bytes(s_bytes.try_into().expect("length is 32"))
+            .ok_or(FastCryptoError::InvalidSignature)?;
+
+        let mut hasher = Sha512::default();
+        hasher.update(r_bytes);
+        hasher.update(self.as_ref());
+        hasher.update(msg);
+        let k = Scalar::from_bytes_mod_order_wide(&hasher.finalize().0);
+
+        // R' = [s]B - [k]A
+        let expected_r = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-a, &s);
+        if expected_r == r {
+            Ok(())
+        } else {
+            Err(FastCryptoError::InvalidSignature)
+        }
+    }
+
+    /// Verify an Ed25519ph signature created by [Ed25519KeyPair::sign_prehashed] on the SHA-512
+    /// hash `prehash` of a message, with the same context. Like [Verifier::verify], this uses the
+    /// cofactored verification equation. Fails with [FastCryptoError::InvalidInput] if the context
+    /// is longer than 255 bytes, and with [FastCryptoError::InvalidSignature] if the signature is
+    /// invalid.
+    pub fn verify_prehashed(
+        &self,
+        prehash: &[u8; 64],
+        context: Option<&[u8]>,
+        signature: &Ed25519Signature,
+    ) -> Result<(), FastCryptoError> {
+        self.verify_with_dom2(1, context.unwrap_or_default(), prehash, signature)
+    }
+
+    /// Verify an Ed25519ctx signature created by [Ed25519KeyPair::sign_with_context] on `msg`
+    /// with the same context. Like [Verifier::verify], this uses the cofactored verification
+    /// equation. Fails with [FastCryptoError::InvalidInput] if the context is longer than 255
+    /// bytes, and with [FastCryptoError::InvalidSignature] if the signature is invalid.
+    pub fn verify_with_context(
+        &self,
+        msg: &[u8],
+        context: &[u8],
+        signature: &Ed25519Signature,
+    ) -> Result<(), FastCryptoError> {
+        self.verify_with_dom2(0, context, msg, signature)
+    }
+
+    /// Verify a signature of the Ed25519 variant given by `dom2(phflag, context)` on `message`.
+    fn verify_with_dom2(
+        &self,
+        phflag: u8,
+        context: &[u8],
+        message: &[u8],
+        signature: &Ed25519Signature,
+    ) -> Result<(), FastCryptoError> {
+        let mut hasher = dom2_hasher(phflag, context)?;
+        let sig_bytes = signature.sig.to_bytes();
+        let (r_bytes, s_bytes) = sig_bytes.split_at(32);
+        let a = CompressedEdwardsY::from_slice(self.as_ref())
+            .decompress()
+            .ok_or(FastCryptoError::InvalidSignature)?;
+        let r = CompressedEdwardsY::from_slice(r_bytes)
+            .decompress()
+            .ok_or(FastCryptoError::InvalidSignature)?;
+        let s = Scalar::from_canonical_bytes(s_bytes.try_into().expect("length is 32"))
+            .ok_or(FastCryptoError::InvalidSignature)?;
+
+        hasher.update(r_bytes);
+        hasher.update(self.as_ref());
+        hasher.update(message);
+        let k = Scalar::from_bytes_mod_order_wide(&hasher.finalize().0);
+
+        // [8]([s]B - [k]A - R) = 0
+        let difference = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-a, &s) - r;
+        if difference.mul_by_cofactor().is_identity() {
+            Ok(())
+        } else {
+            Err(FastCryptoError::InvalidSignature)
+        }
+    }
+}
+
+/// The prefix of `dom2` from RFC 8032, which separates Ed25519ctx and Ed25519ph from Ed25519.
+const DOM2_PREFIX: &[u8] = b"SigEd25519 no Ed25519 collisions";
+
+/// A SHA-512 hasher which has processed `dom2(phflag, context)`. Fails with
+/// [FastCryptoError::InvalidInput] if the context is longer than 255 bytes.
+fn dom2_hasher(phflag: u8, context: &[u8]) -> Result<Sha512, FastCryptoError> {
+    let context_length = u8::try_from(context.len()).map_err(|_| FastCryptoError::InvalidInput)?;
+    let mut hasher = Sha512::default();
+    hasher.update(DOM2_PREFIX);
+    hasher.update([phflag, context_length]);
+    hasher.update(context);
+    Ok(hasher)
+}
+
+/// Returns true if `bytes` is the canonical encoding of a point. `bytes` must have length 32.
+fn is_canonical_encoding(bytes: &[u8]) -> bool {
+    let compressed = CompressedEdwardsY::from_slice(bytes);
+    compressed
+        .decompress()
+        .map_or(false, |point| point.compress() == compressed)
+}
+
+/// Decode an Edwards point, returning `None` if the encoding is not canonical or if the point has
+/// small order.
+fn decompress_strict(bytes: &[u8]) -> Option<EdwardsPoint> {
+    let compressed = CompressedEdwardsY::from_slice(bytes);
+    let point = compressed.decompress()?;
+    if point.is_small_order() || point.compress() != compressed {
+        return None;
+    }
+    Some(point)
 }
 
 impl AsRef<[u8]> for Ed25519PublicKey {
@@ -142,6 +417,14 @@ impl Display for Ed25519PublicKey {
     }
 }
 
+impl FromStr for Ed25519PublicKey {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_base64_str(s)
+    }
+}
+
 /// Missing in ed25519_consensus
 #[allow(clippy::derive_hash_xor_eq)] // ed25519_consensus's PartialEq is compatible
 impl std::hash::Hash for Ed25519PublicKey {
@@ -192,14 +475,66 @@ impl<'de> Deserialize<'de> for Ed25519PublicKey {
 impl SigningKey for Ed25519PrivateKey {
     type PubKey = Ed25519PublicKey;
     type Sig = Ed25519Signature;
+}
+
+impl FixedLength for Ed25519PrivateKey {
     const LENGTH: usize = ED25519_PRIVATE_KEY_LENGTH;
 }
 
 impl ToFromBytes for Ed25519PrivateKey {
     fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
         ed25519_consensus::SigningKey::try_from(bytes)
             .map(Ed25519PrivateKey)
-            .map_err(|_| signature::Error::new())
+            .map_err(|_| FastCryptoError::InvalidInput.into())
+    }
+}
+
+impl Ed25519PrivateKey {
+    /// Parse a private key from a DER encoded PKCS#8 `PrivateKeyInfo` as specified in RFC 8410,
+    /// e.g. one generated by `openssl genpkey -algorithm ed25519 -outform DER`. Fails with
+    /// [FastCryptoError::InvalidInput] if the encoding is invalid, if the key is not an Ed25519
+    /// key or if the public key it contains does not match the private key.
+    pub fn from_pkcs8_der(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let info = PrivateKeyInfo::from_der(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+        if info.algorithm.oid != ED25519_OID || info.algorithm.parameters.is_some() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        // The seed is wrapped in another OCTET STRING, the CurvePrivateKey.
+        let seed = OctetStringRef::from_der(info.private_key)
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        let sk = Self::from_bytes(seed.as_bytes()).map_err(|_| FastCryptoError::InvalidInput)?;
+        if let Some(public_key) = info.public_key {
+            if public_key != Ed25519PublicKey::from(&sk).as_ref() {
+                return Err(FastCryptoError::InvalidInput);
+            }
+        }
+        Ok(sk)
+    }
+
+    /// Encode this key as a DER encoded PKCS#8 `PrivateKeyInfo` as specified in RFC 8410.
+    pub fn to_pkcs8_der(&self) -> Zeroizing<Vec<u8>> {
+        let seed = Zeroizing::new(
+            OctetStringRef::new(self.as_ref())
+                .and_then(|seed| seed.to_vec())
+                .expect("CurvePrivateKey is encodable"),
+        );
+        let info = PrivateKeyInfo::new(
+            AlgorithmIdentifier {
+                oid: ED25519_OID,
+                parameters: None,
+            },
+            &seed,
+        );
+        Zeroizing::new(info.to_vec().expect("PrivateKeyInfo is encodable"))
+    }
+}
+
+impl FromStr for Ed25519PrivateKey {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_base64_str(s)
     }
 }
 
@@ -233,6 +568,9 @@ impl<'de> Deserialize<'de> for Ed25519PrivateKey {
 impl Authenticator for Ed25519Signature {
     type PubKey = Ed25519PublicKey;
     type PrivKey = Ed25519PrivateKey;
+}
+
+impl FixedLength for Ed25519Signature {
     const LENGTH: usize = ED25519_SIGNATURE_LENGTH;
 }
 
@@ -242,14 +580,55 @@ impl AsRef<[u8]> for Ed25519PrivateKey {
     }
 }
 
+impl fmt::Debug for Ed25519PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ed25519PrivateKey(<redacted>)")
+    }
+}
+
+impl PartialEq for Ed25519PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref().ct_eq(other.as_ref()).into()
+    }
+}
+
+impl Eq for Ed25519PrivateKey {}
+
 impl Signature for Ed25519Signature {
     fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
         ed25519_consensus::Signature::try_from(bytes)
             .map(|sig| Ed25519Signature {
                 sig,
                 bytes: OnceCell::new(),
             })
-            .map_err(|_| signature::Error::new())
+            .map_err(|_| FastCryptoError::InvalidInput.into())
+    }
+}
+
+impl Ed25519Signature {
+    /// Returns true if `s` is reduced modulo the group order. Signatures with a non-canonical `s`
+    /// are rejected by verification, and the encoding of `R` is bound by the hash of the
+    /// signature, so canonical signatures have a unique encoding.
+    pub fn is_canonical(&self) -> bool {
+        Scalar::from_canonical_bytes(self.s_bytes()).is_some()
+    }
+
+    /// Reduce `s` modulo the group order. Afterwards [Ed25519Signature::is_canonical] is true.
+    pub fn canonicalize(&mut self) {
+        if self.is_canonical() {
+            return;
+        }
+        let mut bytes = self.sig.to_bytes();
+        bytes[32..].copy_from_slice(Scalar::from_bytes_mod_order(self.s_bytes()).as_bytes());
+        self.sig = ed25519_consensus::Signature::from(bytes);
+        self.bytes = OnceCell::new();
+    }
+
+    fn s_bytes(&self) -> [u8; 32] {
+        let mut s = [0u8; 32];
+        s.copy_from_slice(&self.sig.to_bytes()[32..]);
+        s
     }
 }
 
@@ -261,12 +640,35 @@ impl AsRef<[u8]> for Ed25519Signature {
     }
 }
 
+impl std::hash::Hash for Ed25519Signature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
+    }
+}
+
+// The bytes are a cache of the encoding of sig, so they are ignored when comparing.
+impl PartialEq for Ed25519Signature {
+    fn eq(&self, other: &Self) -> bool {
+        self.sig == other.sig
+    }
+}
+
+impl Eq for Ed25519Signature {}
+
 impl Display for Ed25519Signature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         write!(f, "{}", Base64::encode_string(self.as_ref()))
     }
 }
 
+impl FromStr for Ed25519Signature {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_base64_str(s)
+    }
+}
+
 impl Default for Ed25519Signature {
     fn default() -> Self {
         <Ed25519Signature as Signature>::from_bytes(&[1u8; ED25519_SIGNATURE_LENGTH]).unwrap()
@@ -394,6 +796,15 @@ impl Display for Ed25519AggregateSignature {
     }
 }
 
+#[allow(clippy::derive_hash_xor_eq)] // ed25519_consensus's PartialEq is compatible
+impl std::hash::Hash for Ed25519AggregateSignature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for sig in &self.0 {
+            sig.to_bytes().hash(state);
+        }
+    }
+}
+
 impl AggregateAuthenticator for Ed25519AggregateSignature {
     type Sig = Ed25519Signature;
     type PrivKey = Ed25519PrivateKey;
@@ -429,7 +840,9 @@ impl AggregateAuthenticator for Ed25519AggregateSignature {
             batch.queue((vk_bytes, self.0[i], message));
         }
 
-        batch.verify(OsRng).map_err(|_| signature::Error::new())
+        batch
+            .verify(OsRng)
+            .map_err(|_| FastCryptoError::InvalidSignature.into())
     }
 
     fn batch_verify<'a>(
@@ -453,7 +866,9 @@ impl AggregateAuthenticator for Ed25519AggregateSignature {
                 batch.queue((vk_bytes, *sig, messages[i]));
             }
         }
-        batch.verify(OsRng).map_err(|_| signature::Error::new())
+        batch
+            .verify(OsRng)
+            .map_err(|_| FastCryptoError::InvalidSignature.into())
     }
 }
 
@@ -461,6 +876,7 @@ impl AggregateAuthenticator for Ed25519AggregateSignature {
 /// Implement KeyPair
 ///
 
+#[cfg(feature = "signing")]
 impl From<Ed25519PrivateKey> for Ed25519KeyPair {
     fn from(secret: Ed25519PrivateKey) -> Self {
         let name = Ed25519PublicKey::from(&secret);
@@ -468,6 +884,7 @@ impl From<Ed25519PrivateKey> for Ed25519KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
 impl EncodeDecodeBase64 for Ed25519KeyPair {
     fn decode_base64(value: &str) -> Result<Self, eyre::Report> {
         keypair_decode_base64(value)
@@ -481,6 +898,7 @@ impl EncodeDecodeBase64 for Ed25519KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
 impl KeyPair for Ed25519KeyPair {
     type PubKey = Ed25519PublicKey;
     type PrivKey = Ed25519PrivateKey;
@@ -511,6 +929,7 @@ impl KeyPair for Ed25519KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
 impl FromStr for Ed25519KeyPair {
     type Err = eyre::Report;
 
@@ -520,6 +939,7 @@ impl FromStr for Ed25519KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
 impl From<ed25519_consensus::SigningKey> for Ed25519KeyPair {
     fn from(kp: ed25519_consensus::SigningKey) -> Self {
         Ed25519KeyPair {
@@ -529,6 +949,7 @@ impl From<ed25519_consensus::SigningKey> for Ed25519KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
 impl Signer<Ed25519Signature> for Ed25519KeyPair {
     fn try_sign(&self, msg: &[u8]) -> Result<Ed25519Signature, signature::Error> {
         Ok(Ed25519Signature {
@@ -538,6 +959,71 @@ impl Signer<Ed25519Signature> for Ed25519KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
+impl Ed25519KeyPair {
+    /// Sign the SHA-512 hash `prehash` of a message with Ed25519ph (HashEdDSA) as specified in
+    /// RFC 8032, section 5.1, with an optional context of at most 255 bytes. This avoids holding
+    /// a large message in memory, but the signature is not a valid Ed25519 signature of the
+    /// message or of its hash, and must be verified with [Ed25519PublicKey::verify_prehashed].
+    /// Fails with [FastCryptoError::InvalidInput] if the context is too long.
+    pub fn sign_prehashed(
+        &self,
+        prehash: &[u8; 64],
+        context: Option<&[u8]>,
+    ) -> Result<Ed25519Signature, FastCryptoError> {
+        self.sign_with_dom2(1, context.unwrap_or_default(), prehash)
+    }
+
+    /// Sign `msg` with Ed25519ctx as specified in RFC 8032, section 5.1, with a domain separation
+    /// context of at most 255 bytes. The signature is only valid for this context and is not a
+    /// valid Ed25519 or Ed25519ph signature, so it must be verified with
+    /// [Ed25519PublicKey::verify_with_context]. RFC 8032 recommends a non-empty context. Fails
+    /// with [FastCryptoError::InvalidInput] if the context is too long.
+    pub fn sign_with_context(
+        &self,
+        msg: &[u8],
+        context: &[u8],
+    ) -> Result<Ed25519Signature, FastCryptoError> {
+        self.sign_with_dom2(0, context, msg)
+    }
+
+    /// Sign `message` with the Ed25519 variant given by `dom2(phflag, context)`.
+    fn sign_with_dom2(
+        &self,
+        phflag: u8,
+        context: &[u8],
+        message: &[u8],
+    ) -> Result<Ed25519Signature, FastCryptoError> {
+        let mut hasher = dom2_hasher(phflag, context)?;
+        let expanded = Zeroizing::new(Sha512::digest(self.secret.as_ref()).0);
+        let mut a_bytes = Zeroizing::new([0u8; 32]);
+        a_bytes.copy_from_slice(&expanded[..32]);
+        a_bytes[0] &= 248;
+        a_bytes[31] &= 127;
+        a_bytes[31] |= 64;
+        let a = Scalar::from_bits(*a_bytes);
+
+        hasher.update(&expanded[32..]);
+        hasher.update(message);
+        let r = Scalar::from_bytes_mod_order_wide(&hasher.finalize().0);
+        let r_bytes = (&r * &ED25519_BASEPOINT_TABLE).compress().to_bytes();
+
+        let mut hasher = dom2_hasher(phflag, context)?;
+        hasher.update(r_bytes);
+        hasher.update(self.name.as_ref());
+        hasher.update(message);
+        let k = Scalar::from_bytes_mod_order_wide(&hasher.finalize().0);
+
+        let mut sig_bytes = [0u8; ED25519_SIGNATURE_LENGTH];
+        sig_bytes[..32].copy_from_slice(&r_bytes);
+        sig_bytes[32..].copy_from_slice((r + k * a).as_bytes());
+        Ok(Ed25519Signature {
+            sig: ed25519_consensus::Signature::from(sig_bytes),
+            bytes: OnceCell::new(),
+        })
+    }
+}
+
 ///
 /// Implement VerifyingKeyBytes
 ///
@@ -546,10 +1032,7 @@ impl TryFrom<Ed25519PublicKeyBytes> for Ed25519PublicKey {
     type Error = signature::Error;
 
     fn try_from(bytes: Ed25519PublicKeyBytes) -> Result<Ed25519PublicKey, Self::Error> {
-        VerificationKeyBytes::try_from(bytes.as_ref())
-            .and_then(ed25519_consensus::VerificationKey::try_from)
-            .map(Ed25519PublicKey)
-            .map_err(|_| signature::Error::new())
+        Ed25519PublicKey::from_bytes(bytes.as_ref()).map_err(|_| signature::Error::new())
     }
 }
 
@@ -559,14 +1042,41 @@ impl From<&Ed25519PublicKey> for Ed25519PublicKeyBytes {
     }
 }
 
+impl TryFrom<&[u8]> for Ed25519PublicKey {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Ed25519PublicKey, Self::Error> {
+        <Ed25519PublicKey as ToFromBytes>::from_bytes(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Ed25519PrivateKey {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Ed25519PrivateKey, Self::Error> {
+        <Ed25519PrivateKey as ToFromBytes>::from_bytes(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Ed25519Signature {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Ed25519Signature, Self::Error> {
+        <Ed25519Signature as ToFromBytes>::from_bytes(bytes)
+    }
+}
+
+#[cfg(feature = "signing")]
 impl zeroize::Zeroize for Ed25519KeyPair {
     fn zeroize(&mut self) {
         self.secret.0.zeroize()
     }
 }
 
+#[cfg(feature = "signing")]
 impl zeroize::ZeroizeOnDrop for Ed25519KeyPair {}
 
+#[cfg(feature = "signing")]
 impl Drop for Ed25519KeyPair {
     fn drop(&mut self) {
         self.zeroize();
@@ -0,0 +1,90 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! ElGamal encryption of group elements over the [ristretto255](crate::groups::ristretto255) group.
+//!
+//! The scheme is additively homomorphic: the sum of two ciphertexts decrypts to the sum of the
+//! encrypted points.
+
+use crate::groups::{
+    ristretto255::{RistrettoPoint, RistrettoScalar},
+    GroupElement, Scalar,
+};
+use rand::{CryptoRng, RngCore};
+use std::{
+    fmt::{self, Debug},
+    ops::Add,
+};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// An ElGamal private key, i.e. a secret scalar `x`. The scalar is zeroized on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct ElGamalPrivateKey(RistrettoScalar);
+
+/// An ElGamal public key, i.e. the point `x * G`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElGamalPublicKey(RistrettoPoint);
+
+/// An ElGamal ciphertext `(r * G, M + r * pk)` of a plaintext point `M`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ciphertext {
+    pub ephemeral: RistrettoPoint,
+    pub masked: RistrettoPoint,
+}
+
+impl ElGamalPrivateKey {
+    pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        ElGamalPrivateKey(RistrettoScalar::rand(rng))
+    }
+}
+
+impl Debug for ElGamalPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ElGamalPrivateKey(<redacted>)")
+    }
+}
+
+// Constant time, so comparing secret keys does not leak where they differ.
+impl PartialEq for ElGamalPrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl Eq for ElGamalPrivateKey {}
+
+impl<'a> From<&'a ElGamalPrivateKey> for ElGamalPublicKey {
+    fn from(secret: &'a ElGamalPrivateKey) -> Self {
+        ElGamalPublicKey(RistrettoPoint::generator() * secret.0)
+    }
+}
+
+impl Add for Ciphertext {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Ciphertext {
+            ephemeral: self.ephemeral + rhs.ephemeral,
+            masked: self.masked + rhs.masked,
+        }
+    }
+}
+
+/// Encrypt the point `plaintext` under the public key `pk` using fresh randomness from `rng`.
+pub fn encrypt<R: CryptoRng + RngCore>(
+    pk: &ElGamalPublicKey,
+    plaintext: &RistrettoPoint,
+    rng: &mut R,
+) -> Ciphertext {
+    let r = RistrettoScalar::rand(rng);
+    Ciphertext {
+        ephemeral: RistrettoPoint::generator() * r,
+        masked: *plaintext + pk.0 * r,
+    }
+}
+
+/// Decrypt a ciphertext back to the encrypted point.
+pub fn decrypt(sk: &ElGamalPrivateKey, ct: &Ciphertext) -> RistrettoPoint {
+    ct.masked - ct.ephemeral * sk.0
+}
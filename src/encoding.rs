@@ -0,0 +1,76 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains the text encodings keys, signatures and other values can be given in, and
+//! a single entry point [EncodeDecode::decode] to parse a value from any of them.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PublicKey};
+//! use fastcrypto::encoding::{EncodeDecode, Encoding};
+//! use fastcrypto::traits::KeyPair;
+//! use rand::thread_rng;
+//! # fn main() {
+//!     let pk = Ed25519KeyPair::generate(&mut thread_rng()).public().clone();
+//!     let hex = pk.encode(Encoding::Hex);
+//!     assert_eq!(Ed25519PublicKey::decode(Encoding::Hex, &hex).unwrap(), pk);
+//! # }
+//! ```
+
+use base64ct::Encoding as _;
+
+use crate::{error::FastCryptoError, traits::ToFromBytes};
+
+/// A text encoding of binary data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Standard Base64 with padding (RFC 4648).
+    Base64,
+    /// Base58 with the Bitcoin alphabet.
+    Base58,
+    /// Hexadecimal, either lower or upper case, without a `0x` prefix.
+    Hex,
+}
+
+impl Encoding {
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Base64 => base64ct::Base64::encode_string(bytes),
+            Encoding::Base58 => bs58::encode(bytes).into_string(),
+            Encoding::Hex => hex::encode(bytes),
+        }
+    }
+
+    /// Decode a string in this encoding. Fails with [FastCryptoError::InvalidInput] if `value` is
+    /// not a valid encoding.
+    pub fn decode(&self, value: &str) -> Result<Vec<u8>, FastCryptoError> {
+        match self {
+            Encoding::Base64 => base64ct::Base64::decode_vec(value).ok(),
+            Encoding::Base58 => bs58::decode(value).into_vec().ok(),
+            Encoding::Hex => hex::decode(value).ok(),
+        }
+        .ok_or(FastCryptoError::InvalidInput)
+    }
+}
+
+/// Trait impl'd by all values with a byte representation, to encode them as and parse them from
+/// a string in any [Encoding].
+pub trait EncodeDecode: Sized {
+    fn encode(&self, encoding: Encoding) -> String;
+    fn decode(encoding: Encoding, value: &str) -> Result<Self, FastCryptoError>;
+}
+
+impl<T: ToFromBytes> EncodeDecode for T {
+    fn encode(&self, encoding: Encoding) -> String {
+        encoding.encode(self.as_bytes())
+    }
+
+    fn decode(encoding: Encoding, value: &str) -> Result<Self, FastCryptoError> {
+        let bytes = encoding.decode(value)?;
+        T::from_bytes(&bytes).map_err(|e| {
+            FastCryptoError::from_signature_error(&e)
+                .cloned()
+                .unwrap_or(FastCryptoError::InvalidInput)
+        })
+    }
+}
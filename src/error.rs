@@ -0,0 +1,100 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use thiserror::Error;
+
+/// The result type of fallible functions in fastcrypto.
+pub type FastCryptoResult<T> = Result<T, FastCryptoError>;
+
+/// Collection of errors to be used in fastcrypto.
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum FastCryptoError {
+    /// Invalid value was given to the function.
+    #[error("Invalid value was given to the function")]
+    InvalidInput,
+
+    /// Input had the wrong length.
+    #[error("Expected input of length {expected}, got {got}")]
+    InputLengthWrong { expected: usize, got: usize },
+
+    /// The input was well-formed, but the signature did not verify.
+    #[error("Signature verification failed")]
+    InvalidSignature,
+
+    /// Decryption with a key derived from a password failed, because the password is wrong or the
+    /// ciphertext has been modified.
+    #[error("Invalid password or corrupted ciphertext")]
+    InvalidPassword,
+
+    /// The element at the given position of a batch is invalid.
+    #[error("Invalid element at position {index} of the batch")]
+    InvalidBatchElement { index: usize },
+
+    /// The input was well-formed, but the proof did not verify.
+    #[error("Proof verification failed")]
+    InvalidProof,
+
+    /// General cryptographic error, e.g. a failed authentication, which is deliberately kept
+    /// opaque to callers.
+    #[error("General cryptographic error")]
+    GeneralOpaqueError,
+}
+
+impl FastCryptoError {
+    /// Returns the [FastCryptoError] carried by an error returned from the `signature` traits,
+    /// e.g. by `Verifier::verify` or `ToFromBytes::from_bytes`, if there is one.
+    pub fn from_signature_error(e: &signature::Error) -> Option<&FastCryptoError> {
+        std::error::Error::source(e).and_then(|source| source.downcast_ref())
+    }
+}
+
+impl From<FastCryptoError> for signature::Error {
+    fn from(e: FastCryptoError) -> Self {
+        signature::Error::from_source(e)
+    }
+}
+
+/// Errors returned by the `signature` traits carry a [FastCryptoError] if they come from this
+/// crate. Otherwise, e.g. for errors from k256, they are opaque and are most often returned when
+/// a signature does not verify.
+impl From<signature::Error> for FastCryptoError {
+    fn from(e: signature::Error) -> Self {
+        FastCryptoError::from_signature_error(&e)
+            .cloned()
+            .unwrap_or(FastCryptoError::InvalidSignature)
+    }
+}
+
+#[cfg(feature = "rust_secp256k1")]
+impl From<rust_secp256k1::Error> for FastCryptoError {
+    fn from(e: rust_secp256k1::Error) -> Self {
+        match e {
+            rust_secp256k1::Error::IncorrectSignature => FastCryptoError::InvalidSignature,
+            _ => FastCryptoError::InvalidInput,
+        }
+    }
+}
+
+impl From<k256::elliptic_curve::Error> for FastCryptoError {
+    fn from(_: k256::elliptic_curve::Error) -> Self {
+        FastCryptoError::InvalidInput
+    }
+}
+
+impl From<base64ct::Error> for FastCryptoError {
+    fn from(_: base64ct::Error) -> Self {
+        FastCryptoError::InvalidInput
+    }
+}
+
+impl From<hex::FromHexError> for FastCryptoError {
+    fn from(_: hex::FromHexError) -> Self {
+        FastCryptoError::InvalidInput
+    }
+}
+
+impl From<bs58::decode::Error> for FastCryptoError {
+    fn from(_: bs58::decode::Error) -> Self {
+        FastCryptoError::InvalidInput
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains [GenericSignature], which bundles a signature of any of the supported
+//! schemes with the public key (or multisig public key) it is verified against, and the
+//! [MessageAuthenticator] trait to verify it without knowing the scheme.
+//!
+//! A [GenericSignature] is serialized as a flag byte identifying the scheme followed by the
+//! public key and the signature, so the scheme can be determined before parsing the rest.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::ed25519::Ed25519KeyPair;
+//! use fastcrypto::generic_signature::{GenericSignature, MessageAuthenticator};
+//! use fastcrypto::traits::KeyPair;
+//! use rand::thread_rng;
+//! use signature::Signer;
+//! # fn main() {
+//!     let kp = Ed25519KeyPair::generate(&mut thread_rng());
+//!     let signature = GenericSignature::Ed25519 {
+//!         pk: kp.public().clone(),
+//!         sig: kp.sign(b"Hello, world!"),
+//!     };
+//!     assert!(signature.verify(b"Hello, world!").is_ok());
+//! # }
+//! ```
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use signature::Verifier;
+use std::fmt;
+
+use crate::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    error::FastCryptoError,
+    multisig::{MultiSig, MultiSigPublicKey},
+    secp256k1::{Secp256k1PublicKey, Secp256k1Signature},
+    traits::VerifyingKey,
+};
+
+pub const ED25519_FLAG: u8 = 0x00;
+pub const SECP256K1_FLAG: u8 = 0x01;
+pub const MULTISIG_FLAG: u8 = 0x02;
+
+/// Trait impl'd by values which carry both a signature and the key to verify it with, so they
+/// can be verified on a message without knowing the signature scheme.
+pub trait MessageAuthenticator {
+    /// Verify the signature on `msg`. Fails with [FastCryptoError::InvalidSignature] if the
+    /// signature is invalid.
+    fn verify(&self, msg: &[u8]) -> Result<(), FastCryptoError>;
+}
+
+impl<PK: VerifyingKey> MessageAuthenticator for (PK, PK::Sig) {
+    fn verify(&self, msg: &[u8]) -> Result<(), FastCryptoError> {
+        self.0
+            .verify(msg, &self.1)
+            .map_err(|_| FastCryptoError::InvalidSignature)
+    }
+}
+
+/// A signature of one of the supported schemes together with its public key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GenericSignature {
+    Ed25519 {
+        pk: Ed25519PublicKey,
+        sig: Ed25519Signature,
+    },
+    Secp256k1 {
+        pk: Secp256k1PublicKey,
+        sig: Secp256k1Signature,
+    },
+    MultiSig {
+        pk: MultiSigPublicKey,
+        sig: MultiSig,
+    },
+}
+
+impl GenericSignature {
+    /// The flag byte identifying the scheme of this signature.
+    pub fn flag(&self) -> u8 {
+        match self {
+            GenericSignature::Ed25519 { .. } => ED25519_FLAG,
+            GenericSignature::Secp256k1 { .. } => SECP256K1_FLAG,
+            GenericSignature::MultiSig { .. } => MULTISIG_FLAG,
+        }
+    }
+}
+
+impl MessageAuthenticator for GenericSignature {
+    fn verify(&self, msg: &[u8]) -> Result<(), FastCryptoError> {
+        match self {
+            GenericSignature::Ed25519 { pk, sig } => pk
+                .verify(msg, sig)
+                .map_err(|_| FastCryptoError::InvalidSignature),
+            GenericSignature::Secp256k1 { pk, sig } => pk
+                .verify(msg, sig)
+                .map_err(|_| FastCryptoError::InvalidSignature),
+            GenericSignature::MultiSig { pk, sig } => sig.verify(pk, msg),
+        }
+    }
+}
+
+/// Verify a list of independent signatures, each on its own message, and return the indices of all
+/// the entries which fail to verify. With the `parallel` feature, the signatures are verified in
+/// parallel with rayon.
+pub fn verify_all(items: &[(GenericSignature, &[u8])]) -> Result<(), Vec<usize>> {
+    #[cfg(feature = "parallel")]
+    let items = items.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let items = items.iter();
+    let failures: Vec<usize> = items
+        .enumerate()
+        .filter(|(_, (signature, msg))| signature.verify(msg).is_err())
+        .map(|(i, _)| i)
+        .collect();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
+
+impl Serialize for GenericSignature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.flag())?;
+        match self {
+            GenericSignature::Ed25519 { pk, sig } => tuple.serialize_element(&(pk, sig))?,
+            GenericSignature::Secp256k1 { pk, sig } => tuple.serialize_element(&(pk, sig))?,
+            GenericSignature::MultiSig { pk, sig } => tuple.serialize_element(&(pk, sig))?,
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for GenericSignature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct GenericSignatureVisitor;
+
+        impl<'de> Visitor<'de> for GenericSignatureVisitor {
+            type Value = GenericSignature;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a flag byte followed by a public key and a signature")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let flag: u8 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let missing = || <A::Error as de::Error>::invalid_length(1, &self);
+                match flag {
+                    ED25519_FLAG => {
+                        let (pk, sig) = seq.next_element()?.ok_or_else(missing)?;
+                        Ok(GenericSignature::Ed25519 { pk, sig })
+                    }
+                    SECP256K1_FLAG => {
+                        let (pk, sig) = seq.next_element()?.ok_or_else(missing)?;
+                        Ok(GenericSignature::Secp256k1 { pk, sig })
+                    }
+                    MULTISIG_FLAG => {
+                        let (pk, sig) = seq.next_element()?.ok_or_else(missing)?;
+                        Ok(GenericSignature::MultiSig { pk, sig })
+                    }
+                    _ => Err(de::Error::custom(format!("Unknown flag byte {}", flag))),
+                }
+            }
+        }
+
+        deserializer.deserialize_tuple(2, GenericSignatureVisitor)
+    }
+}
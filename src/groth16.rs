@@ -0,0 +1,76 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a helper verifying [Groth16](https://eprint.iacr.org/2016/260.pdf)
+//! proofs over the BN254 curve, backed by the [ark-groth16](https://docs.rs/ark-groth16) crate.
+//!
+//! Verifying keys and proofs are accepted in the (compressed) arkworks serialization format.
+//! All group elements are checked to be on the curve and in the prime order subgroup before any
+//! pairing is computed.
+
+use crate::error::FastCryptoError;
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_groth16::{Groth16, PreparedVerifyingKey, Proof, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+
+/// A Groth16 verifying key, prepared for verification.
+pub struct Groth16VerifyingKey(PreparedVerifyingKey<Bn254>);
+
+/// A Groth16 proof, consisting of the group elements `A`, `B` and `C`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Groth16Proof(Proof<Bn254>);
+
+impl Groth16VerifyingKey {
+    /// Deserialize a verifying key in the compressed arkworks format, validating that all its
+    /// group elements are in the correct subgroups.
+    pub fn from_arkworks_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let vk = VerifyingKey::<Bn254>::deserialize_compressed(bytes)
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(Groth16VerifyingKey(ark_groth16::prepare_verifying_key(&vk)))
+    }
+}
+
+impl Groth16Proof {
+    /// Build a proof from its group elements. Fails if any of them is not on the curve or not in
+    /// the prime order subgroup.
+    pub fn new(a: G1Affine, b: G2Affine, c: G1Affine) -> Result<Self, FastCryptoError> {
+        let valid = a.is_on_curve()
+            && a.is_in_correct_subgroup_assuming_on_curve()
+            && b.is_on_curve()
+            && b.is_in_correct_subgroup_assuming_on_curve()
+            && c.is_on_curve()
+            && c.is_in_correct_subgroup_assuming_on_curve();
+        if !valid {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Groth16Proof(Proof { a, b, c }))
+    }
+
+    /// Deserialize a proof in the compressed arkworks format, validating that all its group
+    /// elements are in the correct subgroups.
+    pub fn from_arkworks_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        Proof::<Bn254>::deserialize_compressed(bytes)
+            .map(Groth16Proof)
+            .map_err(|_| FastCryptoError::InvalidInput)
+    }
+}
+
+/// Verify a Groth16 proof for the given public inputs against a verifying key. Fails with
+/// [FastCryptoError::InvalidInput] if the number of public inputs does not match the verifying key,
+/// and with [FastCryptoError::InvalidProof] if the proof does not verify.
+pub fn verify(
+    vk: &Groth16VerifyingKey,
+    public_inputs: &[Fr],
+    proof: &Groth16Proof,
+) -> Result<(), FastCryptoError> {
+    // The number of public inputs must match the verifying key (the first element of gamma_abc_g1
+    // corresponds to the constant one).
+    if public_inputs.len() + 1 != vk.0.vk.gamma_abc_g1.len() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    match Groth16::<Bn254>::verify_proof(&vk.0, &proof.0, public_inputs) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(FastCryptoError::InvalidProof),
+        Err(_) => Err(FastCryptoError::InvalidInput),
+    }
+}
@@ -0,0 +1,493 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementations of the groups G1 and G2 of the [BLS12-381](https://hackmd.io/@benjaminion/bls12-381)
+//! pairing-friendly curve, and of scalars modulo their prime order
+//! 0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001, together with hashing to
+//! both groups as specified in [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380). The
+//! [pairing] maps pairs of elements of G1 and G2 to the target group GT.
+
+use crate::{
+    error::FastCryptoError,
+    groups::{GroupElement, Scalar as ScalarTrait, SCALAR_UNIFORM_BYTES_LENGTH},
+};
+use blst::{
+    blst_final_exp, blst_fp12, blst_fp12_conjugate, blst_fp12_is_equal, blst_fp12_mul,
+    blst_fp12_one, blst_fp12_sqr, blst_fr, blst_fr_add, blst_fr_cneg, blst_fr_from_scalar,
+    blst_fr_from_uint64, blst_fr_mul, blst_fr_sub, blst_hash_to_g1, blst_hash_to_g2,
+    blst_miller_loop, blst_p1, blst_p1_add_or_double, blst_p1_affine, blst_p1_affine_in_g1,
+    blst_p1_cneg, blst_p1_compress, blst_p1_from_affine, blst_p1_generator, blst_p1_is_equal,
+    blst_p1_is_inf, blst_p1_mult, blst_p1_to_affine, blst_p1_uncompress, blst_p2,
+    blst_p2_add_or_double, blst_p2_affine, blst_p2_affine_in_g2, blst_p2_cneg, blst_p2_compress,
+    blst_p2_from_affine, blst_p2_generator, blst_p2_is_equal, blst_p2_is_inf, blst_p2_mult,
+    blst_p2_to_affine, blst_p2_uncompress, blst_scalar, blst_scalar_from_be_bytes,
+    blst_scalar_from_fr, blst_scalar_from_le_bytes, BLST_ERROR,
+};
+use rand_core::{CryptoRng, RngCore};
+use std::ops::{Add, Mul, Neg, Sub};
+
+pub const G1_ELEMENT_BYTE_LENGTH: usize = 48;
+pub const G2_ELEMENT_BYTE_LENGTH: usize = 96;
+
+/// The RFC 9380 suite implemented by [hash_to_g1].
+pub const G1_HASH_TO_CURVE_SUITE: &str = "BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+/// The RFC 9380 suite implemented by [hash_to_g2].
+pub const G2_HASH_TO_CURVE_SUITE: &str = "BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+// The scalar field has 255 bits.
+const SCALAR_BITS: usize = 255;
+
+/// Represents an element of G1.
+#[derive(Clone, Copy, Debug)]
+pub struct G1Element(pub(crate) blst_p1);
+
+/// Represents an element of G2.
+#[derive(Clone, Copy, Debug)]
+pub struct G2Element(pub(crate) blst_p2);
+
+/// Represents an element of the target group GT, the subgroup of order `r` of the multiplicative
+/// group of the extension field Fp12. Like G1 and G2, the group is written additively, so `+` is
+/// multiplication in Fp12 and multiplication by a scalar is exponentiation.
+#[derive(Clone, Copy, Debug)]
+pub struct GTElement(blst_fp12);
+
+/// Represents a scalar modulo the order of G1, G2 and GT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Scalar(blst_fr);
+
+/// Hash `msg` to G1 using the suite [G1_HASH_TO_CURVE_SUITE] and the domain separation tag `dst`.
+/// Per RFC 9380, the tag should be unique to the protocol using it and contain the suite string.
+pub fn hash_to_g1(msg: &[u8], dst: &[u8]) -> G1Element {
+    let mut point = blst_p1::default();
+    unsafe {
+        blst_hash_to_g1(
+            &mut point,
+            msg.as_ptr(),
+            msg.len(),
+            dst.as_ptr(),
+            dst.len(),
+            std::ptr::null(),
+            0,
+        );
+    }
+    G1Element(point)
+}
+
+/// Hash `msg` to G2 using the suite [G2_HASH_TO_CURVE_SUITE] and the domain separation tag `dst`.
+/// Per RFC 9380, the tag should be unique to the protocol using it and contain the suite string.
+pub fn hash_to_g2(msg: &[u8], dst: &[u8]) -> G2Element {
+    let mut point = blst_p2::default();
+    unsafe {
+        blst_hash_to_g2(
+            &mut point,
+            msg.as_ptr(),
+            msg.len(),
+            dst.as_ptr(),
+            dst.len(),
+            std::ptr::null(),
+            0,
+        );
+    }
+    G2Element(point)
+}
+
+impl G1Element {
+    /// Encode this element in the compressed form of the
+    /// [ZCash serialization format](https://github.com/zkcrypto/pairing/tree/master/src/bls12_381#serialization).
+    pub fn compress(&self) -> [u8; G1_ELEMENT_BYTE_LENGTH] {
+        let mut bytes = [0u8; G1_ELEMENT_BYTE_LENGTH];
+        unsafe {
+            blst_p1_compress(bytes.as_mut_ptr(), &self.0);
+        }
+        bytes
+    }
+
+    /// Decode an element from its compressed form, failing if the bytes do not encode a point in
+    /// G1.
+    pub fn decompress(bytes: &[u8; G1_ELEMENT_BYTE_LENGTH]) -> Result<Self, FastCryptoError> {
+        let mut affine = blst_p1_affine::default();
+        unsafe {
+            if blst_p1_uncompress(&mut affine, bytes.as_ptr()) != BLST_ERROR::BLST_SUCCESS
+                || !blst_p1_affine_in_g1(&affine)
+            {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            let mut point = blst_p1::default();
+            blst_p1_from_affine(&mut point, &affine);
+            Ok(G1Element(point))
+        }
+    }
+}
+
+impl PartialEq for G1Element {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { blst_p1_is_equal(&self.0, &other.0) }
+    }
+}
+
+impl Eq for G1Element {}
+
+impl Add for G1Element {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut ret = blst_p1::default();
+        unsafe {
+            blst_p1_add_or_double(&mut ret, &self.0, &rhs.0);
+        }
+        G1Element(ret)
+    }
+}
+
+impl Sub for G1Element {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for G1Element {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut ret = self.0;
+        unsafe {
+            blst_p1_cneg(&mut ret, true);
+        }
+        G1Element(ret)
+    }
+}
+
+impl Mul<Scalar> for G1Element {
+    type Output = Self;
+
+    fn mul(self, rhs: Scalar) -> Self::Output {
+        let scalar = rhs.to_blst_scalar();
+        let mut ret = blst_p1::default();
+        unsafe {
+            blst_p1_mult(&mut ret, &self.0, scalar.b.as_ptr(), SCALAR_BITS);
+        }
+        G1Element(ret)
+    }
+}
+
+impl GroupElement for G1Element {
+    type ScalarType = Scalar;
+
+    fn zero() -> Self {
+        // The point at infinity is represented by z = 0.
+        G1Element(blst_p1::default())
+    }
+
+    fn generator() -> Self {
+        G1Element(unsafe { *blst_p1_generator() })
+    }
+}
+
+impl G2Element {
+    /// Encode this element in the compressed form of the
+    /// [ZCash serialization format](https://github.com/zkcrypto/pairing/tree/master/src/bls12_381#serialization).
+    pub fn compress(&self) -> [u8; G2_ELEMENT_BYTE_LENGTH] {
+        let mut bytes = [0u8; G2_ELEMENT_BYTE_LENGTH];
+        unsafe {
+            blst_p2_compress(bytes.as_mut_ptr(), &self.0);
+        }
+        bytes
+    }
+
+    /// Decode an element from its compressed form, failing if the bytes do not encode a point in
+    /// G2.
+    pub fn decompress(bytes: &[u8; G2_ELEMENT_BYTE_LENGTH]) -> Result<Self, FastCryptoError> {
+        let mut affine = blst_p2_affine::default();
+        unsafe {
+            if blst_p2_uncompress(&mut affine, bytes.as_ptr()) != BLST_ERROR::BLST_SUCCESS
+                || !blst_p2_affine_in_g2(&affine)
+            {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            let mut point = blst_p2::default();
+            blst_p2_from_affine(&mut point, &affine);
+            Ok(G2Element(point))
+        }
+    }
+}
+
+impl PartialEq for G2Element {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { blst_p2_is_equal(&self.0, &other.0) }
+    }
+}
+
+impl Eq for G2Element {}
+
+impl Add for G2Element {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut ret = blst_p2::default();
+        unsafe {
+            blst_p2_add_or_double(&mut ret, &self.0, &rhs.0);
+        }
+        G2Element(ret)
+    }
+}
+
+impl Sub for G2Element {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for G2Element {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut ret = self.0;
+        unsafe {
+            blst_p2_cneg(&mut ret, true);
+        }
+        G2Element(ret)
+    }
+}
+
+impl Mul<Scalar> for G2Element {
+    type Output = Self;
+
+    fn mul(self, rhs: Scalar) -> Self::Output {
+        let scalar = rhs.to_blst_scalar();
+        let mut ret = blst_p2::default();
+        unsafe {
+            blst_p2_mult(&mut ret, &self.0, scalar.b.as_ptr(), SCALAR_BITS);
+        }
+        G2Element(ret)
+    }
+}
+
+impl GroupElement for G2Element {
+    type ScalarType = Scalar;
+
+    fn zero() -> Self {
+        // The point at infinity is represented by z = 0.
+        G2Element(blst_p2::default())
+    }
+
+    fn generator() -> Self {
+        G2Element(unsafe { *blst_p2_generator() })
+    }
+}
+
+/// The optimal ate pairing of `p` and `q`, which is bilinear: `pairing(a * p, b * q)` equals
+/// `(a * b) * pairing(p, q)`.
+pub fn pairing(p: &G1Element, q: &G2Element) -> GTElement {
+    multi_pairing(&[(*p, *q)])
+}
+
+/// The sum of the pairings of all pairs. This is faster than computing the pairings one by one,
+/// since the final exponentiation is only done once.
+pub fn multi_pairing(pairs: &[(G1Element, G2Element)]) -> GTElement {
+    let mut product = unsafe { *blst_fp12_one() };
+    for (p, q) in pairs {
+        // The pairing with the identity is the identity, but the Miller loop does not handle it.
+        if unsafe { blst_p1_is_inf(&p.0) || blst_p2_is_inf(&q.0) } {
+            continue;
+        }
+        let mut p_affine = blst_p1_affine::default();
+        let mut q_affine = blst_p2_affine::default();
+        let mut miller_loop = blst_fp12::default();
+        let previous = product;
+        unsafe {
+            blst_p1_to_affine(&mut p_affine, &p.0);
+            blst_p2_to_affine(&mut q_affine, &q.0);
+            blst_miller_loop(&mut miller_loop, &q_affine, &p_affine);
+            blst_fp12_mul(&mut product, &previous, &miller_loop);
+        }
+    }
+    let mut ret = blst_fp12::default();
+    unsafe {
+        blst_final_exp(&mut ret, &product);
+    }
+    GTElement(ret)
+}
+
+impl PartialEq for GTElement {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { blst_fp12_is_equal(&self.0, &other.0) }
+    }
+}
+
+impl Eq for GTElement {}
+
+impl Add for GTElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut ret = blst_fp12::default();
+        unsafe {
+            blst_fp12_mul(&mut ret, &self.0, &rhs.0);
+        }
+        GTElement(ret)
+    }
+}
+
+impl Sub for GTElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for GTElement {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        // In GT, the inverse of an element is its conjugate.
+        let mut ret = self.0;
+        unsafe {
+            blst_fp12_conjugate(&mut ret);
+        }
+        GTElement(ret)
+    }
+}
+
+impl Mul<Scalar> for GTElement {
+    type Output = Self;
+
+    /// Exponentiation by square-and-multiply, which unlike the multiplication in G1 and G2 is not
+    /// constant time in the scalar.
+    fn mul(self, rhs: Scalar) -> Self::Output {
+        let scalar = rhs.to_blst_scalar();
+        let mut ret = Self::zero();
+        for bit in (0..SCALAR_BITS).rev() {
+            let previous = ret.0;
+            unsafe {
+                blst_fp12_sqr(&mut ret.0, &previous);
+            }
+            if (scalar.b[bit / 8] >> (bit % 8)) & 1 == 1 {
+                ret = ret + self;
+            }
+        }
+        ret
+    }
+}
+
+impl GroupElement for GTElement {
+    type ScalarType = Scalar;
+
+    fn zero() -> Self {
+        GTElement(unsafe { *blst_fp12_one() })
+    }
+
+    fn generator() -> Self {
+        pairing(&G1Element::generator(), &G2Element::generator())
+    }
+}
+
+impl Scalar {
+    fn to_blst_scalar(self) -> blst_scalar {
+        let mut scalar = blst_scalar::default();
+        unsafe {
+            blst_scalar_from_fr(&mut scalar, &self.0);
+        }
+        scalar
+    }
+}
+
+impl Add for Scalar {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut ret = blst_fr::default();
+        unsafe {
+            blst_fr_add(&mut ret, &self.0, &rhs.0);
+        }
+        Scalar(ret)
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut ret = blst_fr::default();
+        unsafe {
+            blst_fr_sub(&mut ret, &self.0, &rhs.0);
+        }
+        Scalar(ret)
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        let mut ret = blst_fr::default();
+        unsafe {
+            blst_fr_cneg(&mut ret, &self.0, true);
+        }
+        Scalar(ret)
+    }
+}
+
+impl Mul<Scalar> for Scalar {
+    type Output = Self;
+
+    fn mul(self, rhs: Scalar) -> Self::Output {
+        let mut ret = blst_fr::default();
+        unsafe {
+            blst_fr_mul(&mut ret, &self.0, &rhs.0);
+        }
+        Scalar(ret)
+    }
+}
+
+impl From<u64> for Scalar {
+    fn from(value: u64) -> Self {
+        let mut ret = blst_fr::default();
+        unsafe {
+            blst_fr_from_uint64(&mut ret, [value, 0, 0, 0].as_ptr());
+        }
+        Scalar(ret)
+    }
+}
+
+impl GroupElement for Scalar {
+    type ScalarType = Self;
+
+    fn zero() -> Self {
+        Scalar(blst_fr::default())
+    }
+
+    fn generator() -> Self {
+        Scalar::from(1)
+    }
+}
+
+impl ScalarTrait for Scalar {
+    fn rand<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        // Reducing 512 uniformly random bits gives a scalar whose distribution is statistically
+        // close to uniform.
+        let mut bytes = [0u8; 64];
+        rng.fill_bytes(&mut bytes);
+        let mut scalar = blst_scalar::default();
+        let mut ret = blst_fr::default();
+        unsafe {
+            blst_scalar_from_le_bytes(&mut scalar, bytes.as_ptr(), bytes.len());
+            blst_fr_from_scalar(&mut ret, &scalar);
+        }
+        Scalar(ret)
+    }
+    fn from_uniform_bytes(bytes: &[u8; SCALAR_UNIFORM_BYTES_LENGTH]) -> Self {
+        let mut scalar = blst_scalar::default();
+        let mut ret = blst_fr::default();
+        unsafe {
+            blst_scalar_from_be_bytes(&mut scalar, bytes.as_ptr(), bytes.len());
+            blst_fr_from_scalar(&mut ret, &scalar);
+        }
+        Scalar(ret)
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::hash::{expand_message_xmd, Sha256};
+use rand_core::{CryptoRng, RngCore};
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+pub mod bls12381;
+pub mod ristretto255;
+
+/// Trait impl'd by elements of an additive cyclic group.
+pub trait GroupElement:
+    Copy
+    + Clone
+    + Debug
+    + Eq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Neg<Output = Self>
+    + Mul<Self::ScalarType, Output = Self>
+{
+    /// Type of scalars used in the [Self::mul] multiplication method.
+    type ScalarType: Scalar;
+
+    /// Return an instance of the identity element in this group.
+    fn zero() -> Self;
+
+    /// Return an instance of the generator for this group.
+    fn generator() -> Self;
+}
+
+/// Trait impl'd by scalars to be used with [GroupElement].
+pub trait Scalar: GroupElement<ScalarType = Self> + From<u64> {
+    /// Sample a uniformly random scalar.
+    fn rand<R: CryptoRng + RngCore>(rng: &mut R) -> Self;
+
+    /// Reduce 64 uniformly random bytes modulo the order of the group. The bytes are read in the
+    /// byte order of the group's specification, i.e., big-endian for BLS12-381 as in RFC 9380 and
+    /// little-endian for ristretto255.
+    fn from_uniform_bytes(bytes: &[u8; SCALAR_UNIFORM_BYTES_LENGTH]) -> Self;
+}
+
+/// The number of bytes reduced by [Scalar::from_uniform_bytes]. This is more than the
+/// `ceil((log2(order) + 128) / 8) = 48` bytes required by RFC 9380 for groups of order up to
+/// 2^256, so the bias of the reduction is negligible.
+pub const SCALAR_UNIFORM_BYTES_LENGTH: usize = 64;
+
+/// Hash `msg` to a scalar of the group `G` with the domain separation tag `dst`. The message is
+/// expanded to [SCALAR_UNIFORM_BYTES_LENGTH] bytes with [expand_message_xmd] using SHA-256, which
+/// are then reduced with [Scalar::from_uniform_bytes].
+///
+/// Panics if `dst` is longer than 255 bytes.
+pub fn hash_to_scalar<G: GroupElement>(msg: &[u8], dst: &[u8]) -> G::ScalarType {
+    let uniform_bytes = expand_message_xmd::<Sha256, 32>(msg, dst, SCALAR_UNIFORM_BYTES_LENGTH)
+        .expect("dst is at most 255 bytes");
+    let mut bytes = [0u8; SCALAR_UNIFORM_BYTES_LENGTH];
+    bytes.copy_from_slice(&uniform_bytes);
+    G::ScalarType::from_uniform_bytes(&bytes)
+}
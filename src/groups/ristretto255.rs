@@ -0,0 +1,157 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementations of the [ristretto255 group](https://www.ietf.org/archive/id/draft-irtf-cfrg-ristretto255-decaf448-03.html)
+//! which is a group of prime order 2^{252} + 27742317777372353535851937790883648493 built over Curve25519.
+
+use crate::groups::{GroupElement, Scalar, SCALAR_UNIFORM_BYTES_LENGTH};
+use curve25519_dalek_ng::{
+    constants::RISTRETTO_BASEPOINT_POINT, ristretto::CompressedRistretto,
+    ristretto::RistrettoPoint as ExternalRistrettoPoint, scalar::Scalar as ExternalRistrettoScalar,
+    traits::Identity,
+};
+use rand_core::{CryptoRng, RngCore};
+use std::ops::{Add, Mul, Neg, Sub};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+const RISTRETTO_POINT_BYTE_LENGTH: usize = 32;
+
+/// Represents a point in the Ristretto group for Curve25519.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RistrettoPoint(ExternalRistrettoPoint);
+
+/// Represents a scalar modulo the order of the Ristretto group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RistrettoScalar(ExternalRistrettoScalar);
+
+impl RistrettoPoint {
+    /// Encode this point in its canonical compressed form.
+    pub fn compress(&self) -> [u8; RISTRETTO_POINT_BYTE_LENGTH] {
+        self.0.compress().to_bytes()
+    }
+
+    /// Decode a point from its compressed form, failing if the bytes do not encode a valid point.
+    pub fn decompress(bytes: &[u8; RISTRETTO_POINT_BYTE_LENGTH]) -> Result<Self, signature::Error> {
+        CompressedRistretto::from_slice(bytes)
+            .decompress()
+            .map(RistrettoPoint)
+            .ok_or_else(signature::Error::new)
+    }
+}
+
+impl Add for RistrettoPoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        RistrettoPoint(self.0 + rhs.0)
+    }
+}
+
+impl Sub for RistrettoPoint {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        RistrettoPoint(self.0 - rhs.0)
+    }
+}
+
+impl Neg for RistrettoPoint {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        RistrettoPoint(-self.0)
+    }
+}
+
+impl Mul<RistrettoScalar> for RistrettoPoint {
+    type Output = Self;
+
+    fn mul(self, rhs: RistrettoScalar) -> Self::Output {
+        RistrettoPoint(self.0 * rhs.0)
+    }
+}
+
+impl GroupElement for RistrettoPoint {
+    type ScalarType = RistrettoScalar;
+
+    fn zero() -> Self {
+        RistrettoPoint(ExternalRistrettoPoint::identity())
+    }
+
+    fn generator() -> Self {
+        RistrettoPoint(RISTRETTO_BASEPOINT_POINT)
+    }
+}
+
+impl Add for RistrettoScalar {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        RistrettoScalar(self.0 + rhs.0)
+    }
+}
+
+impl Sub for RistrettoScalar {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        RistrettoScalar(self.0 - rhs.0)
+    }
+}
+
+impl Neg for RistrettoScalar {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        RistrettoScalar(-self.0)
+    }
+}
+
+impl Mul for RistrettoScalar {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        RistrettoScalar(self.0 * rhs.0)
+    }
+}
+
+impl From<u64> for RistrettoScalar {
+    fn from(value: u64) -> Self {
+        RistrettoScalar(ExternalRistrettoScalar::from(value))
+    }
+}
+
+impl ConstantTimeEq for RistrettoScalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_bytes().ct_eq(other.0.as_bytes())
+    }
+}
+
+impl Zeroize for RistrettoScalar {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl GroupElement for RistrettoScalar {
+    type ScalarType = Self;
+
+    fn zero() -> Self {
+        RistrettoScalar(ExternalRistrettoScalar::zero())
+    }
+
+    fn generator() -> Self {
+        RistrettoScalar(ExternalRistrettoScalar::one())
+    }
+}
+
+impl Scalar for RistrettoScalar {
+    fn rand<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        RistrettoScalar(ExternalRistrettoScalar::random(rng))
+    }
+
+    fn from_uniform_bytes(bytes: &[u8; SCALAR_UNIFORM_BYTES_LENGTH]) -> Self {
+        RistrettoScalar(ExternalRistrettoScalar::from_bytes_mod_order_wide(bytes))
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a fixed-size [Digest] type together with wrappers of common hash functions
+//! which all produce a [Digest] through the [HashFunction] trait.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::hash::{HashFunction, Sha256};
+//! # fn main() {
+//!     let digest = Sha256::digest(b"Hello, world!");
+//!     assert_eq!(digest.size(), 32);
+//! # }
+//! ```
+
+use crate::error::FastCryptoError;
+use blake2::{digest::VariableOutput, VarBlake2b};
+use digest::{core_api::BlockSizeUser, typenum::Unsigned};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::fmt;
+
+/// Represents a hash digest of `DIGEST_LEN` bytes. The digest is serialized as a fixed-size array,
+/// i.e., without a length prefix.
+#[serde_as]
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Ord, PartialOrd)]
+pub struct Digest<const DIGEST_LEN: usize>(
+    #[serde_as(as = "[_; DIGEST_LEN]")] pub [u8; DIGEST_LEN],
+);
+
+impl<const DIGEST_LEN: usize> Digest<DIGEST_LEN> {
+    pub fn new(digest: [u8; DIGEST_LEN]) -> Self {
+        Digest(digest)
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    pub fn size(&self) -> usize {
+        DIGEST_LEN
+    }
+
+    /// The hex encoding of the first `n` bytes of the digest followed by `...`, e.g. for logging.
+    /// If `n` is at least the length of the digest, this is the full hex encoding without `...`,
+    /// as returned by the [fmt::Display] implementation.
+    pub fn truncated(&self, n: usize) -> String {
+        if n >= DIGEST_LEN {
+            return hex::encode(self.0);
+        }
+        format!("{}...", hex::encode(&self.0[..n]))
+    }
+}
+
+impl<const DIGEST_LEN: usize> Default for Digest<DIGEST_LEN> {
+    fn default() -> Self {
+        Digest([0u8; DIGEST_LEN])
+    }
+}
+
+impl<const DIGEST_LEN: usize> fmt::Debug for Digest<DIGEST_LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl<const DIGEST_LEN: usize> fmt::Display for Digest<DIGEST_LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl<const DIGEST_LEN: usize> AsRef<[u8]> for Digest<DIGEST_LEN> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const DIGEST_LEN: usize> From<[u8; DIGEST_LEN]> for Digest<DIGEST_LEN> {
+    fn from(digest: [u8; DIGEST_LEN]) -> Self {
+        Digest(digest)
+    }
+}
+
+impl<const DIGEST_LEN: usize> From<Digest<DIGEST_LEN>> for [u8; DIGEST_LEN] {
+    fn from(digest: Digest<DIGEST_LEN>) -> Self {
+        digest.0
+    }
+}
+
+/// Trait implemented by hash functions providing an output of fixed length.
+pub trait HashFunction<const DIGEST_LEN: usize>: Default {
+    /// The length of this hash function's output in bytes.
+    const OUTPUT_SIZE: usize = DIGEST_LEN;
+
+    /// Process the given data and update the internal state of the hasher.
+    fn update<Data: AsRef<[u8]>>(&mut self, data: Data);
+
+    /// Retrieve the result and consume the hasher.
+    fn finalize(self) -> Digest<DIGEST_LEN>;
+
+    /// Compute the digest of the given data in one shot.
+    fn digest<Data: AsRef<[u8]>>(data: Data) -> Digest<DIGEST_LEN> {
+        let mut h = Self::default();
+        h.update(data);
+        h.finalize()
+    }
+}
+
+/// A generic wrapper turning any [digest::Digest] with an output of `DIGEST_LEN` bytes into a
+/// [HashFunction].
+#[derive(Default)]
+pub struct HashFunctionWrapper<Variant: digest::Digest + Default, const DIGEST_LEN: usize>(Variant);
+
+impl<Variant: digest::Digest + Default, const DIGEST_LEN: usize> HashFunction<DIGEST_LEN>
+    for HashFunctionWrapper<Variant, DIGEST_LEN>
+{
+    fn update<Data: AsRef<[u8]>>(&mut self, data: Data) {
+        digest::Digest::update(&mut self.0, data);
+    }
+
+    fn finalize(self) -> Digest<DIGEST_LEN> {
+        let mut result = [0u8; DIGEST_LEN];
+        result.copy_from_slice(&digest::Digest::finalize(self.0));
+        Digest(result)
+    }
+}
+
+/// SHA-2 with a 256 bit output.
+pub type Sha256 = HashFunctionWrapper<sha2::Sha256, 32>;
+
+/// SHA-2 with a 512 bit output.
+pub type Sha512 = HashFunctionWrapper<sha2::Sha512, 64>;
+
+/// SHA-512/256, i.e., SHA-512 with its own initial values and the output truncated to 256 bits.
+pub type Sha512_256 = HashFunctionWrapper<sha2::Sha512_256, 32>;
+
+/// SHA-3 with a 256 bit output.
+pub type Sha3_256 = HashFunctionWrapper<sha3::Sha3_256, 32>;
+
+/// The pre-standardization Keccak with a 256 bit output, as used by Ethereum.
+pub type Keccak256 = HashFunctionWrapper<sha3::Keccak256, 32>;
+
+/// BLAKE2b with a 256 bit output.
+pub struct Blake2b256(VarBlake2b);
+
+impl Default for Blake2b256 {
+    fn default() -> Self {
+        Blake2b256(VarBlake2b::new(32).unwrap())
+    }
+}
+
+impl HashFunction<32> for Blake2b256 {
+    fn update<Data: AsRef<[u8]>>(&mut self, data: Data) {
+        blake2::digest::Update::update(&mut self.0, data);
+    }
+
+    fn finalize(self) -> Digest<32> {
+        let mut digest = [0u8; 32];
+        self.0
+            .finalize_variable(|output| digest.copy_from_slice(output));
+        Digest(digest)
+    }
+}
+
+/// BLAKE3 with a 256 bit output. Besides the default mode, BLAKE3 can be used as a keyed hash
+/// function with [Blake3::new_keyed] and as a key derivation function with [Blake3::derive_key].
+#[derive(Default)]
+pub struct Blake3(blake3::Hasher);
+
+impl Blake3 {
+    /// A hasher in the keyed mode, which computes a MAC of its input under `key`.
+    pub fn new_keyed(key: &[u8; 32]) -> Self {
+        Blake3(blake3::Hasher::new_keyed(key))
+    }
+
+    /// A hasher in the key derivation mode, which derives a key from its input, the key material,
+    /// for the given `context`. The context should be a hardcoded, globally unique string
+    /// describing the application and purpose, and must not depend on the key material.
+    pub fn derive_key(context: &str) -> Self {
+        Blake3(blake3::Hasher::new_derive_key(context))
+    }
+}
+
+impl HashFunction<32> for Blake3 {
+    fn update<Data: AsRef<[u8]>>(&mut self, data: Data) {
+        self.0.update(data.as_ref());
+    }
+
+    fn finalize(self) -> Digest<32> {
+        Digest(self.0.finalize().into())
+    }
+}
+
+/// Hash `msg` with `H`, separated by `domain` from messages hashed with other domains. The input
+/// to the hash function is the length of `domain` as a little-endian u64, followed by `domain` and
+/// `msg`. The length prefix ensures that distinct `(domain, msg)` pairs never give the same input,
+/// even if their concatenations are equal.
+pub fn hash_with_domain<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+    domain: &[u8],
+    msg: &[u8],
+) -> Digest<DIGEST_LEN> {
+    let mut hasher = H::default();
+    hasher.update((domain.len() as u64).to_le_bytes());
+    hasher.update(domain);
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// Trait implemented by hash functions which process their input in blocks of a fixed size.
+pub trait BlockHashFunction<const DIGEST_LEN: usize>: HashFunction<DIGEST_LEN> {
+    /// The size of the input blocks in bytes.
+    const BLOCK_SIZE: usize;
+}
+
+impl<Variant, const DIGEST_LEN: usize> BlockHashFunction<DIGEST_LEN>
+    for HashFunctionWrapper<Variant, DIGEST_LEN>
+where
+    Variant: digest::Digest + Default + BlockSizeUser,
+{
+    const BLOCK_SIZE: usize = <Variant::BlockSize as Unsigned>::USIZE;
+}
+
+impl BlockHashFunction<32> for Blake2b256 {
+    const BLOCK_SIZE: usize = 128;
+}
+
+/// Expand `msg` to `len_in_bytes` uniformly random bytes with `expand_message_xmd` from
+/// [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380#name-expand_message_xmd), using the hash
+/// function `H` and the domain separation tag `dst`.
+///
+/// Fails with [FastCryptoError::InvalidInput] if `len_in_bytes` is larger than 255 digests or
+/// 65535 bytes, or if `dst` is longer than 255 bytes.
+pub fn expand_message_xmd<H: BlockHashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<u8>, FastCryptoError> {
+    let ell = (len_in_bytes + DIGEST_LEN - 1) / DIGEST_LEN;
+    if ell > 255 || len_in_bytes > u16::MAX as usize || dst.len() > 255 {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let mut hasher = H::default();
+    hasher.update(vec![0u8; H::BLOCK_SIZE]);
+    hasher.update(msg);
+    hasher.update((len_in_bytes as u16).to_be_bytes());
+    hasher.update([0u8]);
+    hasher.update(&dst_prime);
+    let b_0 = hasher.finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * DIGEST_LEN);
+    let mut b_i = [0u8; DIGEST_LEN];
+    for i in 1..=ell {
+        // b_1 = H(b_0 || 1 || dst_prime) and b_i = H((b_0 xor b_(i-1)) || i || dst_prime).
+        let mut hasher = H::default();
+        let chained: Vec<u8> = b_0.0.iter().zip(&b_i).map(|(x, y)| x ^ y).collect();
+        hasher.update(chained);
+        hasher.update([i as u8]);
+        hasher.update(&dst_prime);
+        b_i = hasher.finalize().0;
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
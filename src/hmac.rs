@@ -0,0 +1,45 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains typed helpers computing HMAC ([RFC 2104](https://www.rfc-editor.org/rfc/rfc2104))
+//! over SHA-256 and SHA-512, backed by the [hmac](https://docs.rs/hmac) crate.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::hmac::{hmac_sha256, verify_hmac};
+//! # fn main() {
+//!     let tag = hmac_sha256(b"secret key", b"GET /api/v1/orders");
+//!     assert!(verify_hmac(&tag, &hmac_sha256(b"secret key", b"GET /api/v1/orders")));
+//! # }
+//! ```
+
+use ::hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+use subtle::ConstantTimeEq;
+
+pub const HMAC_SHA256_LENGTH: usize = 32;
+pub const HMAC_SHA512_LENGTH: usize = 64;
+
+/// Compute HMAC-SHA256 of `msg` under `key`.
+pub fn hmac_sha256(key: &[u8], msg: &[u8]) -> [u8; HMAC_SHA256_LENGTH] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(msg);
+    mac.finalize().into_bytes().into()
+}
+
+/// Compute HMAC-SHA512 of `msg` under `key`.
+pub fn hmac_sha512(key: &[u8], msg: &[u8]) -> [u8; HMAC_SHA512_LENGTH] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(msg);
+    let mut result = [0u8; HMAC_SHA512_LENGTH];
+    result.copy_from_slice(&mac.finalize().into_bytes());
+    result
+}
+
+/// Check that a received `tag` equals the `expected` one. The comparison runs in constant time
+/// with respect to the contents of the tags (it does not exit early on the first differing byte),
+/// so it does not leak how much of a forged tag is correct. Only the lengths are compared in
+/// variable time, as they are public.
+pub fn verify_hmac(expected: &[u8], tag: &[u8]) -> bool {
+    expected.ct_eq(tag).into()
+}
@@ -0,0 +1,174 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a password-encrypted JSON keystore for key pairs, modelled after version 3
+//! of Ethereum's keystore format. The encryption key is derived from the password with scrypt, and
+//! the key pair is encrypted with [Aes256Gcm], so a wrong password is detected by the
+//! authentication tag instead of decrypting to garbage.
+//!
+//! Key pairs are exported and imported with [KeyPair::to_keystore_json] and
+//! [KeyPair::from_keystore_json], or with [encrypt] to choose a different scrypt cost:
+//! ```rust
+//! use fastcrypto::ed25519::Ed25519KeyPair;
+//! use fastcrypto::traits::KeyPair;
+//! use rand::thread_rng;
+//! # fn main() {
+//!     let kp = Ed25519KeyPair::generate(&mut thread_rng());
+//!     let json = fastcrypto::keystore::encrypt(&kp, "password", 10, &mut thread_rng());
+//!     let decrypted = Ed25519KeyPair::from_keystore_json(&json, "password").unwrap();
+//!     assert_eq!(kp.public(), decrypted.public());
+//! # }
+//! ```
+
+use crate::{
+    aes::{Aes256Gcm, AesKey, Nonce, AES_256_KEY_LENGTH, AES_GCM_NONCE_LENGTH},
+    error::FastCryptoError,
+    traits::{Aead, KeyPair},
+};
+use base64ct::Encoding;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// The scrypt cost parameter `log2(n)` used by [KeyPair::to_keystore_json]. This is the
+/// "standard" setting of Ethereum keystores, which takes about a second and 256 MiB of memory.
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 18;
+
+/// The largest scrypt cost parameter `log2(n)` accepted when decrypting a keystore, so that a
+/// malicious keystore cannot make decryption use an unbounded amount of memory.
+pub const MAX_SCRYPT_LOG_N: u8 = 20;
+
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const SALT_LENGTH: usize = 32;
+
+const KEYSTORE_VERSION: u32 = 1;
+const CIPHER: &str = "aes-256-gcm";
+const KDF: &str = "scrypt";
+
+#[derive(Serialize, Deserialize)]
+struct Keystore {
+    version: u32,
+    crypto: KeystoreCrypto,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: KdfParams,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    nonce: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfParams {
+    dklen: usize,
+    n: u64,
+    r: u32,
+    p: u32,
+    salt: String,
+}
+
+/// Encrypt `kp` with a key derived from `password` with scrypt, using the cost parameter
+/// `n = 2^log_n`, and return the keystore as JSON. [KeyPair::to_keystore_json] uses
+/// [DEFAULT_SCRYPT_LOG_N].
+///
+/// Panics if `log_n` is not a valid scrypt cost parameter.
+pub fn encrypt<K: KeyPair, R: CryptoRng + RngCore>(
+    kp: &K,
+    password: &str,
+    log_n: u8,
+    rng: &mut R,
+) -> String {
+    let mut salt = [0u8; SALT_LENGTH];
+    rng.fill_bytes(&mut salt);
+    let nonce = Nonce::generate(rng);
+    let key = derive_key(password, &salt, log_n).expect("valid scrypt parameters");
+
+    // The key pair is encrypted in the encoding used by EncodeDecodeBase64.
+    let plaintext = Zeroizing::new(
+        base64ct::Base64::decode_vec(&Zeroizing::new(kp.encode_base64()))
+            .expect("valid base64 encoding"),
+    );
+    let ciphertext = Aes256Gcm::encrypt(&key, &nonce, &[], &plaintext);
+
+    let keystore = Keystore {
+        version: KEYSTORE_VERSION,
+        crypto: KeystoreCrypto {
+            cipher: CIPHER.to_string(),
+            cipherparams: CipherParams {
+                nonce: hex::encode(nonce.as_ref()),
+            },
+            ciphertext: hex::encode(ciphertext),
+            kdf: KDF.to_string(),
+            kdfparams: KdfParams {
+                dklen: AES_256_KEY_LENGTH,
+                n: 1 << log_n,
+                r: SCRYPT_R,
+                p: SCRYPT_P,
+                salt: hex::encode(salt),
+            },
+        },
+    };
+    serde_json::to_string(&keystore).expect("keystore is serializable")
+}
+
+/// Decrypt a key pair from a keystore created by [encrypt]. Fails with
+/// [FastCryptoError::InvalidPassword] if the password is wrong or the ciphertext has been
+/// modified, and with [FastCryptoError::InvalidInput] if the keystore is malformed or uses
+/// unsupported parameters.
+pub fn decrypt<K: KeyPair>(json: &str, password: &str) -> Result<K, FastCryptoError> {
+    let keystore: Keystore =
+        serde_json::from_str(json).map_err(|_| FastCryptoError::InvalidInput)?;
+    let crypto = keystore.crypto;
+    let params = crypto.kdfparams;
+    if keystore.version != KEYSTORE_VERSION
+        || crypto.cipher != CIPHER
+        || crypto.kdf != KDF
+        || params.dklen != AES_256_KEY_LENGTH
+        || params.r != SCRYPT_R
+        || params.p != SCRYPT_P
+        || !params.n.is_power_of_two()
+        || params.n.trailing_zeros() > MAX_SCRYPT_LOG_N as u32
+    {
+        return Err(FastCryptoError::InvalidInput);
+    }
+
+    let salt = hex::decode(params.salt)?;
+    let nonce = hex::decode(crypto.cipherparams.nonce)?;
+    if nonce.len() != AES_GCM_NONCE_LENGTH {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let mut nonce_bytes = [0u8; AES_GCM_NONCE_LENGTH];
+    nonce_bytes.copy_from_slice(&nonce);
+    let ciphertext = hex::decode(crypto.ciphertext)?;
+
+    let key = derive_key(password, &salt, params.n.trailing_zeros() as u8)?;
+    let plaintext = Zeroizing::new(
+        Aes256Gcm::decrypt(&key, &Nonce::new(nonce_bytes), &[], &ciphertext)
+            .map_err(|_| FastCryptoError::InvalidPassword)?,
+    );
+    K::decode_base64(&Zeroizing::new(base64ct::Base64::encode_string(&plaintext)))
+        .map_err(|_| FastCryptoError::InvalidInput)
+}
+
+fn derive_key(
+    password: &str,
+    salt: &[u8],
+    log_n: u8,
+) -> Result<AesKey<AES_256_KEY_LENGTH>, FastCryptoError> {
+    let params = scrypt::Params::new(log_n, SCRYPT_R, SCRYPT_P)
+        .map_err(|_| FastCryptoError::InvalidInput)?;
+    let mut key = [0u8; AES_256_KEY_LENGTH];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+        .expect("the key length is supported by scrypt");
+    let aes_key = AesKey::new(key);
+    zeroize::Zeroize::zeroize(&mut key);
+    Ok(aes_key)
+}
@@ -8,105 +8,206 @@
     rust_2021_compatibility
 )]
 
-use base64ct::{Base64, Encoding};
-use blake2::{
-    digest::{Update, VariableOutput},
-    VarBlake2b,
-};
-
+use blake2::digest::VariableOutput;
+#[cfg(feature = "signing")]
 use rand::{rngs::OsRng, CryptoRng, RngCore};
-use serde::{Deserialize, Serialize};
 
 pub use signature::{Signature as _, Verifier};
-use std::fmt;
+#[cfg(feature = "signature-service")]
 use tokio::sync::{
     mpsc::{channel, Sender},
     oneshot,
 };
 
-#[cfg(test)]
+#[cfg(all(test, feature = "signing"))]
 #[path = "tests/pubkey_bytes_tests.rs"]
 pub mod pubkey_bytes_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "signing"))]
 #[path = "tests/ed25519_tests.rs"]
 pub mod ed25519_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "signing"))]
 #[path = "tests/secp256k1_tests.rs"]
 pub mod secp256k1_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/secp256k1_pure_rust_tests.rs"]
+pub mod secp256k1_pure_rust_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/tecdsa_tests.rs"]
+pub mod tecdsa_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/bip32_tests.rs"]
+pub mod bip32_tests;
+
+#[cfg(all(test, feature = "signing"))]
 #[path = "tests/bls12381_tests.rs"]
 pub mod bls12381_tests;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/bls12381_min_pk_tests.rs"]
+pub mod bls12381_min_pk_tests;
+
+#[cfg(all(test, feature = "signing"))]
 #[path = "tests/bulletproofs_tests.rs"]
 pub mod bulletproofs_tests;
 
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/aes_tests.rs"]
+pub mod aes_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/chacha20poly1305_tests.rs"]
+pub mod chacha20poly1305_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/groth16_tests.rs"]
+pub mod groth16_tests;
+
+#[cfg(test)]
+#[path = "tests/poseidon_tests.rs"]
+pub mod poseidon_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/elgamal_tests.rs"]
+pub mod elgamal_tests;
+
+#[cfg(test)]
+#[path = "tests/hash_tests.rs"]
+pub mod hash_tests;
+
+#[cfg(test)]
+#[path = "tests/hmac_tests.rs"]
+pub mod hmac_tests;
+
+#[cfg(test)]
+#[path = "tests/merkle_tests.rs"]
+pub mod merkle_tests;
+
+#[cfg(test)]
+#[path = "tests/sparse_merkle_tests.rs"]
+pub mod sparse_merkle_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/keystore_tests.rs"]
+pub mod keystore_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/multisig_tests.rs"]
+pub mod multisig_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/bcs_tests.rs"]
+pub mod bcs_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/committee_tests.rs"]
+pub mod committee_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/canonical_serialization_tests.rs"]
+pub mod canonical_serialization_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/encoding_tests.rs"]
+pub mod encoding_tests;
+
+#[cfg(test)]
+#[path = "tests/error_tests.rs"]
+pub mod error_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/bls12381_group_tests.rs"]
+pub mod bls12381_group_tests;
+
+#[cfg(test)]
+#[path = "tests/groups_tests.rs"]
+pub mod groups_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/x25519_tests.rs"]
+pub mod x25519_tests;
+
+#[cfg(all(test, feature = "signing"))]
+#[path = "tests/generic_signature_tests.rs"]
+pub mod generic_signature_tests;
+
+#[cfg(all(test, feature = "test-utils"))]
+#[path = "tests/test_utils_tests.rs"]
+pub mod test_utils_tests;
+
+#[cfg(all(test, feature = "wasm", target_arch = "wasm32"))]
+#[path = "tests/wasm_tests.rs"]
+pub mod wasm_tests;
+
+#[cfg(test)]
+#[path = "tests/no_default_features_tests.rs"]
+pub mod no_default_features_tests;
+
 // Signing traits
 pub mod traits;
+// Errors
+pub mod error;
 // Key scheme implementations
 pub mod bls12381;
 pub mod ed25519;
 pub mod secp256k1;
 
+#[cfg(feature = "signing")]
+pub mod aes;
 pub mod bulletproofs;
+#[cfg(feature = "signing")]
+pub mod chacha20poly1305;
+#[cfg(feature = "signing")]
+pub mod elgamal;
+pub mod generic_signature;
+pub mod groth16;
+pub mod groups;
+pub mod multisig;
+pub mod poseidon;
+#[cfg(feature = "signing")]
+pub mod x25519;
 
 // Other tooling
+pub mod committee;
+pub mod encoding;
+pub mod hash;
+#[cfg(feature = "signing")]
 pub mod hkdf;
+pub mod hmac;
+#[cfg(feature = "signing")]
+pub mod keystore;
+pub mod merkle;
 pub mod pubkey_bytes;
 pub mod serde_helpers;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use hash::Digest;
 
 pub const DIGEST_LEN: usize = 32;
 
-pub fn blake2b_256<F: Fn(&mut blake2::VarBlake2b)>(closure: F) -> [u8; DIGEST_LEN] {
-    let mut hasher = VarBlake2b::new(DIGEST_LEN).unwrap();
+pub fn blake2b_256<F: Fn(&mut blake2::VarBlake2b)>(closure: F) -> Digest<DIGEST_LEN> {
+    let mut hasher = blake2::VarBlake2b::new(DIGEST_LEN).unwrap();
     closure(&mut hasher);
     let mut res = [0u8; DIGEST_LEN];
     hasher.finalize_variable(|output| res.copy_from_slice(output));
-    res
-}
-
-/// Represents a hash digest (32 bytes).
-#[derive(Hash, PartialEq, Default, Eq, Clone, Deserialize, Serialize, Ord, PartialOrd, Copy)]
-pub struct Digest([u8; DIGEST_LEN]);
-
-impl Digest {
-    pub fn new(val: [u8; DIGEST_LEN]) -> Self {
-        Digest(val)
-    }
-
-    pub fn to_vec(&self) -> Vec<u8> {
-        self.0.to_vec()
-    }
-
-    pub fn size(&self) -> usize {
-        self.0.len()
-    }
-}
-
-impl fmt::Debug for Digest {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "{}", Base64::encode_string(&self.0))
-    }
-}
-
-impl fmt::Display for Digest {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(f, "{}", Base64::encode_string(&self.0).get(0..16).unwrap())
-    }
-}
-
-impl AsRef<[u8]> for Digest {
-    fn as_ref(&self) -> &[u8] {
-        &self.0
-    }
+    Digest::new(res)
 }
 
 /// This trait is implemented by all messages that can be hashed.
 pub trait Hash {
-    type TypedDigest: Into<Digest> + std::fmt::Display + std::hash::Hash + Eq + Ord + Copy;
+    type TypedDigest: Into<Digest<DIGEST_LEN>>
+        + std::fmt::Display
+        + std::hash::Hash
+        + Eq
+        + Ord
+        + Copy;
     fn digest(&self) -> Self::TypedDigest;
 }
 
@@ -114,10 +215,12 @@ pub trait Hash {
 // Generic Keypair
 ////////////////////////////////////////////////////////////////
 
+#[cfg(feature = "signing")]
 pub fn generate_production_keypair<K: traits::KeyPair>() -> K {
     generate_keypair::<K, _>(&mut OsRng)
 }
 
+#[cfg(feature = "signing")]
 pub fn generate_keypair<K: traits::KeyPair, R>(csprng: &mut R) -> K
 where
     R: CryptoRng + RngCore,
@@ -125,29 +228,102 @@ where
     K::generate(csprng)
 }
 
+/// The default number of pending requests a [SignatureService] buffers before callers of
+/// [SignatureService::request_signature] have to wait for a free slot.
+#[cfg(feature = "signature-service")]
+pub const DEFAULT_SIGNATURE_SERVICE_CAPACITY: usize = 100;
+
 /// This service holds the node's private key. It takes digests as input and returns a signature
 /// over the digest (through a one-shot channel).
+///
+/// The service can be instantiated with the [traits::KeyPair] of any scheme (or any other
+/// [signature::Signer]), and returns the signature type of that scheme:
+/// ```rust
+/// use fastcrypto::{ed25519::Ed25519KeyPair, traits::KeyPair, Hash, SignatureService, Verifier};
+/// use rand::{rngs::StdRng, SeedableRng};
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+///     let kp = Ed25519KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+///     let pk = kp.public().clone();
+///     let mut service = SignatureService::new(kp);
+///
+///     let message: &[u8] = b"Hello, world!";
+///     let digest = message.digest();
+///     let signature = service.request_signature(digest).await;
+///     assert!(pk.verify(digest.as_ref(), &signature).is_ok());
+/// # }
+/// ```
+#[cfg(feature = "signature-service")]
 #[derive(Clone)]
 pub struct SignatureService<Signature: traits::Authenticator> {
-    channel: Sender<(Digest, oneshot::Sender<Signature>)>,
+    channel: Sender<(Digest<DIGEST_LEN>, oneshot::Sender<Signature>)>,
+    // The size of the queue, used to report its depth.
+    #[cfg(feature = "tracing")]
+    capacity: usize,
 }
 
+#[cfg(feature = "signature-service")]
 impl<Signature: traits::Authenticator> SignatureService<Signature> {
     pub fn new<S>(signer: S) -> Self
     where
         S: signature::Signer<Signature> + Send + 'static,
     {
-        let (tx, mut rx): (Sender<(Digest, oneshot::Sender<_>)>, _) = channel(100);
+        Self::new_with_capacity(signer, DEFAULT_SIGNATURE_SERVICE_CAPACITY)
+            .expect("the default capacity is positive")
+    }
+
+    /// Spawn a service whose queue holds at most `capacity` pending requests. Once the queue is
+    /// full, [Self::request_signature] waits for a slot to free up, so that callers experience
+    /// backpressure instead of unbounded memory growth when requests outpace the signer.
+    ///
+    /// Fails with [error::FastCryptoError::InvalidInput] if `capacity` is zero.
+    pub fn new_with_capacity<S>(signer: S, capacity: usize) -> Result<Self, error::FastCryptoError>
+    where
+        S: signature::Signer<Signature> + Send + 'static,
+    {
+        if capacity == 0 {
+            return Err(error::FastCryptoError::InvalidInput);
+        }
+        let (tx, mut rx): (Sender<(Digest<DIGEST_LEN>, oneshot::Sender<_>)>, _) = channel(capacity);
         tokio::spawn(async move {
             while let Some((digest, sender)) = rx.recv().await {
                 let signature = signer.sign(&digest.0);
                 let _ = sender.send(signature);
             }
         });
-        Self { channel: tx }
+        Ok(Self {
+            channel: tx,
+            #[cfg(feature = "tracing")]
+            capacity,
+        })
+    }
+
+    /// Request a signature on `digest`. With the `tracing` feature, each request is wrapped in a
+    /// `request_signature` span at debug level, which records the number of requests queued when
+    /// it was made and the time until the signature was received in microseconds.
+    pub async fn request_signature(&mut self, digest: Digest<DIGEST_LEN>) -> Signature {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+
+            let span = tracing::debug_span!(
+                "request_signature",
+                queue_depth = self.capacity - self.channel.capacity(),
+                latency_us = tracing::field::Empty,
+            );
+            let start = std::time::Instant::now();
+            let signature = self
+                .request_signature_untraced(digest)
+                .instrument(span.clone())
+                .await;
+            span.record("latency_us", start.elapsed().as_micros() as u64);
+            signature
+        }
+        #[cfg(not(feature = "tracing"))]
+        self.request_signature_untraced(digest).await
     }
 
-    pub async fn request_signature(&mut self, digest: Digest) -> Signature {
+    async fn request_signature_untraced(&mut self, digest: Digest<DIGEST_LEN>) -> Signature {
         let (sender, receiver): (oneshot::Sender<_>, oneshot::Receiver<_>) = oneshot::channel();
         if let Err(e) = self.channel.send((digest, sender)).await {
             panic!("Failed to send message Signature Service: {e}");
@@ -159,9 +335,9 @@ impl<Signature: traits::Authenticator> SignatureService<Signature> {
 }
 
 impl Hash for &[u8] {
-    type TypedDigest = Digest;
+    type TypedDigest = Digest<DIGEST_LEN>;
 
-    fn digest(&self) -> Digest {
-        Digest(blake2b_256(|hasher| hasher.update(self)))
+    fn digest(&self) -> Digest<DIGEST_LEN> {
+        <hash::Blake2b256 as hash::HashFunction<DIGEST_LEN>>::digest(self)
     }
 }
@@ -0,0 +1,183 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a binary Merkle tree over digests, e.g. of transactions, with proofs of
+//! inclusion of a single leaf.
+//!
+//! Leaves and internal nodes are hashed as in RFC 6962: a leaf `l` is hashed as `H(0x00 || l)`
+//! and a node with children `a` and `b` as `H(0x01 || a || b)`, so the hash of a leaf can never
+//! be mistaken for the hash of a node. If a level has an odd number of nodes, the last node is
+//! moved up to the next level unchanged.
+//!
+//! The root is `H(0x02 || n || t)`, where `n` is the number of leaves as a big-endian `u64` and
+//! `t` is the top node of the tree. Committing to the number of leaves ties a proof to the
+//! position of its leaf: otherwise, a node over several leaves could be presented as a single
+//! leaf of a smaller tree.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::hash::{HashFunction, Sha256};
+//! use fastcrypto::merkle::MerkleTree;
+//! # fn main() {
+//!     let leaves: Vec<_> = (0u8..5).map(|i| Sha256::digest([i])).collect();
+//!     let tree = MerkleTree::from_leaves::<Sha256>(&leaves).unwrap();
+//!     let proof = tree.proof(3).unwrap();
+//!     assert!(proof.verify::<Sha256>(&tree.root(), &leaves[3], 3).is_ok());
+//!     assert!(proof.verify::<Sha256>(&tree.root(), &leaves[2], 3).is_err());
+//! # }
+//! ```
+
+use crate::{
+    error::FastCryptoError,
+    hash::{Digest, HashFunction},
+};
+use serde::{Deserialize, Serialize};
+
+pub mod sparse;
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+const ROOT_PREFIX: u8 = 0x02;
+
+fn hash_leaf<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+    leaf: &Digest<DIGEST_LEN>,
+) -> Digest<DIGEST_LEN> {
+    let mut hash = H::default();
+    hash.update([LEAF_PREFIX]);
+    hash.update(leaf);
+    hash.finalize()
+}
+
+fn hash_node<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+    left: &Digest<DIGEST_LEN>,
+    right: &Digest<DIGEST_LEN>,
+) -> Digest<DIGEST_LEN> {
+    let mut hash = H::default();
+    hash.update([NODE_PREFIX]);
+    hash.update(left);
+    hash.update(right);
+    hash.finalize()
+}
+
+fn hash_root<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+    leaf_count: usize,
+    top: &Digest<DIGEST_LEN>,
+) -> Digest<DIGEST_LEN> {
+    let mut hash = H::default();
+    hash.update([ROOT_PREFIX]);
+    hash.update((leaf_count as u64).to_be_bytes());
+    hash.update(top);
+    hash.finalize()
+}
+
+/// A Merkle tree over a non-empty list of digests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree<const DIGEST_LEN: usize> {
+    /// The hashes of all levels, from the hashes of the leaves to the top node.
+    levels: Vec<Vec<Digest<DIGEST_LEN>>>,
+    root: Digest<DIGEST_LEN>,
+}
+
+impl<const DIGEST_LEN: usize> MerkleTree<DIGEST_LEN> {
+    /// Build the tree over `leaves` with the hash function `H`. Fails with
+    /// [FastCryptoError::InvalidInput] if there are no leaves.
+    pub fn from_leaves<H: HashFunction<DIGEST_LEN>>(
+        leaves: &[Digest<DIGEST_LEN>],
+    ) -> Result<Self, FastCryptoError> {
+        if leaves.is_empty() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut levels = vec![leaves
+            .iter()
+            .map(hash_leaf::<H, DIGEST_LEN>)
+            .collect::<Vec<_>>()];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node::<H, DIGEST_LEN>(left, right),
+                    [last] => *last,
+                    _ => unreachable!("chunks have one or two elements"),
+                })
+                .collect();
+            levels.push(next);
+        }
+        let root = hash_root::<H, DIGEST_LEN>(leaves.len(), &levels[levels.len() - 1][0]);
+        Ok(Self { levels, root })
+    }
+
+    /// The root of the tree, which commits to all leaves, their order and their number.
+    pub fn root(&self) -> Digest<DIGEST_LEN> {
+        self.root
+    }
+
+    /// The number of leaves of the tree.
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// A proof that the leaf at position `index` is in the tree. Fails with
+    /// [FastCryptoError::InvalidInput] if there is no such leaf.
+    pub fn proof(&self, index: usize) -> Result<MerkleProof<DIGEST_LEN>, FastCryptoError> {
+        if index >= self.leaf_count() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut siblings = Vec::with_capacity(self.levels.len() - 1);
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            // The last node of a level with an odd number of nodes has no sibling.
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+            position /= 2;
+        }
+        Ok(MerkleProof {
+            leaf_count: self.leaf_count(),
+            siblings,
+        })
+    }
+}
+
+/// A proof of inclusion of a leaf in a [MerkleTree], consisting of the siblings of the nodes on
+/// the path from the leaf to the root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof<const DIGEST_LEN: usize> {
+    leaf_count: usize,
+    siblings: Vec<Digest<DIGEST_LEN>>,
+}
+
+impl<const DIGEST_LEN: usize> MerkleProof<DIGEST_LEN> {
+    /// Verify that `leaf` is the leaf at position `index` of the tree with the given `root`, built
+    /// with the hash function `H`. Fails with [FastCryptoError::InvalidInput] if `index` is not
+    /// smaller than the number of leaves of the tree, and with [FastCryptoError::InvalidProof] if
+    /// the proof does not verify.
+    pub fn verify<H: HashFunction<DIGEST_LEN>>(
+        &self,
+        root: &Digest<DIGEST_LEN>,
+        leaf: &Digest<DIGEST_LEN>,
+        index: usize,
+    ) -> Result<(), FastCryptoError> {
+        if index >= self.leaf_count {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut siblings = self.siblings.iter();
+        let mut hash = hash_leaf::<H, DIGEST_LEN>(leaf);
+        let (mut position, mut width) = (index, self.leaf_count);
+        while width > 1 {
+            if position % 2 == 1 {
+                let sibling = siblings.next().ok_or(FastCryptoError::InvalidProof)?;
+                hash = hash_node::<H, DIGEST_LEN>(sibling, &hash);
+            } else if position + 1 < width {
+                let sibling = siblings.next().ok_or(FastCryptoError::InvalidProof)?;
+                hash = hash_node::<H, DIGEST_LEN>(&hash, sibling);
+            }
+            position /= 2;
+            width = width / 2 + width % 2;
+        }
+        if siblings.next().is_some() || hash_root::<H, DIGEST_LEN>(self.leaf_count, &hash) != *root
+        {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
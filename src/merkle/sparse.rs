@@ -0,0 +1,204 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a sparse Merkle tree, i.e., a Merkle tree with a leaf for each of the
+//! 2^256 possible keys, most of which are empty. The root commits to a key-value map, and a
+//! [SmtProof] shows either that a key has a given value or that it has no value.
+//!
+//! The leaf of a key with a value is hashed as `H(0x00 || key || value)` and an empty leaf is the
+//! zero digest. Internal nodes are hashed as in the [MerkleTree](super::MerkleTree). All empty
+//! subtrees of the same height have the same hash, which is computed once when the tree is created,
+//! so only the nodes on the paths to non-empty leaves are stored.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::hash::Sha256;
+//! use fastcrypto::merkle::sparse::SparseMerkleTree;
+//! # fn main() {
+//!     let mut tree = SparseMerkleTree::<Sha256, 32>::new();
+//!     let value: &[u8] = b"Hello, world!";
+//!     tree.insert([1; 32], value);
+//!     let root = tree.root();
+//!     let proof = tree.get_proof(&[1; 32]);
+//!     assert!(proof.verify::<Sha256>(&root, &[1; 32], Some(value)).is_ok());
+//!     let proof = tree.get_proof(&[2; 32]);
+//!     assert!(proof.verify::<Sha256>(&root, &[2; 32], None).is_ok());
+//! # }
+//! ```
+
+use super::{hash_node, LEAF_PREFIX};
+use crate::{
+    error::FastCryptoError,
+    hash::{Digest, HashFunction},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, marker::PhantomData};
+
+/// The length of the keys in bytes.
+pub const KEY_LENGTH: usize = 32;
+
+/// The height of the tree, which is the number of bits of a key.
+const HEIGHT: usize = 8 * KEY_LENGTH;
+
+type Key = [u8; KEY_LENGTH];
+
+fn hash_leaf<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>(
+    key: &Key,
+    value: &[u8],
+) -> Digest<DIGEST_LEN> {
+    let mut hash = H::default();
+    hash.update([LEAF_PREFIX]);
+    hash.update(key);
+    hash.update(value);
+    hash.finalize()
+}
+
+/// The hashes of the empty subtrees of height 0 to [HEIGHT].
+fn empty_subtrees<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize>() -> Vec<Digest<DIGEST_LEN>>
+{
+    std::iter::successors(Some(Digest::default()), |subtree| {
+        Some(hash_node::<H, DIGEST_LEN>(subtree, subtree))
+    })
+    .take(HEIGHT + 1)
+    .collect()
+}
+
+/// Returns true if the node of height `height` on the path to `key` is a right child, i.e., if the
+/// corresponding bit of the key is set. The most significant bit decides below the root.
+fn is_right_child(key: &Key, height: usize) -> bool {
+    let bit = HEIGHT - 1 - height;
+    (key[bit / 8] >> (7 - bit % 8)) & 1 == 1
+}
+
+/// The position of the node of height `height` on the path to `key`: the key with its lowest
+/// `height` bits cleared.
+fn position(key: &Key, height: usize) -> Key {
+    let mut position = *key;
+    for bit in HEIGHT - height..HEIGHT {
+        position[bit / 8] &= !(0x80 >> (bit % 8));
+    }
+    position
+}
+
+/// A sparse Merkle tree mapping 256-bit keys to values, with the hash function `H`.
+pub struct SparseMerkleTree<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize> {
+    values: HashMap<Key, Vec<u8>>,
+    /// The hashes of all non-empty nodes below the root, by height and position.
+    nodes: HashMap<(usize, Key), Digest<DIGEST_LEN>>,
+    empty_subtrees: Vec<Digest<DIGEST_LEN>>,
+    root: Digest<DIGEST_LEN>,
+    _hash_function: PhantomData<H>,
+}
+
+impl<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize> Default
+    for SparseMerkleTree<H, DIGEST_LEN>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: HashFunction<DIGEST_LEN>, const DIGEST_LEN: usize> SparseMerkleTree<H, DIGEST_LEN> {
+    /// Create a tree in which all keys are empty.
+    pub fn new() -> Self {
+        let empty_subtrees = empty_subtrees::<H, DIGEST_LEN>();
+        Self {
+            values: HashMap::new(),
+            nodes: HashMap::new(),
+            root: empty_subtrees[HEIGHT],
+            empty_subtrees,
+            _hash_function: PhantomData,
+        }
+    }
+
+    /// The root of the tree, which commits to all keys and their values.
+    pub fn root(&self) -> Digest<DIGEST_LEN> {
+        self.root
+    }
+
+    /// The value of `key`, if it has one.
+    pub fn get(&self, key: &Key) -> Option<&[u8]> {
+        self.values.get(key).map(Vec::as_slice)
+    }
+
+    /// Set the value of `key` and update the hashes on the path from its leaf to the root. Returns
+    /// the previous value of `key`, if it had one.
+    pub fn insert(&mut self, key: Key, value: &[u8]) -> Option<Vec<u8>> {
+        let mut hash = hash_leaf::<H, DIGEST_LEN>(&key, value);
+        for height in 0..HEIGHT {
+            self.nodes.insert((height, position(&key, height)), hash);
+            let sibling = self.sibling(&key, height);
+            hash = if is_right_child(&key, height) {
+                hash_node::<H, DIGEST_LEN>(&sibling, &hash)
+            } else {
+                hash_node::<H, DIGEST_LEN>(&hash, &sibling)
+            };
+        }
+        self.root = hash;
+        self.values.insert(key, value.to_vec())
+    }
+
+    /// A proof of the value of `key` if it has one, and otherwise a proof that it is empty.
+    pub fn get_proof(&self, key: &Key) -> SmtProof<DIGEST_LEN> {
+        SmtProof {
+            siblings: (0..HEIGHT)
+                .map(|height| {
+                    let sibling = self.sibling(key, height);
+                    (sibling != self.empty_subtrees[height]).then_some(sibling)
+                })
+                .collect(),
+        }
+    }
+
+    /// The hash of the sibling of the node of height `height` on the path to `key`.
+    fn sibling(&self, key: &Key, height: usize) -> Digest<DIGEST_LEN> {
+        let mut sibling = position(key, height);
+        let bit = HEIGHT - 1 - height;
+        sibling[bit / 8] ^= 0x80 >> (bit % 8);
+        self.nodes
+            .get(&(height, sibling))
+            .copied()
+            .unwrap_or(self.empty_subtrees[height])
+    }
+}
+
+/// A proof of the value of a key in a [SparseMerkleTree], or that the key has no value. It holds
+/// the siblings of the nodes on the path from the leaf of the key to the root, starting at the
+/// leaf, where empty subtrees are omitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmtProof<const DIGEST_LEN: usize> {
+    siblings: Vec<Option<Digest<DIGEST_LEN>>>,
+}
+
+impl<const DIGEST_LEN: usize> SmtProof<DIGEST_LEN> {
+    /// Verify that `key` has the value `value` in the tree with the given `root`, built with the
+    /// hash function `H`, or that it has no value if `value` is `None`. Fails with
+    /// [FastCryptoError::InvalidProof] otherwise.
+    pub fn verify<H: HashFunction<DIGEST_LEN>>(
+        &self,
+        root: &Digest<DIGEST_LEN>,
+        key: &Key,
+        value: Option<&[u8]>,
+    ) -> Result<(), FastCryptoError> {
+        if self.siblings.len() != HEIGHT {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        let empty_subtrees = empty_subtrees::<H, DIGEST_LEN>();
+        let mut hash = match value {
+            Some(value) => hash_leaf::<H, DIGEST_LEN>(key, value),
+            None => empty_subtrees[0],
+        };
+        for (height, sibling) in self.siblings.iter().enumerate() {
+            let sibling = sibling.as_ref().unwrap_or(&empty_subtrees[height]);
+            hash = if is_right_child(key, height) {
+                hash_node::<H, DIGEST_LEN>(sibling, &hash)
+            } else {
+                hash_node::<H, DIGEST_LEN>(&hash, sibling)
+            };
+        }
+        if hash != *root {
+            return Err(FastCryptoError::InvalidProof);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a weighted threshold multisig over keys of possibly different schemes.
+//! A [MultiSigPublicKey] lists the member keys, a weight per key and a threshold, and a
+//! [MultiSig] is valid if the weights of the members who signed sum up to at least the threshold.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::ed25519::Ed25519KeyPair;
+//! use fastcrypto::multisig::{MemberPublicKey, MemberSignature, MultiSig, MultiSigPublicKey};
+//! use fastcrypto::secp256k1::Secp256k1KeyPair;
+//! use fastcrypto::traits::KeyPair;
+//! use rand::thread_rng;
+//! use signature::Signer;
+//! # fn main() {
+//!     let ed25519 = Ed25519KeyPair::generate(&mut thread_rng());
+//!     let secp256k1 = Secp256k1KeyPair::generate(&mut thread_rng());
+//!     let pk = MultiSigPublicKey::new(
+//!         vec![
+//!             MemberPublicKey::Ed25519(ed25519.public().clone()),
+//!             MemberPublicKey::Secp256k1(secp256k1.public().clone()),
+//!         ],
+//!         vec![1, 1],
+//!         2,
+//!     )
+//!     .unwrap();
+//!
+//!     let msg = b"Hello, world!";
+//!     let multisig = MultiSig::new(
+//!         vec![
+//!             MemberSignature::Ed25519(ed25519.sign(msg)),
+//!             MemberSignature::Secp256k1(secp256k1.sign(msg)),
+//!         ],
+//!         0b11,
+//!     );
+//!     assert!(multisig.verify(&pk, msg).is_ok());
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use signature::Verifier;
+
+use crate::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    error::FastCryptoError,
+    secp256k1::{Secp256k1PublicKey, Secp256k1Signature},
+};
+
+/// The maximum number of keys in a multisig, bounded by the size of the bitmap.
+pub const MAX_SIGNERS_IN_MULTISIG: usize = 16;
+
+pub type WeightUnit = u8;
+pub type ThresholdUnit = u16;
+pub type BitmapUnit = u16;
+
+/// A public key of one of the members of a multisig.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MemberPublicKey {
+    Ed25519(Ed25519PublicKey),
+    Secp256k1(Secp256k1PublicKey),
+}
+
+/// A signature by one of the members of a multisig.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MemberSignature {
+    Ed25519(Ed25519Signature),
+    Secp256k1(Secp256k1Signature),
+}
+
+impl MemberPublicKey {
+    /// Verify a member signature. Fails if the signature is not of the same scheme as the key.
+    pub fn verify(&self, msg: &[u8], signature: &MemberSignature) -> Result<(), FastCryptoError> {
+        let result = match (self, signature) {
+            (MemberPublicKey::Ed25519(pk), MemberSignature::Ed25519(sig)) => pk.verify(msg, sig),
+            (MemberPublicKey::Secp256k1(pk), MemberSignature::Secp256k1(sig)) => {
+                pk.verify(msg, sig)
+            }
+            _ => return Err(FastCryptoError::InvalidInput),
+        };
+        result.map_err(|_| FastCryptoError::InvalidSignature)
+    }
+}
+
+/// The public key of a multisig: the member keys, their weights and the threshold the weights
+/// of the signers must reach. Deserialization performs the same checks as [MultiSigPublicKey::new].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "RawMultiSigPublicKey")]
+pub struct MultiSigPublicKey {
+    keys: Vec<MemberPublicKey>,
+    weights: Vec<WeightUnit>,
+    threshold: ThresholdUnit,
+}
+
+/// The serialized form of a [MultiSigPublicKey], which is validated when it is converted.
+#[derive(Deserialize)]
+struct RawMultiSigPublicKey {
+    keys: Vec<MemberPublicKey>,
+    weights: Vec<WeightUnit>,
+    threshold: ThresholdUnit,
+}
+
+impl TryFrom<RawMultiSigPublicKey> for MultiSigPublicKey {
+    type Error = FastCryptoError;
+
+    fn try_from(raw: RawMultiSigPublicKey) -> Result<Self, Self::Error> {
+        Self::new(raw.keys, raw.weights, raw.threshold)
+    }
+}
+
+impl MultiSigPublicKey {
+    /// Create a new multisig public key. Fails with [FastCryptoError::InvalidInput] if there are
+    /// no keys or more than [MAX_SIGNERS_IN_MULTISIG], if `keys` and `weights` differ in length,
+    /// if a weight is zero, if a key appears twice, or if the threshold is zero or cannot be
+    /// reached by all members together.
+    pub fn new(
+        keys: Vec<MemberPublicKey>,
+        weights: Vec<WeightUnit>,
+        threshold: ThresholdUnit,
+    ) -> Result<Self, FastCryptoError> {
+        if keys.is_empty()
+            || keys.len() > MAX_SIGNERS_IN_MULTISIG
+            || keys.len() != weights.len()
+            || weights.iter().any(|w| *w == 0)
+            || threshold == 0
+            || weights
+                .iter()
+                .map(|w| *w as ThresholdUnit)
+                .sum::<ThresholdUnit>()
+                < threshold
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        if keys
+            .iter()
+            .enumerate()
+            .any(|(i, key)| keys[i + 1..].contains(key))
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            keys,
+            weights,
+            threshold,
+        })
+    }
+
+    pub fn keys(&self) -> &[MemberPublicKey] {
+        &self.keys
+    }
+
+    pub fn weights(&self) -> &[WeightUnit] {
+        &self.weights
+    }
+
+    pub fn threshold(&self) -> ThresholdUnit {
+        self.threshold
+    }
+}
+
+/// A multisig. Bit `i` of the bitmap is set if the member with index `i` signed, and the
+/// signatures are given in increasing order of the index of their signer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MultiSig {
+    sigs: Vec<MemberSignature>,
+    bitmap: BitmapUnit,
+}
+
+impl MultiSig {
+    pub fn new(sigs: Vec<MemberSignature>, bitmap: BitmapUnit) -> Self {
+        Self { sigs, bitmap }
+    }
+
+    pub fn sigs(&self) -> &[MemberSignature] {
+        &self.sigs
+    }
+
+    pub fn bitmap(&self) -> BitmapUnit {
+        self.bitmap
+    }
+
+    /// Verify the multisig on `msg`. Fails with [FastCryptoError::InvalidInput] if the bitmap
+    /// refers to members that do not exist or does not match the number of signatures, and with
+    /// [FastCryptoError::InvalidSignature] if a signature is invalid or the weight of the signers
+    /// is below the threshold.
+    pub fn verify(&self, pk: &MultiSigPublicKey, msg: &[u8]) -> Result<(), FastCryptoError> {
+        let signers = (0..BitmapUnit::BITS as usize)
+            .filter(|i| self.bitmap & (1 << i) != 0)
+            .collect::<Vec<_>>();
+        if signers.iter().any(|i| *i >= pk.keys.len()) || signers.len() != self.sigs.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        let mut weight: ThresholdUnit = 0;
+        for (i, sig) in signers.into_iter().zip(&self.sigs) {
+            let (key, key_weight) = pk
+                .keys
+                .get(i)
+                .zip(pk.weights.get(i))
+                .ok_or(FastCryptoError::InvalidInput)?;
+            key.verify(msg, sig)?;
+            weight = weight.saturating_add(*key_weight as ThresholdUnit);
+        }
+        if weight < pk.threshold {
+            return Err(FastCryptoError::InvalidSignature);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains the Poseidon hash function over the BN254 scalar field, instantiated
+//! with the round constants and MDS matrices of [circomlib](https://github.com/iden3/circomlib),
+//! so that hashes computed here match those computed inside circom circuits. It is backed by the
+//! [light-poseidon](https://docs.rs/light-poseidon) crate.
+//!
+//! Example:
+//! ```rust
+//! use ark_bn254::Fr;
+//! use fastcrypto::poseidon::hash;
+//! # fn main() {
+//!     let digest = hash(&[Fr::from(1u64), Fr::from(2u64)]).unwrap();
+//! # }
+//! ```
+
+use crate::error::FastCryptoError;
+use ark_bn254::Fr;
+use light_poseidon::{Poseidon, PoseidonHasher};
+
+/// The maximum number of inputs supported by a single Poseidon instance.
+pub const POSEIDON_MAX_INPUTS: usize = 12;
+
+/// A Poseidon instance hashing a fixed number of BN254 scalars into a single scalar.
+pub struct PoseidonHash {
+    arity: usize,
+    instance: Poseidon<Fr>,
+}
+
+impl PoseidonHash {
+    /// Create an instance hashing exactly `arity` inputs. Fails unless `1 <= arity <=
+    /// POSEIDON_MAX_INPUTS`.
+    pub fn new(arity: usize) -> Result<Self, FastCryptoError> {
+        if arity == 0 || arity > POSEIDON_MAX_INPUTS {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let instance =
+            Poseidon::<Fr>::new_circom(arity).map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(PoseidonHash { arity, instance })
+    }
+
+    /// Hash the given inputs. Fails if the number of inputs does not match the arity of this
+    /// instance.
+    pub fn hash(&mut self, inputs: &[Fr]) -> Result<Fr, FastCryptoError> {
+        if inputs.len() != self.arity {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        self.instance
+            .hash(inputs)
+            .map_err(|_| FastCryptoError::GeneralOpaqueError)
+    }
+}
+
+/// Hash between 1 and [POSEIDON_MAX_INPUTS] scalars into a single scalar, picking the Poseidon
+/// instance matching the number of inputs.
+pub fn hash(inputs: &[Fr]) -> Result<Fr, FastCryptoError> {
+    PoseidonHash::new(inputs.len())?.hash(inputs)
+}
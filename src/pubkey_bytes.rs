@@ -2,16 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use self::sealed::SealedPublicKeyLength;
-use crate::traits::{ToFromBytes, VerifyingKey};
-use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, Bytes};
+use crate::traits::{check_length, FixedLength, ToFromBytes, VerifyingKey};
+use base64ct::{Base64, Encoding};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{Bytes, DeserializeAs, SerializeAs};
 use std::{fmt::Display, marker::PhantomData, str::FromStr};
 
 /// A generic construction representing bytes who claim to be the instance of a public key.
-#[serde_as]
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+///
+/// It is serialized as a base64 string in human readable formats and as raw bytes otherwise, like
+/// the public key itself, but the bytes are not checked to be a valid key when deserializing.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct PublicKeyBytes<T, const N: usize> {
-    #[serde_as(as = "Bytes")]
     bytes: [u8; N],
     phantom: PhantomData<T>,
 }
@@ -33,11 +35,17 @@ impl<T, const N: usize> Display for PublicKeyBytes<T, N> {
     }
 }
 
+impl<T, const N: usize> FixedLength for PublicKeyBytes<T, N> {
+    const LENGTH: usize = N;
+}
+
 impl<T: VerifyingKey, const N: usize> ToFromBytes for PublicKeyBytes<T, N> {
     fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
-        let bytes: [u8; N] = bytes.try_into().map_err(signature::Error::from_source)?;
+        check_length::<Self>(bytes)?;
+        let mut array = [0u8; N];
+        array.copy_from_slice(bytes);
         Ok(PublicKeyBytes {
-            bytes,
+            bytes: array,
             phantom: PhantomData,
         })
     }
@@ -79,6 +87,32 @@ impl<T: VerifyingKey, const N: usize> FromStr for PublicKeyBytes<T, N> {
 
 impl<T: VerifyingKey, const N: usize> Copy for PublicKeyBytes<T, N> {}
 
+impl<T, const N: usize> Serialize for PublicKeyBytes<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            Base64::encode_string(&self.bytes).serialize(serializer)
+        } else {
+            Bytes::serialize_as(&self.bytes, serializer)
+        }
+    }
+}
+
+impl<'de, T: VerifyingKey, const N: usize> Deserialize<'de> for PublicKeyBytes<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let value = Base64::decode_vec(&s).map_err(de::Error::custom)?;
+            Self::from_bytes(&value).map_err(de::Error::custom)
+        } else {
+            let bytes: [u8; N] = Bytes::deserialize_as(deserializer)?;
+            Ok(PublicKeyBytes {
+                bytes,
+                phantom: PhantomData,
+            })
+        }
+    }
+}
+
 // This guarantees the security of the constructor of a `PublicKeyBytes` instance
 // TODO: replace this clunky sealed marker trait once feature(associated_const_equality) stabilizes
 mod sealed {
@@ -1,53 +1,121 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
+    error::FastCryptoError,
+    hash::{Digest, HashFunction},
     pubkey_bytes::PublicKeyBytes,
-    serde_helpers::keypair_decode_base64,
-    traits::{Authenticator, EncodeDecodeBase64, KeyPair, SigningKey, ToFromBytes, VerifyingKey},
+    traits::{
+        check_length, from_base64_str, Authenticator, EncodeDecodeBase64, FixedLength, SigningKey,
+        ToFromBytes, VerifyingKey,
+    },
 };
+#[cfg(feature = "signing")]
+use crate::{serde_helpers::keypair_decode_base64, traits::KeyPair, x25519::SharedSecret};
 use base64ct::{Base64, Encoding};
+use k256::{
+    elliptic_curve::{ops::Reduce, sec1::ToEncodedPoint},
+    FieldBytes, ProjectivePoint, U256,
+};
 use once_cell::sync::{Lazy, OnceCell};
-use rust_secp256k1::{
-    constants,
-    ecdsa::{RecoverableSignature, RecoveryId},
-    All, Message, PublicKey, Secp256k1, SecretKey,
+use pkcs8::{
+    der::{Decode, Encode},
+    AlgorithmIdentifier, ObjectIdentifier, PrivateKeyInfo,
 };
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use sec1::EcPrivateKey;
 use serde::{de, Deserialize, Serialize};
-use signature::{Signature, Signer, Verifier};
+#[cfg(feature = "signing")]
+use signature::Signer;
+use signature::{Signature, Verifier};
+use spki::SubjectPublicKeyInfo;
 use std::{
     fmt::{self, Debug, Display},
+    io::{ErrorKind, Read},
     str::FromStr,
 };
-use zeroize::Zeroize;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
 
-pub static SECP256K1: Lazy<rust_secp256k1::Secp256k1<All>> =
-    Lazy::new(rust_secp256k1::Secp256k1::new);
+#[cfg(feature = "signing")]
+use self::backend::ecdh;
+use self::backend::{
+    constants,
+    ecdsa::{RecoverableSignature, RecoveryId},
+    All, Message, PublicKey, Scalar, SecretKey,
+};
+
+#[cfg(feature = "signing")]
+pub mod bip32;
+#[cfg(any(test, feature = "pure-rust", not(feature = "rust_secp256k1")))]
+pub mod pure_rust;
+#[cfg(feature = "signing")]
+pub mod tecdsa;
+
+/// The secp256k1 implementation: libsecp256k1 through rust_secp256k1 by default, or
+/// `pure_rust` on top of k256 with the `pure-rust` feature. Both give byte-identical keys and
+/// signatures, and `pure_rust` implements the subset of the rust_secp256k1 API used here, so the
+/// types returned by [Secp256k1PublicKey::pubkey], [Secp256k1PrivateKey::privkey] and
+/// [Secp256k1Signature::sig] have the same methods with both.
+#[cfg(any(feature = "pure-rust", not(feature = "rust_secp256k1")))]
+pub use self::pure_rust as backend;
+#[cfg(all(feature = "rust_secp256k1", not(feature = "pure-rust")))]
+pub use rust_secp256k1 as backend;
+
+pub static SECP256K1: Lazy<backend::Secp256k1<All>> = Lazy::new(backend::Secp256k1::new);
 
-#[readonly::make]
 #[derive(Debug, Clone)]
 pub struct Secp256k1PublicKey {
-    pub pubkey: PublicKey,
-    pub bytes: OnceCell<[u8; constants::PUBLIC_KEY_SIZE]>,
+    pub(crate) pubkey: PublicKey,
+    pub(crate) bytes: OnceCell<[u8; SECP256K1_PUBLIC_KEY_LENGTH]>,
 }
 
 pub type Secp256k1PublicKeyBytes =
     PublicKeyBytes<Secp256k1PublicKey, { Secp256k1PublicKey::LENGTH }>;
 
-#[readonly::make]
-#[derive(Debug)]
 pub struct Secp256k1PrivateKey {
-    pub privkey: SecretKey,
-    pub bytes: OnceCell<[u8; constants::SECRET_KEY_SIZE]>,
+    pub(crate) privkey: SecretKey,
+    pub(crate) bytes: OnceCell<[u8; SECP256K1_PRIVATE_KEY_LENGTH]>,
 }
 
+/// The algorithm identifier of elliptic curve keys, `id-ecPublicKey`, from RFC 5480.
+const EC_PUBLIC_KEY_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+
+/// The identifier of the secp256k1 curve from SEC 2.
+const SECP256K1_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.10");
+
 // Compact signature followed by one extra byte for recover id, used to recover public key from signature.
 pub const RECOVERABLE_SIGNATURE_SIZE: usize = constants::COMPACT_SIGNATURE_SIZE + 1;
 
-#[readonly::make]
+pub const SECP256K1_PRIVATE_KEY_LENGTH: usize = constants::SECRET_KEY_SIZE;
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = constants::PUBLIC_KEY_SIZE;
+pub const SECP256K1_SIGNATURE_LENGTH: usize = RECOVERABLE_SIGNATURE_SIZE;
+
 #[derive(Debug, Clone)]
 pub struct Secp256k1Signature {
-    pub sig: rust_secp256k1::ecdsa::RecoverableSignature,
-    pub bytes: OnceCell<[u8; RECOVERABLE_SIGNATURE_SIZE]>,
+    pub(crate) sig: RecoverableSignature,
+    pub(crate) bytes: OnceCell<[u8; SECP256K1_SIGNATURE_LENGTH]>,
+}
+
+impl Secp256k1PublicKey {
+    /// The underlying public key of the [backend].
+    pub fn pubkey(&self) -> &PublicKey {
+        &self.pubkey
+    }
+}
+
+impl Secp256k1PrivateKey {
+    /// The underlying private key of the [backend].
+    pub fn privkey(&self) -> &SecretKey {
+        &self.privkey
+    }
+}
+
+impl Secp256k1Signature {
+    /// The underlying recoverable signature of the [backend].
+    pub fn sig(&self) -> &RecoverableSignature {
+        &self.sig
+    }
 }
 
 impl std::hash::Hash for Secp256k1PublicKey {
@@ -79,57 +147,432 @@ impl Eq for Secp256k1PublicKey {}
 impl VerifyingKey for Secp256k1PublicKey {
     type PrivKey = Secp256k1PrivateKey;
     type Sig = Secp256k1Signature;
-    const LENGTH: usize = constants::PUBLIC_KEY_SIZE;
+
+    fn verify_batch_empty_fail(
+        msg: &[u8],
+        pks: &[Self],
+        sigs: &[Self::Sig],
+    ) -> Result<(), eyre::Report> {
+        // The message is hashed once for the whole batch.
+        Self::verify_batch_message(&hash_message(msg), pks, sigs).map_err(|e| match e {
+            FastCryptoError::InvalidSignature => eyre::eyre!("Signature verification failed"),
+            _ => eyre::eyre!(
+                "Invalid batch: it is empty or the number of signatures and public keys differ"
+            ),
+        })
+    }
+
+    /// The message is hashed once for the whole batch.
+    fn verify_batch_constant_time(msg: &[u8], pks: &[Self], sigs: &[Self::Sig]) -> bool {
+        if sigs.is_empty() || pks.len() != sigs.len() {
+            return false;
+        }
+        let message = hash_message(msg);
+        pks.iter().zip(sigs).fold(true, |valid, (pk, sig)| {
+            valid & pk.verify_message(&message, sig).is_ok()
+        })
+    }
+
+    /// The public key is recovered from the signature once and compared with each key.
+    fn verify_any(pks: &[Self], msg: &[u8], sig: &Self::Sig) -> Result<usize, FastCryptoError> {
+        let recovered = sig
+            .sig
+            .recover(&hash_message(msg))
+            .map_err(|_| FastCryptoError::InvalidSignature)?;
+        pks.iter()
+            .position(|pk| pk.pubkey == recovered)
+            .ok_or(FastCryptoError::InvalidSignature)
+    }
+}
+
+impl FixedLength for Secp256k1PublicKey {
+    const LENGTH: usize = SECP256K1_PUBLIC_KEY_LENGTH;
 }
 
 impl Verifier<Secp256k1Signature> for Secp256k1PublicKey {
     fn verify(&self, msg: &[u8], signature: &Secp256k1Signature) -> Result<(), signature::Error> {
-        // k256 defaults to keccak256 as digest to hash message for sign/verify, thus use this hash function to match in proptest.
-        #[cfg(test)]
-        let message =
-            Message::from_slice(<sha3::Keccak256 as sha3::digest::Digest>::digest(msg).as_slice())
-                .unwrap();
+        self.verify_message(&hash_message(msg), signature)
+            .map_err(|e| e.into())
+    }
+}
 
-        #[cfg(not(test))]
-        let message = Message::from_hashed_data::<rust_secp256k1::hashes::sha256::Hash>(msg);
+/// The hash function used to hash messages to the digest which is signed.
+#[cfg(not(test))]
+type MessageHashFunction = crate::hash::Sha256;
+
+// k256 defaults to keccak256 as digest to hash message for sign/verify, thus use this hash function to match in proptest.
+#[cfg(test)]
+type MessageHashFunction = crate::hash::Keccak256;
+
+/// The tagged hash `SHA256(SHA256(tag) || SHA256(tag) || m)` from BIP-340, where `m` is the
+/// concatenation of `parts`.
+fn tagged_hash(tag: &[u8], parts: &[&[u8]]) -> Digest<32> {
+    let tag_hash = crate::hash::Sha256::digest(tag);
+    let mut hasher = crate::hash::Sha256::default();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    parts.iter().for_each(|part| hasher.update(part));
+    hasher.finalize()
+}
 
-        // If pubkey recovered from signature matches original pubkey, verifies signature.
-        // To ensure non-malleability of v, signature.verify_ecdsa() is not used since it will verify using only [r, s] without considering v.
-        match signature.sig.recover(&message) {
-            Ok(recovered_key) if self.as_bytes() == recovered_key.serialize().as_slice() => Ok(()),
-            _ => Err(signature::Error::new()),
-        }
-    }
+/// Hash a message to the digest which is signed.
+fn hash_message(msg: &[u8]) -> Message {
+    Message::from_slice(MessageHashFunction::digest(msg).as_ref())
+        .expect("digest has the message length")
+}
+
+/// Hash a message as Bitcoin's `signmessage` does: double SHA-256 of the message prefixed with
+/// `"\x18Bitcoin Signed Message:\n"` and its length as a compact size integer.
+fn bitcoin_message_hash(msg: &[u8]) -> Digest<{ constants::MESSAGE_SIZE }> {
+    let len = msg.len() as u64;
+    let compact_size = match len {
+        0..=0xfc => vec![len as u8],
+        0xfd..=0xffff => [&[0xfd][..], &(len as u16).to_le_bytes()[..]].concat(),
+        0x10000..=0xffff_ffff => [&[0xfe][..], &(len as u32).to_le_bytes()[..]].concat(),
+        _ => [&[0xff][..], &len.to_le_bytes()[..]].concat(),
+    };
+    let mut hash = crate::hash::Sha256::default();
+    hash.update(b"\x18Bitcoin Signed Message:\n");
+    hash.update(compact_size);
+    hash.update(msg);
+    crate::hash::Sha256::digest(hash.finalize())
 }
 
 impl Secp256k1PublicKey {
+    /// Verify a signature over a 32-byte digest, without hashing it again.
+    ///
+    /// **Warning:** `digest` must be the output of a cryptographic hash function over the message.
+    /// Passing a raw message here is incorrect: use [Verifier::verify] to verify a signature over
+    /// a message. Only a [Digest] is accepted to make this harder to get wrong:
+    ///
+    /// ```compile_fail
+    /// # use fastcrypto::secp256k1::Secp256k1KeyPair;
+    /// # use fastcrypto::traits::KeyPair;
+    /// # use signature::Signer;
+    /// # let kp = Secp256k1KeyPair::generate(&mut rand::thread_rng());
+    /// let signature = kp.sign(b"Hello, world!");
+    /// kp.public().verify_digest(b"Hello, world!", &signature);
+    /// ```
+    pub fn verify_digest(
+        &self,
+        digest: &Digest<{ constants::MESSAGE_SIZE }>,
+        signature: &Secp256k1Signature,
+    ) -> Result<(), signature::Error> {
+        self.verify_hashed(digest.as_ref(), signature)
+    }
+
+    /// Verify a signature created with [Secp256k1KeyPair::sign_with] using the hash function `H`.
+    pub fn verify_with<H: HashFunction<{ constants::MESSAGE_SIZE }>>(
+        &self,
+        msg: &[u8],
+        signature: &Secp256k1Signature,
+    ) -> Result<(), signature::Error> {
+        self.verify_digest(&H::digest(msg), signature)
+    }
+
     pub fn verify_hashed(
         &self,
         hased_msg: &[u8],
         signature: &Secp256k1Signature,
     ) -> Result<(), signature::Error> {
-        match Message::from_slice(hased_msg) {
-            Ok(message) => match signature.sig.recover(&message) {
-                Ok(recovered_key) if self.as_bytes() == recovered_key.serialize().as_slice() => {
-                    Ok(())
-                }
-                _ => Err(signature::Error::new()),
-            },
-            _ => Err(signature::Error::new()),
+        let message = Message::from_slice(hased_msg).map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(self.verify_message(&message, signature)?)
+    }
+
+    /// Verify a signature over a message read from `reader` until the end, without holding the
+    /// whole message in memory. The message is hashed incrementally, and the result is the same as
+    /// verifying it with [Verifier::verify]. Fails with [FastCryptoError::GeneralOpaqueError] if
+    /// reading fails.
+    pub fn verify_reader<R: Read>(
+        &self,
+        reader: &mut R,
+        signature: &Secp256k1Signature,
+    ) -> Result<(), FastCryptoError> {
+        let mut hasher = MessageHashFunction::default();
+        let mut buffer = [0u8; 8192];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => hasher.update(&buffer[..n]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => return Err(FastCryptoError::GeneralOpaqueError),
+            }
+        }
+        let message =
+            Message::from_slice(hasher.finalize().as_ref()).expect("digest has the message length");
+        self.verify_message(&message, signature)
+    }
+
+    /// Verify a 64-byte `(r, s)` signature without a recovery id over a message, as produced by
+    /// most ECDSA implementations. The message is hashed as in [Verifier::verify]. As with
+    /// [Signature::from_bytes], only signatures in the low-S form are accepted.
+    pub fn verify_compact(
+        &self,
+        msg: &[u8],
+        sig64: &[u8; constants::COMPACT_SIGNATURE_SIZE],
+    ) -> Result<(), FastCryptoError> {
+        let signature = backend::ecdsa::Signature::from_compact(sig64)
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        // libsecp256k1 rejects signatures in the high-S form.
+        SECP256K1
+            .verify_ecdsa(&hash_message(msg), &signature, &self.pubkey)
+            .map_err(|_| FastCryptoError::InvalidSignature)
+    }
+
+    /// Verify a 64-byte `(r, s)` signature over a 32-byte digest when the recovery id is not known,
+    /// and return the recovery id under which the signature recovers to this key. Together with
+    /// `sig64`, it gives a [Secp256k1Signature] with [Secp256k1Signature::from_parts].
+    ///
+    /// As with [Secp256k1Signature::from_bytes_unchecked], signatures in the high-S form are also
+    /// accepted, and `None` is returned if the signature is invalid.
+    pub fn verify_and_find_recovery_id(
+        &self,
+        digest: &Digest<{ constants::MESSAGE_SIZE }>,
+        sig64: &[u8; constants::COMPACT_SIGNATURE_SIZE],
+    ) -> Option<u8> {
+        let message = Message::from_slice(digest.as_ref()).expect("digest has the message length");
+        (0..4)
+            .find(|&recovery_id| {
+                RecoveryId::from_i32(recovery_id)
+                    .and_then(|id| RecoverableSignature::from_compact(sig64, id))
+                    .and_then(|sig| sig.recover(&message))
+                    .map_or(false, |recovered| recovered == self.pubkey)
+            })
+            .map(|recovery_id| recovery_id as u8)
+    }
+
+    /// Recover the public key of the signer of `msg` from a compact signature as produced by
+    /// Bitcoin's `signmessage`: a header byte followed by `(r, s)`. The header is 27 plus the
+    /// recovery id, plus 4 if the signer's address uses the compressed public key. Unlike
+    /// [Secp256k1Signature::recover], the message is hashed with the Bitcoin message prefix and
+    /// double SHA-256.
+    ///
+    /// Fails with [FastCryptoError::InvalidInput] if the header is not between 27 and 34 or if the
+    /// signature is rejected by [Signature::from_bytes], and with
+    /// [FastCryptoError::InvalidSignature] if no key can be recovered.
+    pub fn recover_compact(
+        signature: &[u8; RECOVERABLE_SIGNATURE_SIZE],
+        msg: &[u8],
+    ) -> Result<Self, FastCryptoError> {
+        let header = signature[0]
+            .checked_sub(27)
+            .filter(|header| *header < 8)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let mut compact = [0u8; constants::COMPACT_SIGNATURE_SIZE];
+        compact.copy_from_slice(&signature[1..]);
+        Secp256k1Signature::from_parts(&compact, header & 3)?
+            .recover(bitcoin_message_hash(msg).as_ref())
+            .map_err(|_| FastCryptoError::InvalidSignature)
+    }
+
+    /// Verify a batch of signatures over the same 32-byte digest, which is not hashed again. This
+    /// avoids hashing the message once per signature.
+    ///
+    /// **Warning:** as for [Secp256k1PublicKey::verify_digest], `digest` must be the output of a
+    /// cryptographic hash function over the message. Fails with [FastCryptoError::InvalidInput]
+    /// if the batch is empty or `pks` and `sigs` differ in length.
+    pub fn verify_batch_digest(
+        digest: &Digest<{ constants::MESSAGE_SIZE }>,
+        pks: &[Self],
+        sigs: &[Secp256k1Signature],
+    ) -> Result<(), FastCryptoError> {
+        let message = Message::from_slice(digest.as_ref()).expect("digest has the message length");
+        Self::verify_batch_message(&message, pks, sigs)
+    }
+
+    /// Verify a batch of signatures by this key on distinct messages, where `sigs[i]` is a
+    /// signature on `msgs[i]`, e.g. a stream of messages signed by a single server. Each message is
+    /// hashed as in [Verifier::verify].
+    ///
+    /// Fails with [FastCryptoError::InvalidInput] if the batch is empty or `msgs` and `sigs` differ
+    /// in length, and with [FastCryptoError::InvalidBatchElement] with the position of the first
+    /// invalid signature.
+    pub fn verify_batch_single_signer(
+        &self,
+        msgs: &[&[u8]],
+        sigs: &[Secp256k1Signature],
+    ) -> Result<(), FastCryptoError> {
+        if sigs.is_empty() || msgs.len() != sigs.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        msgs.iter()
+            .zip(sigs)
+            .enumerate()
+            .try_for_each(|(index, (msg, sig))| {
+                self.verify_message(&hash_message(msg), sig)
+                    .map_err(|_| FastCryptoError::InvalidBatchElement { index })
+            })
+    }
+
+    fn verify_batch_message(
+        message: &Message,
+        pks: &[Self],
+        sigs: &[Secp256k1Signature],
+    ) -> Result<(), FastCryptoError> {
+        if sigs.is_empty() || pks.len() != sigs.len() {
+            return Err(FastCryptoError::InvalidInput);
         }
+        pks.iter()
+            .zip(sigs)
+            .try_for_each(|(pk, sig)| pk.verify_message(message, sig))
     }
 
-    /// util function to parse wycheproof test key from DER format.
-    #[cfg(test)]
-    pub fn from_uncompressed(uncompressed: &[u8]) -> Self {
-        let pubkey = PublicKey::from_slice(uncompressed).unwrap();
-        Self {
+    fn verify_message(
+        &self,
+        message: &Message,
+        signature: &Secp256k1Signature,
+    ) -> Result<(), FastCryptoError> {
+        // If pubkey recovered from signature matches original pubkey, verifies signature.
+        // To ensure non-malleability of v, signature.verify_ecdsa() is not used since it will verify using only [r, s] without considering v.
+        match signature.sig.recover(message) {
+            Ok(recovered_key) if self.as_bytes() == recovered_key.serialize().as_slice() => Ok(()),
+            _ => Err(FastCryptoError::InvalidSignature),
+        }
+    }
+
+    /// Check whether `bytes` is a valid encoding of a public key, i.e., whether
+    /// [ToFromBytes::from_bytes] would succeed, without constructing the key.
+    pub fn is_valid_bytes(bytes: &[u8]) -> bool {
+        match bytes.len() {
+            constants::PUBLIC_KEY_SIZE => PublicKey::from_slice(bytes).is_ok(),
+            constants::UNCOMPRESSED_PUBLIC_KEY_SIZE => {
+                bytes[0] == 0x04 && PublicKey::from_slice(bytes).is_ok()
+            }
+            _ => false,
+        }
+    }
+
+    /// Parse a public key from its 33-byte compressed SEC1 encoding, which is also the encoding
+    /// returned by [ToFromBytes::as_bytes].
+    pub fn from_compressed(
+        compressed: &[u8; constants::PUBLIC_KEY_SIZE],
+    ) -> Result<Self, FastCryptoError> {
+        Self::from_sec1(compressed)
+    }
+
+    /// Parse a public key from its 65-byte uncompressed SEC1 encoding, which starts with `0x04`.
+    /// The hybrid encodings starting with `0x06` or `0x07` are rejected.
+    pub fn from_uncompressed(
+        uncompressed: &[u8; constants::UNCOMPRESSED_PUBLIC_KEY_SIZE],
+    ) -> Result<Self, FastCryptoError> {
+        Self::from_sec1(uncompressed)
+    }
+
+    /// Parse a public key from the 32-byte x-coordinate of the point with even y-coordinate, as
+    /// used by BIP-340.
+    pub fn from_x_only(x: &[u8; 32]) -> Result<Self, FastCryptoError> {
+        let mut compressed = [0u8; constants::PUBLIC_KEY_SIZE];
+        compressed[0] = 0x02;
+        compressed[1..].copy_from_slice(x);
+        Self::from_sec1(&compressed)
+    }
+
+    fn from_sec1(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        // libsecp256k1 also parses the hybrid encoding, which is not allowed by SEC1.
+        if bytes.len() == constants::UNCOMPRESSED_PUBLIC_KEY_SIZE && bytes[0] != 0x04 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let pubkey = PublicKey::from_slice(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(Self {
             pubkey,
             bytes: OnceCell::new(),
+        })
+    }
+
+    /// The 33-byte compressed SEC1 encoding of this key, which is also what `as_bytes` returns.
+    pub fn to_compressed(&self) -> [u8; constants::PUBLIC_KEY_SIZE] {
+        self.pubkey.serialize()
+    }
+
+    /// The 65-byte uncompressed SEC1 encoding of this key.
+    pub fn to_uncompressed(&self) -> [u8; constants::UNCOMPRESSED_PUBLIC_KEY_SIZE] {
+        self.pubkey.serialize_uncompressed()
+    }
+
+    /// Add `tweak * G` to this key, where `G` is the generator. If `sk` is the private key of
+    /// this key, the result is the public key of `sk.add_tweak(tweak)`. Fails if the tweak is not
+    /// smaller than the group order or if the result is the point at infinity.
+    pub fn add_tweak(&self, tweak: &[u8; 32]) -> Result<Secp256k1PublicKey, FastCryptoError> {
+        let tweak = Scalar::from_be_bytes(*tweak).map_err(|_| FastCryptoError::InvalidInput)?;
+        let pubkey = self
+            .pubkey
+            .add_exp_tweak(&SECP256K1, &tweak)
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(Secp256k1PublicKey {
+            pubkey,
+            bytes: OnceCell::new(),
+        })
+    }
+
+    /// Aggregate `keys` into a single MuSig2 public key as specified by `KeyAgg` in
+    /// [BIP-327](https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki): the aggregate key
+    /// is `sum_i a_i * P_i`, where the coefficient `a_i` is a hash of the list of all keys and of
+    /// `P_i`, which prevents rogue key attacks. As in BIP-327, the first key which differs from the
+    /// first key of the list has coefficient 1.
+    ///
+    /// The result depends on the order of the keys, so all signers must use the same order, e.g.
+    /// the keys sorted by their encoding. Fails with [FastCryptoError::InvalidInput] if `keys` is
+    /// empty or, with negligible probability for honestly generated keys, if the aggregate key is
+    /// the point at infinity.
+    pub fn aggregate_musig(keys: &[Self]) -> Result<Self, FastCryptoError> {
+        let first = keys.first().ok_or(FastCryptoError::InvalidInput)?;
+        let encodings: Vec<&[u8]> = keys.iter().map(|pk| pk.as_ref()).collect();
+        let list_hash = tagged_hash(b"KeyAgg list", &encodings);
+        let second = keys.iter().find(|pk| *pk != first);
+        let aggregate = keys
+            .iter()
+            .map(|pk| {
+                let point = k256::PublicKey::from_sec1_bytes(pk.as_ref())
+                    .expect("a valid SEC1 encoded key")
+                    .to_projective();
+                if Some(pk) == second {
+                    return point;
+                }
+                let coefficient =
+                    tagged_hash(b"KeyAgg coefficient", &[&list_hash.0[..], pk.as_ref()]);
+                point
+                    * <k256::Scalar as Reduce<U256>>::from_be_bytes_reduced(
+                        *FieldBytes::from_slice(&coefficient.0),
+                    )
+            })
+            .fold(ProjectivePoint::IDENTITY, |sum, point| sum + point);
+        if aggregate == ProjectivePoint::IDENTITY {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Self::from_bytes(aggregate.to_affine().to_encoded_point(true).as_bytes())
+            .map_err(|_| FastCryptoError::InvalidInput)
+    }
+
+    /// Parse a public key from a DER encoded X.509 `SubjectPublicKeyInfo` (RFC 5480) with either
+    /// a compressed or an uncompressed point. Fails with [FastCryptoError::InvalidInput] if the
+    /// encoding is invalid or if the key is not a secp256k1 key.
+    pub fn from_spki_der(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let info =
+            SubjectPublicKeyInfo::from_der(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+        if info.algorithm.oid != EC_PUBLIC_KEY_OID
+            || info.algorithm.parameters_oid().ok() != Some(SECP256K1_OID)
+        {
+            return Err(FastCryptoError::InvalidInput);
         }
+        Self::from_sec1(info.subject_public_key)
+    }
+
+    /// Encode this key as a DER encoded X.509 `SubjectPublicKeyInfo` with an uncompressed point,
+    /// which is the default of OpenSSL.
+    pub fn to_spki_der(&self) -> Vec<u8> {
+        SubjectPublicKeyInfo {
+            algorithm: AlgorithmIdentifier {
+                oid: EC_PUBLIC_KEY_OID,
+                parameters: Some((&SECP256K1_OID).into()),
+            },
+            subject_public_key: &self.to_uncompressed(),
+        }
+        .to_vec()
+        .expect("SubjectPublicKeyInfo is encodable")
     }
 }
 
+// The 33-byte compressed SEC1 encoding of the key.
 impl AsRef<[u8]> for Secp256k1PublicKey {
     fn as_ref(&self) -> &[u8] {
         self.bytes
@@ -146,13 +589,23 @@ impl AsRef<[u8]> for Secp256k1PublicKey {
 }
 
 impl ToFromBytes for Secp256k1PublicKey {
+    /// Parse a public key from either its 33-byte compressed or its 65-byte uncompressed SEC1
+    /// encoding. The hybrid encodings starting with `0x06` or `0x07` are rejected. Other lengths
+    /// fail with [FastCryptoError::InputLengthWrong], and x-only keys must be parsed with
+    /// [Secp256k1PublicKey::from_x_only].
+    ///
+    /// Since [ToFromBytes::as_bytes] always returns the compressed encoding, use
+    /// [ToFromBytes::canonical_deserialize] where a key must have a unique encoding.
     fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
-        match PublicKey::from_slice(bytes) {
-            Ok(pubkey) => Ok(Secp256k1PublicKey {
-                pubkey,
-                bytes: OnceCell::new(),
-            }),
-            Err(_) => Err(signature::Error::new()),
+        match bytes.len() {
+            constants::PUBLIC_KEY_SIZE | constants::UNCOMPRESSED_PUBLIC_KEY_SIZE => {
+                Ok(Self::from_sec1(bytes)?)
+            }
+            got => Err(FastCryptoError::InputLengthWrong {
+                expected: constants::PUBLIC_KEY_SIZE,
+                got,
+            }
+            .into()),
         }
     }
 }
@@ -169,6 +622,14 @@ impl Display for Secp256k1PublicKey {
     }
 }
 
+impl FromStr for Secp256k1PublicKey {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_base64_str(s)
+    }
+}
+
 // There is a strong requirement for this specific impl. in Fab benchmarks
 impl Serialize for Secp256k1PublicKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -202,21 +663,111 @@ impl<'a> From<&'a Secp256k1PrivateKey> for Secp256k1PublicKey {
 impl SigningKey for Secp256k1PrivateKey {
     type PubKey = Secp256k1PublicKey;
     type Sig = Secp256k1Signature;
-    const LENGTH: usize = constants::SECRET_KEY_SIZE;
+}
+
+impl FixedLength for Secp256k1PrivateKey {
+    const LENGTH: usize = SECP256K1_PRIVATE_KEY_LENGTH;
 }
 
 impl ToFromBytes for Secp256k1PrivateKey {
     fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
         match SecretKey::from_slice(bytes) {
             Ok(privkey) => Ok(Secp256k1PrivateKey {
                 privkey,
                 bytes: OnceCell::new(),
             }),
-            Err(_) => Err(signature::Error::new()),
+            Err(_) => Err(FastCryptoError::InvalidInput.into()),
         }
     }
 }
 
+impl Secp256k1PrivateKey {
+    /// Add `tweak` to this key modulo the group order, as in BIP-32 child key derivation. Fails if
+    /// the tweak is not smaller than the group order or if the result is zero.
+    pub fn add_tweak(&self, tweak: &[u8; 32]) -> Result<Secp256k1PrivateKey, FastCryptoError> {
+        let tweak = Scalar::from_be_bytes(*tweak).map_err(|_| FastCryptoError::InvalidInput)?;
+        let privkey = self
+            .privkey
+            .add_tweak(&tweak)
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(Secp256k1PrivateKey {
+            privkey,
+            bytes: OnceCell::new(),
+        })
+    }
+
+    /// Parse a private key from a DER encoded PKCS#8 `PrivateKeyInfo` (RFC 5208) wrapping a SEC1
+    /// `ECPrivateKey` (RFC 5915), as exported by `openssl pkcs8 -topk8 -nocrypt -outform DER`.
+    /// Fails with [FastCryptoError::InvalidInput] if the encoding is invalid, if the key is not a
+    /// secp256k1 key or if the public key it contains does not match the private key.
+    pub fn from_pkcs8_der(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let info = PrivateKeyInfo::from_der(bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+        if info.algorithm.oid != EC_PUBLIC_KEY_OID
+            || info.algorithm.parameters_oid().ok() != Some(SECP256K1_OID)
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let ec_key =
+            EcPrivateKey::from_der(info.private_key).map_err(|_| FastCryptoError::InvalidInput)?;
+        if let Some(parameters) = ec_key.parameters {
+            if parameters.named_curve() != Some(SECP256K1_OID) {
+                return Err(FastCryptoError::InvalidInput);
+            }
+        }
+        let sk = Self::from_bytes(ec_key.private_key).map_err(|_| FastCryptoError::InvalidInput)?;
+        if let Some(public_key) = ec_key.public_key {
+            if PublicKey::from_slice(public_key).ok()
+                != Some(PublicKey::from_secret_key(&SECP256K1, &sk.privkey))
+            {
+                return Err(FastCryptoError::InvalidInput);
+            }
+        }
+        Ok(sk)
+    }
+
+    /// Encode this key as a DER encoded PKCS#8 `PrivateKeyInfo` in the format of OpenSSL, which
+    /// includes the uncompressed public key.
+    pub fn to_pkcs8_der(&self) -> Zeroizing<Vec<u8>> {
+        let secret = Zeroizing::new(self.privkey.secret_bytes());
+        let public_key =
+            PublicKey::from_secret_key(&SECP256K1, &self.privkey).serialize_uncompressed();
+        let ec_key = Zeroizing::new(
+            EcPrivateKey {
+                private_key: &secret[..],
+                parameters: None,
+                public_key: Some(&public_key),
+            }
+            .to_vec()
+            .expect("ECPrivateKey is encodable"),
+        );
+        let info = PrivateKeyInfo::new(
+            AlgorithmIdentifier {
+                oid: EC_PUBLIC_KEY_OID,
+                parameters: Some((&SECP256K1_OID).into()),
+            },
+            &ec_key,
+        );
+        Zeroizing::new(info.to_vec().expect("PrivateKeyInfo is encodable"))
+    }
+
+    /// Compute the shared secret with `pk` as the SHA-256 hash of the compressed encoding of the
+    /// point `sk * pk`, which is the convention of libsecp256k1 and of most ECIES
+    /// implementations. Only available with the `signing` feature.
+    #[cfg(feature = "signing")]
+    pub fn diffie_hellman(&self, pk: &Secp256k1PublicKey) -> SharedSecret {
+        SharedSecret::new(ecdh::SharedSecret::new(&pk.pubkey, &self.privkey).secret_bytes())
+    }
+}
+
+impl FromStr for Secp256k1PrivateKey {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_base64_str(s)
+    }
+}
+
 // There is a strong requirement for this specific impl. in Fab benchmarks
 impl Serialize for Secp256k1PrivateKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -247,6 +798,21 @@ impl AsRef<[u8]> for Secp256k1PrivateKey {
     }
 }
 
+impl Debug for Secp256k1PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256k1PrivateKey(<redacted>)")
+    }
+}
+
+// Constant time, so comparing secret keys does not leak where they differ.
+impl PartialEq for Secp256k1PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref().ct_eq(other.as_ref()).into()
+    }
+}
+
+impl Eq for Secp256k1PrivateKey {}
+
 impl Serialize for Secp256k1Signature {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -268,27 +834,25 @@ impl<'de> Deserialize<'de> for Secp256k1Signature {
 }
 
 impl Signature for Secp256k1Signature {
+    /// Parse a signature in its canonical form: `r` and `s` are smaller than the group order and
+    /// `s` is at most half of it. Both `(r, s)` and `(r, n - s)` are valid signatures, so only
+    /// accepting the low-S form makes signatures non-malleable.
     fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
-        if bytes.len() != 65 {
-            return Err(signature::Error::new());
+        let signature = Self::from_bytes_unchecked(bytes)?;
+        if !signature.is_canonical() {
+            return Err(FastCryptoError::InvalidInput.into());
         }
-        RecoveryId::from_i32(bytes[64] as i32)
-            .and_then(|rec_id| {
-                RecoverableSignature::from_compact(&bytes[..64], rec_id).map(|sig| {
-                    Secp256k1Signature {
-                        sig,
-                        bytes: OnceCell::new(),
-                    }
-                })
-            })
-            .map_err(|_| signature::Error::new())
+        Ok(signature)
     }
 }
 
 impl Authenticator for Secp256k1Signature {
     type PubKey = Secp256k1PublicKey;
     type PrivKey = Secp256k1PrivateKey;
-    const LENGTH: usize = RECOVERABLE_SIGNATURE_SIZE;
+}
+
+impl FixedLength for Secp256k1Signature {
+    const LENGTH: usize = SECP256K1_SIGNATURE_LENGTH;
 }
 
 impl AsRef<[u8]> for Secp256k1Signature {
@@ -323,6 +887,14 @@ impl Display for Secp256k1Signature {
     }
 }
 
+impl FromStr for Secp256k1Signature {
+    type Err = FastCryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        from_base64_str(s)
+    }
+}
+
 impl Default for Secp256k1Signature {
     fn default() -> Self {
         <Secp256k1Signature as Signature>::from_bytes(&[1u8; RECOVERABLE_SIGNATURE_SIZE]).unwrap()
@@ -330,6 +902,7 @@ impl Default for Secp256k1Signature {
 }
 
 // There is a strong requirement for this specific impl. in Fab benchmarks
+#[cfg(feature = "signing")]
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type")] // necessary so as not to deser under a != type
 pub struct Secp256k1KeyPair {
@@ -337,6 +910,7 @@ pub struct Secp256k1KeyPair {
     pub secret: Secp256k1PrivateKey,
 }
 
+#[cfg(feature = "signing")]
 impl EncodeDecodeBase64 for Secp256k1KeyPair {
     fn decode_base64(value: &str) -> Result<Self, eyre::Report> {
         keypair_decode_base64(value)
@@ -350,6 +924,7 @@ impl EncodeDecodeBase64 for Secp256k1KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
 impl KeyPair for Secp256k1KeyPair {
     type PubKey = Secp256k1PublicKey;
     type PrivKey = Secp256k1PrivateKey;
@@ -387,6 +962,7 @@ impl KeyPair for Secp256k1KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
 impl FromStr for Secp256k1KeyPair {
     type Err = eyre::Report;
 
@@ -396,23 +972,49 @@ impl FromStr for Secp256k1KeyPair {
     }
 }
 
+#[cfg(feature = "signing")]
 impl Signer<Secp256k1Signature> for Secp256k1KeyPair {
     fn try_sign(&self, msg: &[u8]) -> Result<Secp256k1Signature, signature::Error> {
-        let secp = Secp256k1::signing_only();
-        #[cfg(test)]
-        let message =
-            Message::from_slice(<sha3::Keccak256 as sha3::digest::Digest>::digest(msg).as_slice())
-                .unwrap();
+        Ok(self.sign_deterministic(msg))
+    }
+}
+
+#[cfg(feature = "signing")]
+impl Secp256k1KeyPair {
+    /// Sign a message using a deterministic nonce derived from the private key and the message
+    /// digest as specified in RFC 6979. No randomness is involved: signing the same message twice
+    /// with the same key gives identical signatures. This is also what [Signer::sign] does.
+    pub fn sign_deterministic(&self, msg: &[u8]) -> Secp256k1Signature {
+        self.sign_message(&hash_message(msg))
+    }
+
+    /// Sign a 32-byte digest directly, without hashing it again.
+    ///
+    /// **Warning:** `digest` must be the output of a cryptographic hash function over the message.
+    /// Passing a raw message here is incorrect and insecure: use [Signer::sign] to sign a message.
+    pub fn sign_digest(&self, digest: &Digest<{ constants::MESSAGE_SIZE }>) -> Secp256k1Signature {
+        self.sign_message(
+            &Message::from_slice(digest.as_ref()).expect("digest has the message length"),
+        )
+    }
 
-        #[cfg(not(test))]
-        let message = Message::from_hashed_data::<rust_secp256k1::hashes::sha256::Hash>(msg);
+    /// Sign the digest of a message computed with the hash function `H` instead of the default
+    /// one used by [Signer::sign], e.g. [Sha256](crate::hash::Sha256) for standard ECDSA. The
+    /// signature is verified with [Secp256k1PublicKey::verify_with] using the same `H`.
+    pub fn sign_with<H: HashFunction<{ constants::MESSAGE_SIZE }>>(
+        &self,
+        msg: &[u8],
+    ) -> Secp256k1Signature {
+        self.sign_digest(&H::digest(msg))
+    }
 
+    fn sign_message(&self, message: &Message) -> Secp256k1Signature {
         // Creates a 65-bytes sigature of shape [r, s, v] where v can be 0 or 1.
         // Pseudo-random deterministic nonce generation is used according to RFC6979.
-        Ok(Secp256k1Signature {
-            sig: secp.sign_ecdsa_recoverable(&message, &self.secret.privkey),
+        Secp256k1Signature {
+            sig: SECP256K1.sign_ecdsa_recoverable(message, &self.secret.privkey),
             bytes: OnceCell::new(),
-        })
+        }
     }
 }
 
@@ -430,6 +1032,74 @@ impl From<&Secp256k1PublicKey> for Secp256k1PublicKeyBytes {
     }
 }
 
+impl PartialEq<Secp256k1PublicKeyBytes> for Secp256k1PublicKey {
+    fn eq(&self, other: &Secp256k1PublicKeyBytes) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl PartialEq<Secp256k1PublicKey> for Secp256k1PublicKeyBytes {
+    fn eq(&self, other: &Secp256k1PublicKey) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl From<&Secp256k1PublicKey> for k256::ecdsa::VerifyingKey {
+    fn from(pk: &Secp256k1PublicKey) -> Self {
+        k256::ecdsa::VerifyingKey::from_sec1_bytes(pk.as_ref()).expect("a valid SEC1 encoded key")
+    }
+}
+
+impl From<&k256::ecdsa::VerifyingKey> for Secp256k1PublicKey {
+    fn from(pk: &k256::ecdsa::VerifyingKey) -> Self {
+        Secp256k1PublicKey::from_bytes(pk.to_bytes().as_slice()).expect("a valid SEC1 encoded key")
+    }
+}
+
+impl TryFrom<&Secp256k1Signature> for k256::ecdsa::recoverable::Signature {
+    type Error = signature::Error;
+
+    /// Fails if `r` or `s` is zero, which k256 rejects.
+    fn try_from(signature: &Secp256k1Signature) -> Result<Self, Self::Error> {
+        k256::ecdsa::recoverable::Signature::try_from(signature.as_ref())
+            .map_err(|_| FastCryptoError::InvalidInput.into())
+    }
+}
+
+impl TryFrom<&k256::ecdsa::recoverable::Signature> for Secp256k1Signature {
+    type Error = signature::Error;
+
+    /// Fails if the signature is not in the low-S form, as in [Signature::from_bytes].
+    fn try_from(signature: &k256::ecdsa::recoverable::Signature) -> Result<Self, Self::Error> {
+        <Secp256k1Signature as Signature>::from_bytes(signature.as_ref())
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1PublicKey {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Secp256k1PublicKey, Self::Error> {
+        <Secp256k1PublicKey as ToFromBytes>::from_bytes(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1PrivateKey {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Secp256k1PrivateKey, Self::Error> {
+        <Secp256k1PrivateKey as ToFromBytes>::from_bytes(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1Signature {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Secp256k1Signature, Self::Error> {
+        <Secp256k1Signature as ToFromBytes>::from_bytes(bytes)
+    }
+}
+
+#[cfg(feature = "signing")]
 impl From<Secp256k1PrivateKey> for Secp256k1KeyPair {
     fn from(secret: Secp256k1PrivateKey) -> Self {
         let name = Secp256k1PublicKey::from(&secret);
@@ -438,9 +1108,158 @@ impl From<Secp256k1PrivateKey> for Secp256k1KeyPair {
 }
 
 impl Secp256k1Signature {
-    /// Recover public key from signature
+    /// Parse a signature, also accepting the high-S form which is rejected by
+    /// [Signature::from_bytes]. Components which are not smaller than the group order are still
+    /// rejected. Use this only for signatures coming from a trusted source.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
+        RecoveryId::from_i32(bytes[64] as i32)
+            .and_then(|rec_id| {
+                RecoverableSignature::from_compact(&bytes[..64], rec_id).map(|sig| {
+                    Secp256k1Signature {
+                        sig,
+                        bytes: OnceCell::new(),
+                    }
+                })
+            })
+            .map_err(|_| FastCryptoError::InvalidInput.into())
+    }
+
+    /// Parse a signature like [Secp256k1Signature::from_bytes_unchecked], and bring a signature in
+    /// the high-S form into the low-S form with [Secp256k1Signature::canonicalize]. The result
+    /// recovers the same public key and is accepted by [Signature::from_bytes], so signatures from
+    /// external sources can be stored in a unique encoding. Unlike [Signature::from_bytes], the
+    /// bytes of the result may differ from the input.
+    pub fn from_bytes_normalized(bytes: &[u8]) -> Result<Self, signature::Error> {
+        let mut signature = Self::from_bytes_unchecked(bytes)?;
+        signature.canonicalize();
+        Ok(signature)
+    }
+
+    /// Split this signature into the 64-byte compact signature `(r, s)` and the recovery id, which
+    /// is 0, 1, 2 or 3.
+    pub fn split(&self) -> ([u8; constants::COMPACT_SIGNATURE_SIZE], u8) {
+        let (recovery_id, compact) = self.sig.serialize_compact();
+        (compact, recovery_id.to_i32() as u8)
+    }
+
+    /// Build a signature from the parts returned by [Secp256k1Signature::split]. Fails with
+    /// [FastCryptoError::InvalidInput] if the recovery id is larger than 3 or if the signature is
+    /// rejected by [Signature::from_bytes].
+    pub fn from_parts(
+        compact: &[u8; constants::COMPACT_SIGNATURE_SIZE],
+        recovery_id: u8,
+    ) -> Result<Self, FastCryptoError> {
+        <Self as Signature>::from_bytes(&[&compact[..], &[recovery_id]].concat())
+            .map_err(|_| FastCryptoError::InvalidInput)
+    }
+
+    /// Encode this signature as returned by the Ethereum JSON-RPC `eth_sign` method: `0x`
+    /// followed by the hex encoding of `r || s || v`, where `v` is 27 plus the recovery id.
+    ///
+    /// Ethereum only accepts `v` equal to 27 or 28, so signatures with recovery id 2 or 3 (which
+    /// occur with negligible probability) are encoded with `v` equal to 29 or 30 and are rejected
+    /// by [Secp256k1Signature::from_eth_rsv_hex].
+    pub fn to_eth_rsv_hex(&self) -> String {
+        let (compact, recovery_id) = self.split();
+        format!("0x{}{:02x}", hex::encode(compact), recovery_id + 27)
+    }
+
+    /// Parse a signature in the format returned by [Secp256k1Signature::to_eth_rsv_hex]. Fails
+    /// with [FastCryptoError::InvalidInput] if the `0x` prefix is missing, if the input is not
+    /// 65 hex encoded bytes, if `v` is not 27 or 28, or if the signature is rejected by
+    /// [Signature::from_bytes].
+    pub fn from_eth_rsv_hex(s: &str) -> Result<Self, FastCryptoError> {
+        let bytes = s
+            .strip_prefix("0x")
+            .and_then(|s| hex::decode(s).ok())
+            .ok_or(FastCryptoError::InvalidInput)?;
+        if bytes.len() != RECOVERABLE_SIGNATURE_SIZE {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut compact = [0u8; constants::COMPACT_SIGNATURE_SIZE];
+        compact.copy_from_slice(&bytes[..constants::COMPACT_SIGNATURE_SIZE]);
+        let recovery_id = bytes[constants::COMPACT_SIGNATURE_SIZE]
+            .checked_sub(27)
+            .filter(|id| *id <= 1)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        Self::from_parts(&compact, recovery_id)
+    }
+
+    /// Returns true if the signature is in the low-S form accepted by [Signature::from_bytes].
+    /// Only canonical signatures have a unique encoding, so signatures should be canonical before
+    /// their bytes are hashed or compared.
+    pub fn is_canonical(&self) -> bool {
+        let mut normalized = self.sig.to_standard();
+        normalized.normalize_s();
+        normalized == self.sig.to_standard()
+    }
+
+    /// Replace `s` by `n - s` if `s` is larger than half the group order `n`, and flip the parity
+    /// of the recovery id accordingly, so that the signature still recovers the same public key.
+    /// Afterwards [Secp256k1Signature::is_canonical] is true.
+    pub fn canonicalize(&mut self) {
+        if self.is_canonical() {
+            return;
+        }
+        let mut normalized = self.sig.to_standard();
+        normalized.normalize_s();
+        let (recovery_id, _) = self.sig.serialize_compact();
+        self.sig = RecoverableSignature::from_compact(
+            &normalized.serialize_compact(),
+            RecoveryId::from_i32(recovery_id.to_i32() ^ 1).expect("valid recovery id"),
+        )
+        .expect("valid signature");
+        self.bytes = OnceCell::new();
+    }
+
+    /// Recover the public keys of the signers of a batch of signatures, where `sigs[i]` is a
+    /// signature on the digest `digests[i]` as in [Secp256k1Signature::recover]. With the
+    /// `parallel` feature, the keys are recovered in parallel with rayon.
+    ///
+    /// Fails with [FastCryptoError::InvalidBatchElement] with the position of the first signature
+    /// whose key cannot be recovered, and with [FastCryptoError::InvalidInput] if the number of
+    /// signatures and digests differ.
+    pub fn recover_batch(
+        sigs: &[Secp256k1Signature],
+        digests: &[Digest<{ constants::MESSAGE_SIZE }>],
+    ) -> Result<Vec<Secp256k1PublicKey>, FastCryptoError> {
+        if sigs.len() != digests.len() {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        #[cfg(feature = "parallel")]
+        let sigs_iter = sigs.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let sigs_iter = sigs.iter();
+        let recovered: Vec<Option<PublicKey>> = sigs_iter
+            .zip(digests)
+            .map(|(sig, digest)| {
+                let message =
+                    Message::from_slice(digest.as_ref()).expect("digest has the right length");
+                sig.sig.recover(&message).ok()
+            })
+            .collect();
+        recovered
+            .into_iter()
+            .enumerate()
+            .map(|(index, pubkey)| {
+                pubkey
+                    .map(|pubkey| Secp256k1PublicKey {
+                        pubkey,
+                        bytes: OnceCell::new(),
+                    })
+                    .ok_or(FastCryptoError::InvalidBatchElement { index })
+            })
+            .collect()
+    }
+
+    /// Recover the public key of the signer from the 32-byte hash `hashed_msg` of the signed
+    /// message. Recovery does not depend on the hash function, but the caller must hash the
+    /// message with the same function that was used to sign it: SHA-256 for
+    /// [Signer::sign], and `H` for [Secp256k1KeyPair::sign_with]. With another hash, a different
+    /// key is recovered (or none), so the result must still be compared with the expected key.
     pub fn recover(&self, hashed_msg: &[u8]) -> Result<Secp256k1PublicKey, signature::Error> {
-        match rust_secp256k1::Message::from_slice(hashed_msg) {
+        match Message::from_slice(hashed_msg) {
             Ok(message) => match self.sig.recover(&message) {
                 Ok(pubkey) => Secp256k1PublicKey::from_bytes(pubkey.serialize().as_slice()),
                 Err(_) => Err(signature::Error::new()),
@@ -450,9 +1269,28 @@ impl Secp256k1Signature {
     }
 }
 
+/// Recover the Ethereum address of the signer of `hash` from a signature `(v, r, s)`, with the
+/// semantics of the `ecrecover` precompile: `v` must be 27 or 28, and `None` is returned instead of
+/// an error if the signature is invalid. Unlike the precompile, signatures in the high-S form are
+/// rejected as in [Signature::from_bytes].
+///
+/// The address is the last 20 bytes of the Keccak-256 hash of the uncompressed public key without
+/// its `0x04` prefix.
+pub fn ecrecover(hash: &[u8; 32], v: u8, r: &[u8; 32], s: &[u8; 32]) -> Option<[u8; 20]> {
+    let recovery_id = v.checked_sub(27).filter(|id| *id <= 1)?;
+    let signature =
+        <Secp256k1Signature as Signature>::from_bytes(&[&r[..], &s[..], &[recovery_id]].concat())
+            .ok()?;
+    let pk = signature.recover(hash).ok()?;
+    let digest = crate::hash::Keccak256::digest(&pk.to_uncompressed()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&digest.as_ref()[12..]);
+    Some(address)
+}
+
 impl zeroize::Zeroize for Secp256k1PrivateKey {
     fn zeroize(&mut self) {
-        self.privkey = rust_secp256k1::ONE_KEY;
+        self.privkey = backend::ONE_KEY;
         self.bytes.take().zeroize();
     }
 }
@@ -465,14 +1303,17 @@ impl Drop for Secp256k1PrivateKey {
     }
 }
 
+#[cfg(feature = "signing")]
 impl zeroize::Zeroize for Secp256k1KeyPair {
     fn zeroize(&mut self) {
         self.secret.zeroize()
     }
 }
 
+#[cfg(feature = "signing")]
 impl zeroize::ZeroizeOnDrop for Secp256k1KeyPair {}
 
+#[cfg(feature = "signing")]
 impl Drop for Secp256k1KeyPair {
     fn drop(&mut self) {
         self.secret.zeroize();
@@ -0,0 +1,286 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains hierarchical deterministic secp256k1 keys as specified in
+//! [BIP-32](https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki), as used by HD wallets.
+//!
+//! An [ExtendedPrivateKey] is a private key together with a 32-byte chain code. It is created from
+//! a seed with [ExtendedPrivateKey::from_seed] or parsed from its Base58Check serialization
+//! (`xprv...` or `tprv...`) with [ExtendedPrivateKey::from_base58]. Children are derived with
+//! [ExtendedPrivateKey::derive_child], where child numbers from [HARDENED_OFFSET] on give hardened
+//! children. Extended public keys (`xpub...`) are not supported.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::secp256k1::bip32::{ExtendedPrivateKey, HARDENED_OFFSET};
+//! use fastcrypto::traits::KeyPair;
+//! use signature::{Signer, Verifier};
+//! # fn main() {
+//!     let master = ExtendedPrivateKey::from_seed(&[7; 32]).unwrap();
+//!     // m/44'/0'/0'/0/1
+//!     let path = [44 + HARDENED_OFFSET, HARDENED_OFFSET, HARDENED_OFFSET, 0, 1];
+//!     let child = master.derive_path(&path).unwrap();
+//!     let parsed = ExtendedPrivateKey::from_base58(&child.to_base58()).unwrap();
+//!     assert_eq!(parsed.public_key(), child.public_key());
+//!
+//!     let kp = child.key_pair();
+//!     let message: &[u8] = b"Hello, world!";
+//!     assert!(kp.public().verify(message, &kp.sign(message)).is_ok());
+//! # }
+//! ```
+
+use super::{Secp256k1KeyPair, Secp256k1PrivateKey, Secp256k1PublicKey};
+use crate::{
+    error::FastCryptoError,
+    hash::{HashFunction, Sha256},
+    hmac::hmac_sha512,
+    traits::ToFromBytes,
+};
+use bitcoin_hashes::{hash160, Hash};
+use zeroize::Zeroizing;
+
+/// Child numbers from this offset on derive hardened children, written `i'` or `iH` in paths.
+pub const HARDENED_OFFSET: u32 = 1 << 31;
+
+/// The version bytes of extended private keys on mainnet, which are serialized as `xprv...`.
+pub const MAINNET_PRIVATE_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+
+/// The version bytes of extended private keys on testnet, which are serialized as `tprv...`.
+pub const TESTNET_PRIVATE_VERSION: [u8; 4] = [0x04, 0x35, 0x83, 0x94];
+
+/// The length of a serialized extended key without the checksum.
+const SERIALIZED_LENGTH: usize = 78;
+
+/// The length of the Base58Check checksum.
+const CHECKSUM_LENGTH: usize = 4;
+
+/// The HMAC key used to derive the master key from a seed.
+const MASTER_HMAC_KEY: &[u8] = b"Bitcoin seed";
+
+/// The first four bytes of the double SHA-256 hash of `payload`.
+fn checksum(payload: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let hash = Sha256::digest(Sha256::digest(payload));
+    let mut checksum = [0u8; CHECKSUM_LENGTH];
+    checksum.copy_from_slice(&hash.0[..CHECKSUM_LENGTH]);
+    checksum
+}
+
+/// A secp256k1 private key with a chain code, from which child keys can be derived.
+pub struct ExtendedPrivateKey {
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: Zeroizing<[u8; 32]>,
+    private_key: Secp256k1PrivateKey,
+}
+
+impl ExtendedPrivateKey {
+    /// Derive the master key from a seed of 16 to 64 bytes, e.g. a BIP-39 seed. The key has the
+    /// mainnet version. Fails with [FastCryptoError::InvalidInput] if the seed length is out of
+    /// range or, with negligible probability, if the seed gives an invalid key.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, FastCryptoError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let hash = Zeroizing::new(hmac_sha512(MASTER_HMAC_KEY, seed));
+        Self::from_hmac_output(MAINNET_PRIVATE_VERSION, 0, [0; 4], 0, &hash, |key| {
+            Secp256k1PrivateKey::from_bytes(key).map_err(|_| FastCryptoError::InvalidInput)
+        })
+    }
+
+    /// Parse an extended private key from its Base58Check serialization. Fails with
+    /// [FastCryptoError::InvalidInput] if the checksum is wrong, if the version is not one of
+    /// [MAINNET_PRIVATE_VERSION] and [TESTNET_PRIVATE_VERSION] (in particular for extended public
+    /// keys), if a master key has a parent fingerprint or child number, or if the key is invalid.
+    pub fn from_base58(s: &str) -> Result<Self, FastCryptoError> {
+        let bytes = Zeroizing::new(bs58::decode(s).into_vec()?);
+        if bytes.len() != SERIALIZED_LENGTH + CHECKSUM_LENGTH {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let (payload, expected_checksum) = bytes.split_at(SERIALIZED_LENGTH);
+        if checksum(payload) != expected_checksum {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        let version: [u8; 4] = payload[..4].try_into().expect("length is 4");
+        if version != MAINNET_PRIVATE_VERSION && version != TESTNET_PRIVATE_VERSION {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let depth = payload[4];
+        let parent_fingerprint: [u8; 4] = payload[5..9].try_into().expect("length is 4");
+        let child_number = u32::from_be_bytes(payload[9..13].try_into().expect("length is 4"));
+        if depth == 0 && (parent_fingerprint != [0; 4] || child_number != 0) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut chain_code = Zeroizing::new([0u8; 32]);
+        chain_code.copy_from_slice(&payload[13..45]);
+        // The key is prefixed by a zero byte to distinguish it from a compressed public key.
+        if payload[45] != 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let private_key = Secp256k1PrivateKey::from_bytes(&payload[46..])
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+
+        Ok(Self {
+            version,
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            private_key,
+        })
+    }
+
+    /// The Base58Check serialization of this key, which is parsed by
+    /// [ExtendedPrivateKey::from_base58].
+    pub fn to_base58(&self) -> Zeroizing<String> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(SERIALIZED_LENGTH + CHECKSUM_LENGTH));
+        bytes.extend_from_slice(&self.version);
+        bytes.push(self.depth);
+        bytes.extend_from_slice(&self.parent_fingerprint);
+        bytes.extend_from_slice(&self.child_number.to_be_bytes());
+        bytes.extend_from_slice(&self.chain_code[..]);
+        bytes.push(0);
+        bytes.extend_from_slice(self.private_key.as_ref());
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum);
+        Zeroizing::new(bs58::encode(&bytes[..]).into_string())
+    }
+
+    /// Derive the child with the given child number. If `child_number` is at least
+    /// [HARDENED_OFFSET], a hardened child is derived from the private key, and otherwise a
+    /// normal child is derived from the public key.
+    ///
+    /// Fails with [FastCryptoError::InvalidInput] if this key has the maximal depth 255 or, with
+    /// negligible probability, if the child key is invalid. BIP-32 says to continue with the next
+    /// child number in this case, which is left to the caller.
+    pub fn derive_child(&self, child_number: u32) -> Result<Self, FastCryptoError> {
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or(FastCryptoError::InvalidInput)?;
+        let mut data = Zeroizing::new(Vec::with_capacity(37));
+        if child_number >= HARDENED_OFFSET {
+            data.push(0);
+            data.extend_from_slice(self.private_key.as_ref());
+        } else {
+            data.extend_from_slice(self.public_key().as_ref());
+        }
+        data.extend_from_slice(&child_number.to_be_bytes());
+        let hash = Zeroizing::new(hmac_sha512(&self.chain_code[..], &data));
+        Self::from_hmac_output(
+            self.version,
+            depth,
+            self.fingerprint(),
+            child_number,
+            &hash,
+            |tweak| {
+                self.private_key
+                    .add_tweak(tweak.try_into().expect("length is 32"))
+            },
+        )
+    }
+
+    /// Derive the descendant of this key along `path`, e.g. `[44 + HARDENED_OFFSET, 0]` for
+    /// `m/44'/0` from a master key.
+    pub fn derive_path(&self, path: &[u32]) -> Result<Self, FastCryptoError> {
+        let (first, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return Ok(self.clone()),
+        };
+        rest.iter()
+            .try_fold(self.derive_child(*first)?, |key, child_number| {
+                key.derive_child(*child_number)
+            })
+    }
+
+    /// The private key.
+    pub fn private_key(&self) -> &Secp256k1PrivateKey {
+        &self.private_key
+    }
+
+    /// The public key of the private key.
+    pub fn public_key(&self) -> Secp256k1PublicKey {
+        Secp256k1PublicKey::from(&self.private_key)
+    }
+
+    /// A key pair for signing with the private key.
+    pub fn key_pair(&self) -> Secp256k1KeyPair {
+        Secp256k1KeyPair::from(self.private_key_copy())
+    }
+
+    /// The chain code, which is needed together with the key to derive children.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// The version bytes, which are [MAINNET_PRIVATE_VERSION] or [TESTNET_PRIVATE_VERSION].
+    pub fn version(&self) -> [u8; 4] {
+        self.version
+    }
+
+    /// The number of derivations from the master key, which has depth 0.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The child number of this key, or 0 for the master key.
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// The fingerprint of the parent key, or zero for the master key.
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// The fingerprint of this key: the first four bytes of the HASH160 (RIPEMD-160 of SHA-256) of
+    /// the compressed public key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let hash = hash160::Hash::hash(self.public_key().as_ref()).into_inner();
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash[..4]);
+        fingerprint
+    }
+
+    /// Build a key from the HMAC-SHA512 output of a derivation, where the left half is turned into
+    /// the private key by `derive_key` and the right half is the chain code.
+    fn from_hmac_output(
+        version: [u8; 4],
+        depth: u8,
+        parent_fingerprint: [u8; 4],
+        child_number: u32,
+        hash: &[u8; 64],
+        derive_key: impl FnOnce(&[u8]) -> Result<Secp256k1PrivateKey, FastCryptoError>,
+    ) -> Result<Self, FastCryptoError> {
+        let private_key = derive_key(&hash[..32])?;
+        let mut chain_code = Zeroizing::new([0u8; 32]);
+        chain_code.copy_from_slice(&hash[32..]);
+        Ok(Self {
+            version,
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            private_key,
+        })
+    }
+
+    fn private_key_copy(&self) -> Secp256k1PrivateKey {
+        Secp256k1PrivateKey::from_bytes(self.private_key.as_ref()).expect("the key is valid")
+    }
+}
+
+impl Clone for ExtendedPrivateKey {
+    fn clone(&self) -> Self {
+        Self {
+            version: self.version,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code.clone(),
+            private_key: self.private_key_copy(),
+        }
+    }
+}
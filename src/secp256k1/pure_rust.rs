@@ -0,0 +1,430 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A pure Rust implementation on top of [k256] of the subset of the rust_secp256k1 API used by the
+//! [secp256k1](super) module. It replaces libsecp256k1 with the `pure-rust` feature, or when the
+//! `rust_secp256k1` feature is disabled, and is then re-exported as
+//! [secp256k1::backend](super::backend).
+//!
+//! Signing, verification and recovery use [k256::ecdsa], whose output matches libsecp256k1: both
+//! sign with the RFC 6979 nonce of the private key and the digest reduced modulo the group order
+//! and return signatures in the low-S form. As in libsecp256k1, verification rejects signatures in
+//! the high-S form, key generation draws 32 bytes at a time from the RNG until they form a valid
+//! key, and the ECDH secret is the SHA-256 hash of the compressed encoding of the shared point.
+//!
+//! The only difference is that recovery ids 2 and 3, which mean that the x-coordinate of `R` is
+//! larger than the group order, are not supported by k256 and are rejected. They occur with
+//! negligible probability, so neither backend produces them in practice.
+
+use k256::{
+    ecdsa::{
+        recoverable,
+        signature::hazmat::{PrehashSigner, PrehashVerifier},
+    },
+    elliptic_curve::{scalar::IsHigh, sec1::ToEncodedPoint, Field, PrimeField},
+    FieldBytes, ProjectivePoint,
+};
+use std::marker::PhantomData;
+use zeroize::Zeroizing;
+
+pub mod constants {
+    /// The size in bytes of a message digest which is signed.
+    pub const MESSAGE_SIZE: usize = 32;
+
+    /// The size in bytes of a private key.
+    pub const SECRET_KEY_SIZE: usize = 32;
+
+    /// The size in bytes of a compressed public key.
+    pub const PUBLIC_KEY_SIZE: usize = 33;
+
+    /// The size in bytes of an uncompressed public key.
+    pub const UNCOMPRESSED_PUBLIC_KEY_SIZE: usize = 65;
+
+    /// The size in bytes of a signature `(r, s)` without recovery id.
+    pub const COMPACT_SIGNATURE_SIZE: usize = 64;
+}
+
+/// The errors of rust_secp256k1 which can occur in this subset of its API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    IncorrectSignature,
+    InvalidMessage,
+    InvalidPublicKey,
+    InvalidSignature,
+    InvalidSecretKey,
+    InvalidRecoveryId,
+    InvalidTweak,
+}
+
+/// Parse a 32-byte scalar, which must be smaller than the group order.
+fn parse_scalar(bytes: &[u8]) -> Option<k256::Scalar> {
+    if bytes.len() != 32 {
+        return None;
+    }
+    Option::<k256::Scalar>::from(<k256::Scalar as PrimeField>::from_repr(
+        *FieldBytes::from_slice(bytes),
+    ))
+}
+
+fn scalar_bytes(scalar: &k256::Scalar) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&<k256::Scalar as PrimeField>::to_repr(scalar));
+    bytes
+}
+
+fn is_zero(scalar: &k256::Scalar) -> bool {
+    <k256::Scalar as Field>::is_zero(scalar).into()
+}
+
+/// Whether `s` is larger than `(n - 1) / 2`, i.e., whether a signature with it is in the high-S
+/// form.
+fn is_high(scalar: &k256::Scalar) -> bool {
+    <k256::Scalar as IsHigh>::is_high(scalar).into()
+}
+
+/// The compressed encoding of a point, or `None` for the identity.
+fn compressed(point: &ProjectivePoint) -> Option<[u8; constants::PUBLIC_KEY_SIZE]> {
+    point
+        .to_affine()
+        .to_encoded_point(true)
+        .as_bytes()
+        .try_into()
+        .ok()
+}
+
+/// The context of rust_secp256k1, which holds no state here.
+pub struct Secp256k1<C> {
+    phantom: PhantomData<C>,
+}
+
+/// The capability of a context to sign and verify.
+pub enum All {}
+
+impl Secp256k1<All> {
+    pub fn new() -> Self {
+        Secp256k1 {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl Default for Secp256k1<All> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Secp256k1<C> {
+    /// Draw 32 bytes from `rng` until they form a valid private key, as rust_secp256k1 does.
+    pub fn generate_keypair<R: rand_core::RngCore + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> (SecretKey, PublicKey) {
+        let secret_key = loop {
+            let mut bytes = Zeroizing::new([0u8; constants::SECRET_KEY_SIZE]);
+            rng.fill_bytes(&mut bytes[..]);
+            if let Ok(secret_key) = SecretKey::from_slice(&bytes[..]) {
+                break secret_key;
+            }
+        };
+        (secret_key, PublicKey::from_secret_key(self, &secret_key))
+    }
+
+    /// Sign `msg` with the RFC 6979 nonce. The signature is in the low-S form.
+    pub fn sign_ecdsa_recoverable(
+        &self,
+        msg: &Message,
+        sk: &SecretKey,
+    ) -> ecdsa::RecoverableSignature {
+        let signing_key = k256::ecdsa::SigningKey::from_bytes(&sk.0).expect("a valid private key");
+        let signature: recoverable::Signature = signing_key
+            .sign_prehash(&msg.0)
+            .expect("signing a 32-byte digest does not fail");
+        ecdsa::RecoverableSignature::from_k256(&signature)
+    }
+
+    /// Verify a signature as libsecp256k1 does, which rejects signatures in the high-S form.
+    pub fn verify_ecdsa(
+        &self,
+        msg: &Message,
+        sig: &ecdsa::Signature,
+        pk: &PublicKey,
+    ) -> Result<(), Error> {
+        if is_high(&sig.s) {
+            return Err(Error::IncorrectSignature);
+        }
+        let signature = sig.to_k256().map_err(|_| Error::IncorrectSignature)?;
+        k256::ecdsa::VerifyingKey::from(&pk.0)
+            .verify_prehash(&msg.0, &signature)
+            .map_err(|_| Error::IncorrectSignature)
+    }
+}
+
+/// A 32-byte message digest which is signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Message([u8; constants::MESSAGE_SIZE]);
+
+impl Message {
+    pub fn from_slice(data: &[u8]) -> Result<Message, Error> {
+        data.try_into()
+            .map(Message)
+            .map_err(|_| Error::InvalidMessage)
+    }
+}
+
+/// A scalar smaller than the group order, used to tweak keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scalar(k256::Scalar);
+
+impl Scalar {
+    pub fn from_be_bytes(value: [u8; 32]) -> Result<Scalar, Error> {
+        parse_scalar(&value).map(Scalar).ok_or(Error::InvalidTweak)
+    }
+}
+
+/// A private key, which is a non-zero scalar smaller than the group order. As in libsecp256k1, it
+/// is stored as its 32-byte big-endian encoding.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SecretKey([u8; constants::SECRET_KEY_SIZE]);
+
+/// The private key 1, which overwrites keys when they are zeroized.
+pub const ONE_KEY: SecretKey = SecretKey([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+]);
+
+impl SecretKey {
+    pub fn from_slice(data: &[u8]) -> Result<SecretKey, Error> {
+        match parse_scalar(data) {
+            Some(scalar) if !is_zero(&scalar) => Ok(SecretKey(scalar_bytes(&scalar))),
+            _ => Err(Error::InvalidSecretKey),
+        }
+    }
+
+    pub fn secret_bytes(&self) -> [u8; constants::SECRET_KEY_SIZE] {
+        self.0
+    }
+
+    pub fn public_key<C>(&self, secp: &Secp256k1<C>) -> PublicKey {
+        PublicKey::from_secret_key(secp, self)
+    }
+
+    /// Add `tweak` modulo the group order. Fails if the result is zero.
+    pub fn add_tweak(self, tweak: &Scalar) -> Result<SecretKey, Error> {
+        let sum = self.scalar() + tweak.0;
+        if is_zero(&sum) {
+            return Err(Error::InvalidTweak);
+        }
+        Ok(SecretKey(scalar_bytes(&sum)))
+    }
+
+    fn scalar(&self) -> k256::Scalar {
+        parse_scalar(&self.0).expect("a valid private key")
+    }
+}
+
+/// A public key, which is never the identity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(k256::PublicKey);
+
+impl PublicKey {
+    /// Parse a compressed, uncompressed or hybrid SEC1 encoded public key.
+    pub fn from_slice(data: &[u8]) -> Result<PublicKey, Error> {
+        let pubkey = match (data.len(), data.first().copied()) {
+            (constants::PUBLIC_KEY_SIZE, Some(0x02 | 0x03))
+            | (constants::UNCOMPRESSED_PUBLIC_KEY_SIZE, Some(0x04)) => {
+                k256::PublicKey::from_sec1_bytes(data)
+            }
+            // The hybrid encoding is the uncompressed encoding with the parity of y in the tag.
+            (constants::UNCOMPRESSED_PUBLIC_KEY_SIZE, Some(tag @ (0x06 | 0x07)))
+                if data[64] & 1 == tag & 1 =>
+            {
+                k256::PublicKey::from_sec1_bytes(&[&[0x04], &data[1..]].concat())
+            }
+            _ => return Err(Error::InvalidPublicKey),
+        };
+        pubkey.map(PublicKey).map_err(|_| Error::InvalidPublicKey)
+    }
+
+    pub fn from_secret_key<C>(_secp: &Secp256k1<C>, sk: &SecretKey) -> PublicKey {
+        Self::from_point(ProjectivePoint::GENERATOR * sk.scalar()).expect("the key is not zero")
+    }
+
+    pub fn serialize(&self) -> [u8; constants::PUBLIC_KEY_SIZE] {
+        compressed(&self.0.to_projective()).expect("not the identity")
+    }
+
+    pub fn serialize_uncompressed(&self) -> [u8; constants::UNCOMPRESSED_PUBLIC_KEY_SIZE] {
+        self.0
+            .as_affine()
+            .to_encoded_point(false)
+            .as_bytes()
+            .try_into()
+            .expect("not the identity")
+    }
+
+    /// Add `tweak * G`. Fails if the result is the identity.
+    pub fn add_exp_tweak<C>(
+        self,
+        _secp: &Secp256k1<C>,
+        tweak: &Scalar,
+    ) -> Result<PublicKey, Error> {
+        Self::from_point(self.0.to_projective() + ProjectivePoint::GENERATOR * tweak.0)
+            .ok_or(Error::InvalidTweak)
+    }
+
+    fn from_point(point: ProjectivePoint) -> Option<PublicKey> {
+        k256::PublicKey::from_affine(point.to_affine())
+            .ok()
+            .map(PublicKey)
+    }
+}
+
+pub mod ecdh {
+    use super::{compressed, PublicKey, SecretKey};
+    use crate::hash::{HashFunction, Sha256};
+
+    /// The shared secret of libsecp256k1, which is the SHA-256 hash of the compressed encoding of
+    /// the shared point.
+    pub struct SharedSecret([u8; 32]);
+
+    impl SharedSecret {
+        pub fn new(point: &PublicKey, scalar: &SecretKey) -> SharedSecret {
+            let shared = compressed(&(point.0.to_projective() * scalar.scalar()))
+                .expect("the product of a point and a non-zero scalar is not the identity");
+            SharedSecret(Sha256::digest(shared).0)
+        }
+
+        pub fn secret_bytes(&self) -> [u8; 32] {
+            self.0
+        }
+    }
+}
+
+pub mod ecdsa {
+    use super::{constants, is_high, parse_scalar, scalar_bytes, Error, Message, PublicKey};
+    use k256::{ecdsa::recoverable, FieldBytes};
+
+    /// The recovery id: bit 0 is the parity of the y-coordinate of `R`, and bit 1 is set if its
+    /// x-coordinate is not smaller than the group order.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RecoveryId(pub(super) i32);
+
+    impl RecoveryId {
+        pub fn from_i32(id: i32) -> Result<RecoveryId, Error> {
+            match id {
+                0..=3 => Ok(RecoveryId(id)),
+                _ => Err(Error::InvalidRecoveryId),
+            }
+        }
+
+        pub fn to_i32(self) -> i32 {
+            self.0
+        }
+    }
+
+    /// A signature `(r, s)`. As in libsecp256k1, `r` and `s` are smaller than the group order but
+    /// may be zero, in which case the signature is invalid.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Signature {
+        pub(super) r: k256::Scalar,
+        pub(super) s: k256::Scalar,
+    }
+
+    impl Signature {
+        pub fn from_compact(data: &[u8]) -> Result<Signature, Error> {
+            if data.len() != constants::COMPACT_SIGNATURE_SIZE {
+                return Err(Error::InvalidSignature);
+            }
+            match (parse_scalar(&data[..32]), parse_scalar(&data[32..])) {
+                (Some(r), Some(s)) => Ok(Signature { r, s }),
+                _ => Err(Error::InvalidSignature),
+            }
+        }
+
+        /// Parse a strict DER encoded signature.
+        pub fn from_der(data: &[u8]) -> Result<Signature, Error> {
+            let signature =
+                k256::ecdsa::Signature::from_der(data).map_err(|_| Error::InvalidSignature)?;
+            Self::from_compact(signature.as_ref())
+        }
+
+        pub fn serialize_compact(&self) -> [u8; constants::COMPACT_SIGNATURE_SIZE] {
+            let mut bytes = [0u8; constants::COMPACT_SIGNATURE_SIZE];
+            bytes[..32].copy_from_slice(&scalar_bytes(&self.r));
+            bytes[32..].copy_from_slice(&scalar_bytes(&self.s));
+            bytes
+        }
+
+        /// Replace `s` by `n - s` if the signature is in the high-S form.
+        pub fn normalize_s(&mut self) {
+            if is_high(&self.s) {
+                self.s = -self.s;
+            }
+        }
+
+        /// The k256 signature, which fails if `r` or `s` is zero.
+        pub(super) fn to_k256(self) -> Result<k256::ecdsa::Signature, Error> {
+            k256::ecdsa::Signature::from_scalars(self.r, self.s)
+                .map_err(|_| Error::InvalidSignature)
+        }
+    }
+
+    /// A signature together with the recovery id of its `R`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RecoverableSignature {
+        pub(super) sig: Signature,
+        pub(super) recovery_id: RecoveryId,
+    }
+
+    impl RecoverableSignature {
+        pub fn from_compact(data: &[u8], recid: RecoveryId) -> Result<RecoverableSignature, Error> {
+            Ok(RecoverableSignature {
+                sig: Signature::from_compact(data)?,
+                recovery_id: recid,
+            })
+        }
+
+        pub fn serialize_compact(&self) -> (RecoveryId, [u8; constants::COMPACT_SIGNATURE_SIZE]) {
+            (self.recovery_id, self.sig.serialize_compact())
+        }
+
+        pub fn to_standard(&self) -> Signature {
+            self.sig
+        }
+
+        /// Recover the public key as libsecp256k1 does, also for signatures in the high-S form.
+        /// Recovery ids 2 and 3 are not supported by k256 and fail.
+        pub fn recover(&self, msg: &Message) -> Result<PublicKey, Error> {
+            let RecoverableSignature {
+                mut sig,
+                recovery_id: RecoveryId(mut id),
+            } = *self;
+            if id & 2 != 0 {
+                return Err(Error::InvalidSignature);
+            }
+            // Negating s corresponds to negating R, which flips the parity of its y-coordinate.
+            if is_high(&sig.s) {
+                sig.s = -sig.s;
+                id ^= 1;
+            }
+            let id = recoverable::Id::new(id as u8).map_err(|_| Error::InvalidRecoveryId)?;
+            recoverable::Signature::new(&sig.to_k256()?, id)
+                .and_then(|signature| {
+                    signature
+                        .recover_verifying_key_from_digest_bytes(FieldBytes::from_slice(&msg.0))
+                })
+                .map(|key| PublicKey(k256::PublicKey::from(&key)))
+                .map_err(|_| Error::InvalidSignature)
+        }
+
+        /// Convert a signature of k256, whose recovery id is the last byte.
+        pub(super) fn from_k256(signature: &recoverable::Signature) -> RecoverableSignature {
+            let bytes: &[u8] = signature.as_ref();
+            RecoverableSignature {
+                sig: Signature::from_compact(&bytes[..constants::COMPACT_SIGNATURE_SIZE])
+                    .expect("k256 signatures are valid"),
+                recovery_id: RecoveryId(i32::from(bytes[constants::COMPACT_SIGNATURE_SIZE])),
+            }
+        }
+    }
+}
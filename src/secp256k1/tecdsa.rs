@@ -0,0 +1,963 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a threshold ECDSA signing protocol for secp256k1. A set of at least `t` out
+//! of `n` parties jointly produces a standard [Secp256k1Signature], which verifies under the group
+//! public key with [Verifier::verify](signature::Verifier::verify).
+//!
+//! Only the key generation uses a trusted dealer: [KeyGen::new] creates the private key `x` and
+//! hands a Shamir share of it to each party as a [KeyShare]. The nonces are generated jointly by
+//! the signers, following the structure of the GG18 signing protocol:
+//!
+//! 1. Each signer `i` picks random `k_i` and `γ_i` and broadcasts an [MtaRequest] with
+//!    `Γ_i = [γ_i]G` ([Presigner::new]).
+//! 2. Each pair of signers runs multiplicative-to-additive (MtA) conversions of `k_i γ_j` and
+//!    `k_i w_j`, where `w_j` is the Lagrange-weighted key share of `j`, so that no party learns the
+//!    other party's input. Each signer sends an [MtaResponse] to every other signer
+//!    ([Presigner::respond]).
+//! 3. Each signer now holds additive shares `δ_i` of `δ = kγ` and `σ_i` of `σ = kx`, where
+//!    `k = Σ k_i` and `γ = Σ γ_i`, and broadcasts `δ_i` as a [DeltaShare]
+//!    ([PresignerAwaitingResponses::receive]). The nonce point is `R = [1/δ](Σ Γ_i) = [1/k]G`, so
+//!    nobody learns the nonce `1/k` ([PresignerAwaitingDeltas::finish]).
+//!
+//! The result is a [PresignatureShare], which does not depend on the message. Signing is a single
+//! round: each signer computes `s_i = z k_i + r σ_i` on the message hash `z` with
+//! [PresignatureShare::partial_sign], and [PartialSign::combine] adds the partial signatures of all
+//! signers to get `s = k (z + r x)`.
+//!
+//! The key shares and all protocol messages have canonical encodings with `to_bytes` and
+//! `from_bytes`, which are also used by their [Serialize] and [Deserialize] implementations, so
+//! they can be sent between parties on different machines. Decoding checks that all scalars are
+//! smaller than the group order, that all points are valid and that the indices are consistent.
+//!
+//! The MtA conversions use Gilboa's multiplication on top of one oblivious transfer per bit of
+//! `k_i`, based on the Bellare-Micali construction over secp256k1.
+//!
+//! Note that the protocol is only secure against honest-but-curious parties: the parties do not
+//! prove that their messages are computed correctly, so a malicious party can make the signature
+//! invalid or learn bits of the nonce shares of the other parties. Each presignature must be used
+//! for at most one message, since two signatures with the same nonce reveal the private key, which
+//! is why [PresignatureShare::partial_sign] consumes the share.
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::secp256k1::tecdsa::{KeyGen, PartialSign, Presigner};
+//! use rand::thread_rng;
+//! use signature::Verifier;
+//! # fn main() {
+//!     let key_gen = KeyGen::new(2, 3, &mut thread_rng()).unwrap();
+//!     let public_key = key_gen.public_key().clone();
+//!     let key_shares = key_gen.into_shares();
+//!     let signers = [1, 3];
+//!
+//!     // Round 1: broadcast requests.
+//!     let (presigners, requests): (Vec<_>, Vec<_>) = signers
+//!         .iter()
+//!         .map(|i| {
+//!             let key_share = &key_shares[*i as usize - 1];
+//!             Presigner::new(key_share, &signers, &mut thread_rng()).unwrap()
+//!         })
+//!         .unzip();
+//!
+//!     // Round 2: send a response to each other signer.
+//!     let (presigners, responses): (Vec<_>, Vec<_>) = presigners
+//!         .into_iter()
+//!         .map(|p| p.respond(&requests, &mut thread_rng()).unwrap())
+//!         .unzip();
+//!     let responses: Vec<_> = responses.into_iter().flatten().collect();
+//!
+//!     // Round 3: broadcast the shares of delta.
+//!     let (presigners, deltas): (Vec<_>, Vec<_>) = presigners
+//!         .into_iter()
+//!         .map(|p| p.receive(&responses).unwrap())
+//!         .unzip();
+//!     let presignatures = presigners.into_iter().map(|p| p.finish(&deltas).unwrap());
+//!
+//!     // Signing.
+//!     let message: &[u8] = b"Hello, world!";
+//!     let partials: Vec<PartialSign> = presignatures
+//!         .map(|share| share.partial_sign(message))
+//!         .collect();
+//!     let signature = PartialSign::combine(&partials).unwrap();
+//!     assert!(public_key.verify(message, &signature).is_ok());
+//! # }
+//! ```
+
+use super::{hash_message, Secp256k1PublicKey, Secp256k1Signature};
+use crate::{
+    error::FastCryptoError,
+    hash::{HashFunction, Sha256},
+    traits::ToFromBytes,
+};
+use k256::{
+    elliptic_curve::{group::Group, ops::Reduce, sec1::ToEncodedPoint, Field, PrimeField},
+    FieldBytes, ProjectivePoint, Scalar, U256,
+};
+use once_cell::sync::Lazy;
+use rand::{CryptoRng, RngCore};
+use serde::{de, Deserialize, Serialize};
+use std::fmt::{self, Debug};
+use subtle::{Choice, ConditionallySelectable};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// The number of oblivious transfers in an MtA conversion, one for each bit of a scalar.
+const OT_COUNT: usize = 256;
+
+/// The sizes in bytes of the encodings of indices, scalars and compressed points.
+const INDEX_SIZE: usize = 2;
+const SCALAR_SIZE: usize = 32;
+const POINT_SIZE: usize = 33;
+
+/// The domain separation tag for deriving the oblivious transfer pads.
+const OT_PAD_DST: &[u8] = b"fastcrypto-tecdsa-mta-pad";
+
+/// A point whose discrete logarithm is unknown, found by hashing to x-coordinates until one is on
+/// the curve. The receiver of an oblivious transfer can know the discrete logarithm of at most one
+/// of two points adding up to it.
+static OT_POINT: Lazy<ProjectivePoint> = Lazy::new(|| {
+    (0u8..)
+        .find_map(|counter| {
+            let x =
+                Sha256::digest([b"fastcrypto-tecdsa-ot-point".as_slice(), &[counter][..]].concat());
+            k256::PublicKey::from_sec1_bytes(&[[2u8].as_slice(), x.0.as_slice()].concat()).ok()
+        })
+        .expect("half of all x-coordinates are on the curve")
+        .to_projective()
+});
+
+/// The trusted dealer, which generates the private key and a share of it for each party.
+pub struct KeyGen {
+    public_key: Secp256k1PublicKey,
+    shares: Vec<KeyShare>,
+}
+
+/// The share of the private key held by one party.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct KeyShare {
+    index: u16,
+    threshold: u16,
+    parties: u16,
+    secret_share: Scalar,
+    #[zeroize(skip)]
+    public_key: Secp256k1PublicKey,
+}
+
+/// The state of a signer after the first round of presigning.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct Presigner {
+    index: u16,
+    signers: Vec<u16>,
+    nonce_share: Scalar,
+    gamma_share: Scalar,
+    weighted_secret_share: Scalar,
+    ot_secrets: Vec<Scalar>,
+}
+
+/// The state of a signer after the second round of presigning.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct PresignerAwaitingResponses {
+    index: u16,
+    signers: Vec<u16>,
+    nonce_share: Scalar,
+    ot_secrets: Vec<Scalar>,
+    delta_share: Scalar,
+    sigma_share: Scalar,
+    #[zeroize(skip)]
+    gamma_point: ProjectivePoint,
+}
+
+/// The state of a signer after the third round of presigning.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct PresignerAwaitingDeltas {
+    index: u16,
+    signers: Vec<u16>,
+    nonce_share: Scalar,
+    delta_share: Scalar,
+    sigma_share: Scalar,
+    #[zeroize(skip)]
+    gamma_point: ProjectivePoint,
+}
+
+/// The message broadcast by a signer in the first round of presigning.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MtaRequest {
+    sender: u16,
+    gamma_point: [u8; POINT_SIZE],
+    ot_keys: Vec<[u8; POINT_SIZE]>,
+}
+
+/// The message sent by a signer to another signer in the second round of presigning.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MtaResponse {
+    sender: u16,
+    receiver: u16,
+    ot_nonce: [u8; POINT_SIZE],
+    ciphertexts: Vec<[Scalar; 4]>,
+}
+
+/// The message broadcast by a signer in the third round of presigning.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeltaShare {
+    sender: u16,
+    delta: Scalar,
+}
+
+/// The share of a presignature held by one signer. It may be used to sign a single message.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct PresignatureShare {
+    index: u16,
+    signers: Vec<u16>,
+    nonce_point: [u8; POINT_SIZE],
+    nonce_share: Scalar,
+    sigma_share: Scalar,
+}
+
+/// A partial signature of one signer on a message.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PartialSign {
+    index: u16,
+    signers: Vec<u16>,
+    nonce_point: [u8; POINT_SIZE],
+    s: Scalar,
+}
+
+impl KeyGen {
+    /// Generate a private key, such that any `threshold` out of `parties` parties can sign. Fails
+    /// with [FastCryptoError::InvalidInput] if `threshold` is zero or larger than `parties`.
+    pub fn new<R: CryptoRng + RngCore>(
+        threshold: u16,
+        parties: u16,
+        rng: &mut R,
+    ) -> Result<Self, FastCryptoError> {
+        if threshold == 0 || threshold > parties {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        // Shamir shares of the secret, i.e., the evaluations at `1, ..., parties` of a random
+        // polynomial of degree `threshold - 1` whose constant term is the secret.
+        let coefficients: Zeroizing<Vec<Scalar>> = Zeroizing::new(
+            (0..threshold)
+                .map(|_| random_nonzero_scalar(&mut *rng))
+                .collect(),
+        );
+        let public_key = Secp256k1PublicKey::from_bytes(&encode_point(
+            &(ProjectivePoint::GENERATOR * coefficients[0]),
+        ))
+        .expect("a multiple of the generator by a non-zero scalar is a valid key");
+        let shares = (1..=parties)
+            .map(|index| {
+                let x = Scalar::from(index as u64);
+                KeyShare {
+                    index,
+                    threshold,
+                    parties,
+                    secret_share: coefficients
+                        .iter()
+                        .rev()
+                        .fold(Scalar::ZERO, |acc, c| acc * x + c),
+                    public_key: public_key.clone(),
+                }
+            })
+            .collect();
+        Ok(Self { public_key, shares })
+    }
+
+    /// The public key under which the combined signatures verify.
+    pub fn public_key(&self) -> &Secp256k1PublicKey {
+        &self.public_key
+    }
+
+    /// The key shares of all parties, where the share of party `i` is at position `i - 1`. The
+    /// dealer does not keep the private key, so this can only be called once.
+    pub fn into_shares(self) -> Vec<KeyShare> {
+        self.shares
+    }
+}
+
+impl KeyShare {
+    /// The index of the party holding this share, starting at 1.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// The public key under which the combined signatures verify.
+    pub fn public_key(&self) -> &Secp256k1PublicKey {
+        &self.public_key
+    }
+}
+
+impl Presigner {
+    /// Start presigning with the parties `signers`, which must include the owner of `key_share`.
+    /// Returns the state of this signer and the request to broadcast to the other signers. Fails
+    /// with [FastCryptoError::InvalidInput] if there are fewer signers than the threshold or if a
+    /// signer is invalid or appears twice.
+    pub fn new<R: CryptoRng + RngCore>(
+        key_share: &KeyShare,
+        signers: &[u16],
+        rng: &mut R,
+    ) -> Result<(Self, MtaRequest), FastCryptoError> {
+        let mut sorted_signers = signers.to_vec();
+        sorted_signers.sort_unstable();
+        sorted_signers.dedup();
+        if sorted_signers.len() != signers.len()
+            || signers.len() < key_share.threshold as usize
+            || !signers.contains(&key_share.index)
+            || signers.iter().any(|i| *i == 0 || *i > key_share.parties)
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        let nonce_share = random_nonzero_scalar(rng);
+        let gamma_share = random_nonzero_scalar(rng);
+        let ot_secrets: Vec<Scalar> = (0..OT_COUNT).map(|_| random_nonzero_scalar(rng)).collect();
+        // The receiver knows the discrete logarithm of the key for its choice bit, and the key for
+        // the other bit is the difference to the point with unknown discrete logarithm.
+        let ot_keys = ot_secrets
+            .iter()
+            .zip(bits(&nonce_share))
+            .map(|(secret, bit)| {
+                let key = ProjectivePoint::GENERATOR * secret;
+                encode_point(&ProjectivePoint::conditional_select(
+                    &key,
+                    &(*OT_POINT - key),
+                    bit,
+                ))
+            })
+            .collect();
+        let request = MtaRequest {
+            sender: key_share.index,
+            gamma_point: encode_point(&(ProjectivePoint::GENERATOR * gamma_share)),
+            ot_keys,
+        };
+        let presigner = Self {
+            index: key_share.index,
+            signers: sorted_signers.clone(),
+            nonce_share,
+            gamma_share,
+            weighted_secret_share: lagrange_coefficient(key_share.index, &sorted_signers)
+                * key_share.secret_share,
+            ot_secrets,
+        };
+        Ok((presigner, request))
+    }
+
+    /// Process the requests of the other signers, which may also include the request of this
+    /// signer. Returns the state of this signer and the responses to send to the other signers.
+    /// Fails with [FastCryptoError::InvalidInput] if a request is missing or invalid.
+    pub fn respond<R: CryptoRng + RngCore>(
+        self,
+        requests: &[MtaRequest],
+        rng: &mut R,
+    ) -> Result<(PresignerAwaitingResponses, Vec<MtaResponse>), FastCryptoError> {
+        let requests = from_other_signers(self.index, &self.signers, requests, |r| r.sender)?;
+        let mut gamma_point = ProjectivePoint::GENERATOR * self.gamma_share;
+        let mut delta_share = self.nonce_share * self.gamma_share;
+        let mut sigma_share = self.nonce_share * self.weighted_secret_share;
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            gamma_point += decode_point(&request.gamma_point)?;
+            if request.ot_keys.len() != OT_COUNT {
+                return Err(FastCryptoError::InvalidInput);
+            }
+
+            // Gilboa's multiplication: for bit l of the receiver's nonce share, the receiver
+            // learns t_l + bit * y, and the sender keeps -t_l, for both inputs y of this signer.
+            let nonce = random_nonzero_scalar(rng);
+            let nonce_times_ot_point = *OT_POINT * nonce;
+            let mut power = Scalar::ONE;
+            let mut ciphertexts = Vec::with_capacity(OT_COUNT);
+            for (l, key) in request.ot_keys.iter().enumerate() {
+                let key_0 = decode_point(key)? * nonce;
+                let key_1 = nonce_times_ot_point - key_0;
+                let mask_gamma = Scalar::random(&mut *rng);
+                let mask_secret = Scalar::random(&mut *rng);
+                let pad = |key: &ProjectivePoint, product: u8| {
+                    ot_pad(self.index, request.sender, l, product, key)
+                };
+                ciphertexts.push([
+                    mask_gamma + pad(&key_0, 0),
+                    mask_gamma + self.gamma_share + pad(&key_1, 0),
+                    mask_secret + pad(&key_0, 1),
+                    mask_secret + self.weighted_secret_share + pad(&key_1, 1),
+                ]);
+                delta_share -= power * mask_gamma;
+                sigma_share -= power * mask_secret;
+                power += power;
+            }
+            responses.push(MtaResponse {
+                sender: self.index,
+                receiver: request.sender,
+                ot_nonce: encode_point(&(ProjectivePoint::GENERATOR * nonce)),
+                ciphertexts,
+            });
+        }
+        let presigner = PresignerAwaitingResponses {
+            index: self.index,
+            signers: self.signers.clone(),
+            nonce_share: self.nonce_share,
+            ot_secrets: self.ot_secrets.clone(),
+            delta_share,
+            sigma_share,
+            gamma_point,
+        };
+        Ok((presigner, responses))
+    }
+}
+
+impl PresignerAwaitingResponses {
+    /// Process the responses sent to this signer. Responses to other signers are ignored. Returns
+    /// the state of this signer and the share of delta to broadcast to the other signers. Fails
+    /// with [FastCryptoError::InvalidInput] if a response is missing or invalid.
+    pub fn receive(
+        self,
+        responses: &[MtaResponse],
+    ) -> Result<(PresignerAwaitingDeltas, DeltaShare), FastCryptoError> {
+        let responses: Vec<&MtaResponse> = responses
+            .iter()
+            .filter(|r| r.receiver == self.index)
+            .collect();
+        let responses = from_other_signers(self.index, &self.signers, &responses, |r| r.sender)?;
+        let mut delta_share = self.delta_share;
+        let mut sigma_share = self.sigma_share;
+        for response in responses {
+            if response.ciphertexts.len() != OT_COUNT {
+                return Err(FastCryptoError::InvalidInput);
+            }
+            let ot_nonce = decode_point(&response.ot_nonce)?;
+            let mut power = Scalar::ONE;
+            for (l, ((ciphertext, secret), bit)) in response
+                .ciphertexts
+                .iter()
+                .zip(&self.ot_secrets)
+                .zip(bits(&self.nonce_share))
+                .enumerate()
+            {
+                let key = ot_nonce * secret;
+                let pad = |product: u8| ot_pad(response.sender, self.index, l, product, &key);
+                let gamma = Scalar::conditional_select(&ciphertext[0], &ciphertext[1], bit);
+                let weighted_secret =
+                    Scalar::conditional_select(&ciphertext[2], &ciphertext[3], bit);
+                delta_share += power * (gamma - pad(0));
+                sigma_share += power * (weighted_secret - pad(1));
+                power += power;
+            }
+        }
+        let presigner = PresignerAwaitingDeltas {
+            index: self.index,
+            signers: self.signers.clone(),
+            nonce_share: self.nonce_share,
+            delta_share,
+            sigma_share,
+            gamma_point: self.gamma_point,
+        };
+        let delta = DeltaShare {
+            sender: self.index,
+            delta: delta_share,
+        };
+        Ok((presigner, delta))
+    }
+}
+
+impl PresignerAwaitingDeltas {
+    /// Compute the presignature share of this signer from the shares of delta of the other
+    /// signers, which may also include the share of this signer. Fails with
+    /// [FastCryptoError::InvalidInput] if a share is missing or if the shares give an invalid
+    /// nonce.
+    pub fn finish(self, deltas: &[DeltaShare]) -> Result<PresignatureShare, FastCryptoError> {
+        let deltas = from_other_signers(self.index, &self.signers, deltas, |d| d.sender)?;
+        let delta = deltas
+            .iter()
+            .fold(self.delta_share, |acc, share| acc + share.delta);
+        let delta_inverse =
+            Option::<Scalar>::from(delta.invert()).ok_or(FastCryptoError::InvalidInput)?;
+        let nonce_point = self.gamma_point * delta_inverse;
+        if bool::from(Group::is_identity(&nonce_point)) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(PresignatureShare {
+            index: self.index,
+            signers: self.signers.clone(),
+            nonce_point: encode_point(&nonce_point),
+            nonce_share: self.nonce_share,
+            sigma_share: self.sigma_share,
+        })
+    }
+}
+
+impl MtaRequest {
+    /// The index of the signer which sent this request.
+    pub fn sender(&self) -> u16 {
+        self.sender
+    }
+}
+
+impl MtaResponse {
+    /// The index of the signer which sent this response.
+    pub fn sender(&self) -> u16 {
+        self.sender
+    }
+
+    /// The index of the signer to which this response must be sent.
+    pub fn receiver(&self) -> u16 {
+        self.receiver
+    }
+}
+
+impl DeltaShare {
+    /// The index of the signer which sent this share.
+    pub fn sender(&self) -> u16 {
+        self.sender
+    }
+}
+
+impl PresignatureShare {
+    /// The index of the signer holding this share, starting at 1.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Compute this signer's partial signature on `msg`. The message is hashed as in
+    /// [Verifier::verify](signature::Verifier::verify).
+    pub fn partial_sign(self, msg: &[u8]) -> PartialSign {
+        let z = message_scalar(msg);
+        let r = x_coordinate(&self.nonce_point);
+        PartialSign {
+            index: self.index,
+            signers: self.signers.clone(),
+            nonce_point: self.nonce_point,
+            s: z * self.nonce_share + r * self.sigma_share,
+        }
+    }
+}
+
+impl PartialSign {
+    /// The index of the signer which created this partial signature, starting at 1.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Combine the partial signatures of all signers of a presignature on the same message into a
+    /// signature. Fails with [FastCryptoError::InvalidInput] if a partial signature is missing,
+    /// if they come from different presignatures or if a signer appears twice.
+    ///
+    /// The result is only a valid signature if all partial signatures were computed correctly, so
+    /// it should be verified before it is used.
+    pub fn combine(partials: &[PartialSign]) -> Result<Secp256k1Signature, FastCryptoError> {
+        let first = partials.first().ok_or(FastCryptoError::InvalidInput)?;
+        if partials.len() != first.signers.len()
+            || partials
+                .iter()
+                .any(|p| p.signers != first.signers || p.nonce_point != first.nonce_point)
+        {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut indices: Vec<u16> = partials.iter().map(|p| p.index).collect();
+        indices.sort_unstable();
+        if indices != first.signers {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut s = partials.iter().fold(Scalar::ZERO, |acc, p| acc + p.s);
+
+        // The recovery id encodes the parity of the y-coordinate of R and whether its
+        // x-coordinate is larger than the group order. Replacing s by -s to get the low-S form
+        // corresponds to replacing R by -R, which flips the parity.
+        let r = x_coordinate(&first.nonce_point);
+        let mut recovery_id = first.nonce_point[0] - 2;
+        if r.to_bytes().as_slice() != &first.nonce_point[1..] {
+            recovery_id |= 2;
+        }
+        if bool::from(s.is_high()) {
+            s = -s;
+            recovery_id ^= 1;
+        }
+
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&r.to_bytes());
+        bytes[32..64].copy_from_slice(&s.to_bytes());
+        bytes[64] = recovery_id;
+        <Secp256k1Signature as signature::Signature>::from_bytes(&bytes)
+            .map_err(|_| FastCryptoError::InvalidSignature)
+    }
+}
+
+impl KeyShare {
+    /// The encoding of this share: the index, the threshold and the number of parties as
+    /// big-endian `u16`, the 32-byte share of the private key and the compressed public key.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(
+            3 * INDEX_SIZE + SCALAR_SIZE + POINT_SIZE,
+        ));
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&self.threshold.to_be_bytes());
+        bytes.extend_from_slice(&self.parties.to_be_bytes());
+        bytes.extend_from_slice(&self.secret_share.to_bytes());
+        bytes.extend_from_slice(self.public_key.as_ref());
+        bytes
+    }
+
+    /// Decode the output of [KeyShare::to_bytes]. Fails with [FastCryptoError::InvalidInput] if
+    /// the encoding is invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let mut reader = Reader(bytes);
+        let index = reader.index()?;
+        let threshold = reader.index()?;
+        let parties = reader.index()?;
+        let secret_share = reader.scalar()?;
+        let public_key = Secp256k1PublicKey::from_bytes(&reader.point()?)
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        reader.finish()?;
+        if threshold > parties || index > parties {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            index,
+            threshold,
+            parties,
+            secret_share,
+            public_key,
+        })
+    }
+}
+
+impl MtaRequest {
+    /// The encoding of this request: the index of the sender as big-endian `u16`, followed by the
+    /// compressed points `Γ_i` and the 256 oblivious transfer keys.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(INDEX_SIZE + (1 + OT_COUNT) * POINT_SIZE);
+        bytes.extend_from_slice(&self.sender.to_be_bytes());
+        bytes.extend_from_slice(&self.gamma_point);
+        self.ot_keys
+            .iter()
+            .for_each(|key| bytes.extend_from_slice(key));
+        bytes
+    }
+
+    /// Decode the output of [MtaRequest::to_bytes]. Fails with [FastCryptoError::InvalidInput]
+    /// if the encoding is invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let mut reader = Reader(bytes);
+        let sender = reader.index()?;
+        let gamma_point = reader.point()?;
+        let ot_keys = (0..OT_COUNT)
+            .map(|_| reader.point())
+            .collect::<Result<_, _>>()?;
+        reader.finish()?;
+        Ok(Self {
+            sender,
+            gamma_point,
+            ot_keys,
+        })
+    }
+}
+
+impl MtaResponse {
+    /// The encoding of this response: the indices of the sender and the receiver as big-endian
+    /// `u16`, the compressed nonce point of the oblivious transfers and the four 32-byte
+    /// ciphertexts of each of the 256 oblivious transfers.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(2 * INDEX_SIZE + POINT_SIZE + 4 * OT_COUNT * SCALAR_SIZE);
+        bytes.extend_from_slice(&self.sender.to_be_bytes());
+        bytes.extend_from_slice(&self.receiver.to_be_bytes());
+        bytes.extend_from_slice(&self.ot_nonce);
+        self.ciphertexts
+            .iter()
+            .flatten()
+            .for_each(|c| bytes.extend_from_slice(&c.to_bytes()));
+        bytes
+    }
+
+    /// Decode the output of [MtaResponse::to_bytes]. Fails with [FastCryptoError::InvalidInput]
+    /// if the encoding is invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let mut reader = Reader(bytes);
+        let sender = reader.index()?;
+        let receiver = reader.index()?;
+        let ot_nonce = reader.point()?;
+        let ciphertexts = (0..OT_COUNT)
+            .map(|_| {
+                Ok([
+                    reader.scalar()?,
+                    reader.scalar()?,
+                    reader.scalar()?,
+                    reader.scalar()?,
+                ])
+            })
+            .collect::<Result<_, FastCryptoError>>()?;
+        reader.finish()?;
+        if sender == receiver {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            sender,
+            receiver,
+            ot_nonce,
+            ciphertexts,
+        })
+    }
+}
+
+impl DeltaShare {
+    /// The encoding of this share: the index of the sender as big-endian `u16` and the 32-byte
+    /// share of delta.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&self.sender.to_be_bytes()[..], &self.delta.to_bytes()].concat()
+    }
+
+    /// Decode the output of [DeltaShare::to_bytes]. Fails with [FastCryptoError::InvalidInput]
+    /// if the encoding is invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let mut reader = Reader(bytes);
+        let sender = reader.index()?;
+        let delta = reader.scalar()?;
+        reader.finish()?;
+        Ok(Self { sender, delta })
+    }
+}
+
+impl PartialSign {
+    /// The encoding of this partial signature: the index of the signer and the number of signers
+    /// as big-endian `u16`, the indices of all signers in increasing order, the compressed nonce
+    /// point and the 32-byte partial signature.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity((2 + self.signers.len()) * INDEX_SIZE + POINT_SIZE + SCALAR_SIZE);
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&(self.signers.len() as u16).to_be_bytes());
+        self.signers
+            .iter()
+            .for_each(|i| bytes.extend_from_slice(&i.to_be_bytes()));
+        bytes.extend_from_slice(&self.nonce_point);
+        bytes.extend_from_slice(&self.s.to_bytes());
+        bytes
+    }
+
+    /// Decode the output of [PartialSign::to_bytes]. Fails with [FastCryptoError::InvalidInput]
+    /// if the encoding is invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let mut reader = Reader(bytes);
+        let index = reader.index()?;
+        let signer_count = reader.take(INDEX_SIZE)?;
+        let signers: Vec<u16> = (0..u16::from_be_bytes([signer_count[0], signer_count[1]]))
+            .map(|_| reader.index())
+            .collect::<Result<_, _>>()?;
+        let nonce_point = reader.point()?;
+        let s = reader.scalar()?;
+        reader.finish()?;
+        if signers.windows(2).any(|pair| pair[0] >= pair[1]) || !signers.contains(&index) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(Self {
+            index,
+            signers,
+            nonce_point,
+            s,
+        })
+    }
+}
+
+/// Implement [Serialize] and [Deserialize] with the `to_bytes` and `from_bytes` methods of a type.
+macro_rules! serde_with_bytes {
+    ($type:ty) => {
+        impl Serialize for $type {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&self.to_bytes())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = Zeroizing::new(Vec::<u8>::deserialize(deserializer)?);
+                Self::from_bytes(&bytes).map_err(|e| de::Error::custom(e.to_string()))
+            }
+        }
+    };
+}
+
+serde_with_bytes!(KeyShare);
+serde_with_bytes!(MtaRequest);
+serde_with_bytes!(MtaResponse);
+serde_with_bytes!(DeltaShare);
+serde_with_bytes!(PartialSign);
+
+impl Debug for KeyGen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyGen(<redacted>)")
+    }
+}
+
+impl Debug for KeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyShare({}, <redacted>)", self.index)
+    }
+}
+
+impl Debug for Presigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Presigner({}, <redacted>)", self.index)
+    }
+}
+
+impl Debug for PresignerAwaitingResponses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PresignerAwaitingResponses({}, <redacted>)", self.index)
+    }
+}
+
+impl Debug for PresignerAwaitingDeltas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PresignerAwaitingDeltas({}, <redacted>)", self.index)
+    }
+}
+
+impl Debug for PresignatureShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PresignatureShare({}, <redacted>)", self.index)
+    }
+}
+
+impl Debug for PartialSign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PartialSign({}, {})",
+            self.index,
+            hex::encode(self.s.to_bytes())
+        )
+    }
+}
+
+/// The messages of all signers other than `index`, in the order of `signers`. Fails if a message
+/// is missing, appears twice or comes from a party which is not a signer.
+fn from_other_signers<'a, M>(
+    index: u16,
+    signers: &[u16],
+    messages: &'a [M],
+    sender: impl Fn(&M) -> u16,
+) -> Result<Vec<&'a M>, FastCryptoError> {
+    if messages.iter().any(|m| {
+        !signers.contains(&sender(m))
+            || messages.iter().filter(|n| sender(n) == sender(m)).count() != 1
+    }) {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    signers
+        .iter()
+        .filter(|i| **i != index)
+        .map(|i| {
+            messages
+                .iter()
+                .find(|m| sender(m) == *i)
+                .ok_or(FastCryptoError::InvalidInput)
+        })
+        .collect()
+}
+
+/// A reader of the fields of an encoding, which checks that each field is valid.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], FastCryptoError> {
+        if self.0.len() < length {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let (field, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(field)
+    }
+
+    /// A big-endian `u16` index of a party, which must not be zero.
+    fn index(&mut self) -> Result<u16, FastCryptoError> {
+        let bytes = self.take(INDEX_SIZE)?;
+        match u16::from_be_bytes([bytes[0], bytes[1]]) {
+            0 => Err(FastCryptoError::InvalidInput),
+            index => Ok(index),
+        }
+    }
+
+    /// A 32-byte big-endian scalar, which must be smaller than the group order.
+    fn scalar(&mut self) -> Result<Scalar, FastCryptoError> {
+        let bytes = self.take(SCALAR_SIZE)?;
+        Option::<Scalar>::from(<Scalar as PrimeField>::from_repr(*FieldBytes::from_slice(
+            bytes,
+        )))
+        .ok_or(FastCryptoError::InvalidInput)
+    }
+
+    /// A compressed point, which must be a valid point other than the identity.
+    fn point(&mut self) -> Result<[u8; POINT_SIZE], FastCryptoError> {
+        let mut point = [0u8; POINT_SIZE];
+        point.copy_from_slice(self.take(POINT_SIZE)?);
+        decode_point(&point)?;
+        Ok(point)
+    }
+
+    /// Check that all bytes were read.
+    fn finish(self) -> Result<(), FastCryptoError> {
+        match self.0.is_empty() {
+            true => Ok(()),
+            false => Err(FastCryptoError::InvalidInput),
+        }
+    }
+}
+
+/// The bits of a scalar, starting with the least significant one.
+fn bits(scalar: &Scalar) -> impl Iterator<Item = Choice> {
+    let bytes = scalar.to_bytes();
+    (0..OT_COUNT).map(move |l| Choice::from((bytes[31 - l / 8] >> (l % 8)) & 1))
+}
+
+/// The pad for the message of the oblivious transfer `l` from `sender` to `receiver` for the
+/// product `product`, derived from the shared `key`.
+fn ot_pad(sender: u16, receiver: u16, l: usize, product: u8, key: &ProjectivePoint) -> Scalar {
+    let mut hash = Sha256::default();
+    hash.update(OT_PAD_DST);
+    hash.update(sender.to_be_bytes());
+    hash.update(receiver.to_be_bytes());
+    hash.update((l as u16).to_be_bytes());
+    hash.update([product]);
+    hash.update(encode_point(key));
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(*FieldBytes::from_slice(&hash.finalize().0))
+}
+
+fn random_nonzero_scalar<R: CryptoRng + RngCore>(rng: &mut R) -> Scalar {
+    loop {
+        let scalar = Scalar::random(&mut *rng);
+        if !bool::from(scalar.is_zero()) {
+            return scalar;
+        }
+    }
+}
+
+/// The compressed encoding of a point, which must not be the identity.
+fn encode_point(point: &ProjectivePoint) -> [u8; POINT_SIZE] {
+    let mut bytes = [0u8; 33];
+    bytes.copy_from_slice(point.to_affine().to_encoded_point(true).as_bytes());
+    bytes
+}
+
+fn decode_point(bytes: &[u8; POINT_SIZE]) -> Result<ProjectivePoint, FastCryptoError> {
+    k256::PublicKey::from_sec1_bytes(bytes)
+        .map(|key| key.to_projective())
+        .map_err(|_| FastCryptoError::InvalidInput)
+}
+
+/// The x-coordinate of a compressed point reduced modulo the group order.
+fn x_coordinate(point: &[u8; POINT_SIZE]) -> Scalar {
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(*FieldBytes::from_slice(&point[1..]))
+}
+
+fn message_scalar(msg: &[u8]) -> Scalar {
+    <Scalar as Reduce<U256>>::from_be_bytes_reduced(*FieldBytes::from_slice(&hash_message(msg)[..]))
+}
+
+/// The Lagrange coefficient of `index` for interpolating at zero from the shares of `indices`,
+/// which must be distinct and non-zero.
+fn lagrange_coefficient(index: u16, indices: &[u16]) -> Scalar {
+    let mut numerator = Scalar::ONE;
+    let mut denominator = Scalar::ONE;
+    for &j in indices.iter().filter(|&&j| j != index) {
+        numerator *= Scalar::from(j as u64);
+        denominator *= Scalar::from(j as u64) - Scalar::from(index as u64);
+    }
+    numerator * Option::<Scalar>::from(denominator.invert()).expect("indices are distinct")
+}
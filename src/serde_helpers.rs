@@ -2,16 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use base64ct::Encoding as _;
-use blst::min_sig as blst;
+use bincode::Options as _;
 use serde::{
-    de::{Deserializer, Error},
+    de::{DeserializeOwned, Deserializer, Error},
     ser::Serializer,
     Deserialize, Serialize,
 };
 use serde_with::{Bytes, DeserializeAs, SerializeAs};
 use std::fmt::Debug;
 
-use crate::traits::{KeyPair, SigningKey, ToFromBytes, VerifyingKey};
+use crate::error::FastCryptoError;
+#[cfg(feature = "signing")]
+use crate::traits::{FixedLength, KeyPair, ToFromBytes};
 
 fn to_custom_error<'de, D, E>(e: E) -> D::Error
 where
@@ -21,42 +23,57 @@ where
     D::Error::custom(format!("byte deserialization failed, cause by: {:?}", e))
 }
 
-pub struct BlsSignature;
+/// Define a serde_with adapter for the signatures of a blst BLS variant, which serializes to the
+/// compressed encoding and only accepts valid, compressed signatures.
+macro_rules! define_bls_signature_serde {
+    ($name:ident, $sig:ty) => {
+        pub struct $name;
 
-impl SerializeAs<blst::Signature> for BlsSignature {
-    fn serialize_as<S>(source: &blst::Signature, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        if serializer.is_human_readable() {
-            base64ct::Base64::encode_string(source.to_bytes().as_ref()).serialize(serializer)
-        } else {
-            // Serialise to Bytes
-            Bytes::serialize_as(&source.serialize(), serializer)
+        impl SerializeAs<$sig> for $name {
+            fn serialize_as<S>(source: &$sig, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                if serializer.is_human_readable() {
+                    base64ct::Base64::encode_string(source.to_bytes().as_ref())
+                        .serialize(serializer)
+                } else {
+                    // Serialise to the compressed encoding, the only one accepted when deserializing
+                    Bytes::serialize_as(&source.to_bytes(), serializer)
+                }
+            }
         }
-    }
-}
 
-impl<'de> DeserializeAs<'de, blst::Signature> for BlsSignature {
-    fn deserialize_as<D>(deserializer: D) -> Result<blst::Signature, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let bytes = if deserializer.is_human_readable() {
-            let s = String::deserialize(deserializer)?;
-            base64ct::Base64::decode_vec(&s).map_err(to_custom_error::<'de, D, _>)?
-        } else {
-            Bytes::deserialize_as(deserializer)?
-        };
-        blst::Signature::deserialize(&bytes).map_err(to_custom_error::<'de, D, _>)
-    }
+        impl<'de> DeserializeAs<'de, $sig> for $name {
+            fn deserialize_as<D>(deserializer: D) -> Result<$sig, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let bytes = if deserializer.is_human_readable() {
+                    let s = String::deserialize(deserializer)?;
+                    base64ct::Base64::decode_vec(&s).map_err(to_custom_error::<'de, D, _>)?
+                } else {
+                    Bytes::deserialize_as(deserializer)?
+                };
+                // Only accept the compressed encoding, so every signature has a unique (canonical)
+                // encoding.
+                let sig = <$sig>::uncompress(&bytes).map_err(to_custom_error::<'de, D, _>)?;
+                sig.validate(true).map_err(to_custom_error::<'de, D, _>)?;
+                Ok(sig)
+            }
+        }
+    };
 }
 
+define_bls_signature_serde!(BlsSignature, blst::min_sig::Signature);
+define_bls_signature_serde!(BlsMinPkSignature, blst::min_pk::Signature);
+
+#[cfg(feature = "signing")]
 pub fn keypair_decode_base64<T: KeyPair>(value: &str) -> Result<T, eyre::Report> {
     let bytes =
         base64ct::Base64::decode_vec(value).map_err(|e| eyre::eyre!("{}", e.to_string()))?;
-    let sk_length = <<T as KeyPair>::PrivKey as SigningKey>::LENGTH;
-    let pk_length = <<T as KeyPair>::PubKey as VerifyingKey>::LENGTH;
+    let sk_length = <<T as KeyPair>::PrivKey as FixedLength>::LENGTH;
+    let pk_length = <<T as KeyPair>::PubKey as FixedLength>::LENGTH;
     if bytes.len() != pk_length + sk_length {
         return Err(eyre::eyre!("Invalid keypair length"));
     }
@@ -68,6 +85,19 @@ pub fn keypair_decode_base64<T: KeyPair>(value: &str) -> Result<T, eyre::Report>
     Ok(kp)
 }
 
+/// Deserialize a value from its bincode encoding, as created by `bincode::serialize`. Unlike
+/// `bincode::deserialize`, which ignores any bytes after the value, this fails with
+/// [FastCryptoError::InvalidInput] if `bytes` has trailing data, so every value has a single
+/// encoding. A `Deserialize` implementation cannot detect trailing data itself, so values from
+/// untrusted sources should be decoded with this or with BCS, which also rejects trailing bytes.
+pub fn deserialize_strict<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, FastCryptoError> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+        .map_err(|_| FastCryptoError::InvalidInput)
+}
+
 pub struct Ed25519Signature;
 
 impl SerializeAs<ed25519_consensus::Signature> for Ed25519Signature {
@@ -0,0 +1,106 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! [Arbitrary] implementations for the key pairs, keys and signatures of all signature schemes, so
+//! that code using them can be property tested with [proptest]:
+//! ```rust
+//! use fastcrypto::{secp256k1::Secp256k1KeyPair, traits::KeyPair, Verifier};
+//! use proptest::prelude::*;
+//! use signature::Signer;
+//!
+//! proptest! {
+//!     fn sign_verify(kp in any::<Secp256k1KeyPair>(), message in any::<Vec<u8>>()) {
+//!         prop_assert!(kp.public().verify(&message, &kp.sign(&message)).is_ok());
+//!     }
+//! }
+//! # fn main() { sign_verify(); }
+//! ```
+//!
+//! The generated values are always valid: key pairs are generated from a random seed, and
+//! signatures are created by signing a random message with such a key pair.
+
+use crate::{
+    bls12381::{min_pk, min_sig},
+    ed25519::{Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    secp256k1::{Secp256k1KeyPair, Secp256k1PrivateKey, Secp256k1PublicKey, Secp256k1Signature},
+    traits::KeyPair,
+};
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    strategy::{BoxedStrategy, Strategy},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::fmt::Debug;
+
+fn key_pair<K: KeyPair + Debug + 'static>() -> BoxedStrategy<K> {
+    any::<[u8; 32]>()
+        .prop_map(|seed| K::generate(&mut StdRng::from_seed(seed)))
+        .boxed()
+}
+
+macro_rules! impl_arbitrary {
+    ($kp:ty, $pk:ty, $sk:ty, $sig:ty) => {
+        impl Arbitrary for $kp {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                key_pair()
+            }
+        }
+
+        impl Arbitrary for $pk {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                key_pair::<$kp>().prop_map(|kp| kp.public().clone()).boxed()
+            }
+        }
+
+        impl Arbitrary for $sk {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                key_pair::<$kp>().prop_map(|kp| kp.private()).boxed()
+            }
+        }
+
+        impl Arbitrary for $sig {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                (key_pair::<$kp>(), any::<Vec<u8>>())
+                    .prop_map(|(kp, message)| signature::Signer::sign(&kp, &message))
+                    .boxed()
+            }
+        }
+    };
+}
+
+impl_arbitrary!(
+    Ed25519KeyPair,
+    Ed25519PublicKey,
+    Ed25519PrivateKey,
+    Ed25519Signature
+);
+impl_arbitrary!(
+    Secp256k1KeyPair,
+    Secp256k1PublicKey,
+    Secp256k1PrivateKey,
+    Secp256k1Signature
+);
+impl_arbitrary!(
+    min_sig::BLS12381KeyPair,
+    min_sig::BLS12381PublicKey,
+    min_sig::BLS12381PrivateKey,
+    min_sig::BLS12381Signature
+);
+impl_arbitrary!(
+    min_pk::BLS12381KeyPair,
+    min_pk::BLS12381PublicKey,
+    min_pk::BLS12381PrivateKey,
+    min_pk::BLS12381Signature
+);
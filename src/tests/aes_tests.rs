@@ -0,0 +1,98 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    aes::{Aes256Gcm, AesKey, Nonce},
+    error::FastCryptoError,
+    traits::{Aead, FixedLength, ToFromBytes},
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+// Test cases 14 and 16 from "The Galois/Counter Mode of Operation (GCM)", McGrew and Viega.
+#[test]
+fn test_nist_vectors() {
+    let key = AesKey::new([0u8; 32]);
+    let nonce = Nonce::new([0u8; 12]);
+    let ciphertext = Aes256Gcm::encrypt(&key, &nonce, &[], &[0u8; 16]);
+    assert_eq!(
+        hex::encode(&ciphertext),
+        "cea7403d4d606b6e074ec5d3baf39d18d0d1c8a799996bf0265b98b5d48ab919"
+    );
+    assert_eq!(
+        Aes256Gcm::decrypt(&key, &nonce, &[], &ciphertext).unwrap(),
+        [0u8; 16]
+    );
+
+    let key = AesKey::from_bytes(
+        &hex::decode("feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308").unwrap(),
+    )
+    .unwrap();
+    let nonce = Nonce::from_bytes(&hex::decode("cafebabefacedbaddecaf888").unwrap()).unwrap();
+    let aad = hex::decode("feedfacedeadbeeffeedfacedeadbeefabaddad2").unwrap();
+    let plaintext = hex::decode(
+        "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+    )
+    .unwrap();
+    let ciphertext = Aes256Gcm::encrypt(&key, &nonce, &aad, &plaintext);
+    assert_eq!(
+        hex::encode(&ciphertext),
+        "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f66276fc6ece0f4e1768cddf8853bb2d551b"
+    );
+    assert_eq!(
+        Aes256Gcm::decrypt(&key, &nonce, &aad, &ciphertext).unwrap(),
+        plaintext
+    );
+}
+
+#[test]
+fn test_tampered_ciphertext() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key = AesKey::generate(&mut rng);
+    let nonce = Nonce::generate(&mut rng);
+    let aad = b"header";
+
+    let mut ciphertext = Aes256Gcm::encrypt(&key, &nonce, aad, b"Hello, world!");
+    ciphertext[0] ^= 1;
+    assert_eq!(
+        Aes256Gcm::decrypt(&key, &nonce, aad, &ciphertext),
+        Err(FastCryptoError::GeneralOpaqueError)
+    );
+}
+
+#[test]
+fn test_wrong_aad() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key = AesKey::generate(&mut rng);
+    let nonce = Nonce::generate(&mut rng);
+
+    let ciphertext = Aes256Gcm::encrypt(&key, &nonce, b"header", b"Hello, world!");
+    assert!(Aes256Gcm::decrypt(&key, &nonce, b"other header", &ciphertext).is_err());
+}
+
+#[test]
+fn test_invalid_lengths() {
+    assert!(AesKey::<32>::from_bytes(&[0u8; 16]).is_err());
+    assert!(Nonce::<12>::from_bytes(&[0u8; 16]).is_err());
+
+    let key = AesKey::new([0u8; 32]);
+    let nonce = Nonce::new([0u8; 12]);
+    assert_eq!(
+        Aes256Gcm::decrypt(&key, &nonce, &[], &[0u8; 15]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_fixed_lengths() {
+    assert_eq!(AesKey::<32>::LENGTH, 32);
+    assert_eq!(Nonce::<12>::LENGTH, 12);
+
+    let err = AesKey::<32>::from_bytes(&[0u8; 16]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InputLengthWrong {
+            expected: 32,
+            got: 16
+        })
+    );
+}
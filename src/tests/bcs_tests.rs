@@ -0,0 +1,238 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::{
+        min_pk, BLS12381AggregateSignature, BLS12381KeyPair, BLS12381PublicKeyBytes,
+        BLS12381Signature,
+    },
+    ed25519::{
+        Ed25519AggregateSignature, Ed25519KeyPair, Ed25519PublicKey, Ed25519PublicKeyBytes,
+        Ed25519Signature,
+    },
+    error::FastCryptoError,
+    multisig::{MemberPublicKey, MemberSignature, MultiSig, MultiSigPublicKey},
+    secp256k1::{Secp256k1KeyPair, Secp256k1PublicKeyBytes},
+    serde_helpers::deserialize_strict,
+    traits::{AggregateAuthenticator, KeyPair},
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+use serde::{de::DeserializeOwned, Serialize};
+use signature::Signer;
+
+const MESSAGE: &[u8] = b"Hello, world!";
+
+/// Round trip `value` through BCS and check that the decoded value encodes to the exact same bytes.
+fn bcs_round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let bytes = bcs::to_bytes(value).unwrap();
+    let decoded: T = bcs::from_bytes(&bytes).unwrap();
+    assert_eq!(bcs::to_bytes(&decoded).unwrap(), bytes);
+
+    // BCS rejects trailing bytes.
+    let mut extended = bytes;
+    extended.push(0);
+    assert!(bcs::from_bytes::<T>(&extended).is_err());
+
+    decoded
+}
+
+#[test]
+fn test_ed25519_bcs_round_trip() {
+    let kp = Ed25519KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let sig = kp.sign(MESSAGE);
+
+    assert_eq!(&bcs_round_trip(kp.public()), kp.public());
+    assert_eq!(bcs_round_trip(kp.private()).as_ref(), kp.private().as_ref());
+    assert_eq!(bcs_round_trip(&sig), sig);
+
+    let pk_bytes = Ed25519PublicKeyBytes::from(kp.public());
+    assert_eq!(bcs_round_trip(&pk_bytes), pk_bytes);
+
+    let aggregate = Ed25519AggregateSignature::aggregate(vec![sig.clone(), sig]).unwrap();
+    assert_eq!(bcs_round_trip(&aggregate), aggregate);
+}
+
+#[test]
+fn test_secp256k1_bcs_round_trip() {
+    let kp = Secp256k1KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let sig = kp.sign(MESSAGE);
+
+    assert_eq!(&bcs_round_trip(kp.public()), kp.public());
+    assert_eq!(bcs_round_trip(kp.private()).as_ref(), kp.private().as_ref());
+    assert_eq!(bcs_round_trip(&sig), sig);
+
+    let pk_bytes = Secp256k1PublicKeyBytes::from(kp.public());
+    assert_eq!(bcs_round_trip(&pk_bytes), pk_bytes);
+}
+
+#[test]
+fn test_bls12381_bcs_round_trip() {
+    let kp = BLS12381KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let sig = kp.sign(MESSAGE);
+
+    assert_eq!(&bcs_round_trip(kp.public()), kp.public());
+    assert_eq!(bcs_round_trip(kp.private()).as_ref(), kp.private().as_ref());
+    assert_eq!(bcs_round_trip(&sig), sig);
+
+    let pk_bytes = BLS12381PublicKeyBytes::from(kp.public());
+    assert_eq!(bcs_round_trip(&pk_bytes), pk_bytes);
+
+    let aggregate = BLS12381AggregateSignature::aggregate(vec![sig.clone(), sig]).unwrap();
+    assert_eq!(bcs_round_trip(&aggregate).as_ref(), aggregate.as_ref());
+
+    let empty = BLS12381AggregateSignature::default();
+    assert_eq!(bcs_round_trip(&empty).sig, None);
+}
+
+#[test]
+fn test_multisig_bcs_round_trip() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let ed25519 = Ed25519KeyPair::generate(&mut rng);
+    let secp256k1 = Secp256k1KeyPair::generate(&mut rng);
+
+    let pk = MultiSigPublicKey::new(
+        vec![
+            MemberPublicKey::Ed25519(ed25519.public().clone()),
+            MemberPublicKey::Secp256k1(secp256k1.public().clone()),
+        ],
+        vec![1, 2],
+        3,
+    )
+    .unwrap();
+    let multisig = MultiSig::new(
+        vec![
+            MemberSignature::Ed25519(ed25519.sign(MESSAGE)),
+            MemberSignature::Secp256k1(secp256k1.sign(MESSAGE)),
+        ],
+        0b11,
+    );
+
+    assert_eq!(bcs_round_trip(&pk), pk);
+    let decoded = bcs_round_trip(&multisig);
+    assert_eq!(decoded, multisig);
+    assert!(decoded.verify(&pk, MESSAGE).is_ok());
+}
+
+#[test]
+fn test_signatures_are_raw_bytes_in_bcs() {
+    let mut rng = StdRng::from_seed([0; 32]);
+
+    // A ULEB128 length prefix followed by the signature bytes.
+    let sig = Ed25519KeyPair::generate(&mut rng).sign(MESSAGE);
+    assert_eq!(
+        bcs::to_bytes(&sig).unwrap(),
+        [&[64u8][..], sig.as_ref()].concat()
+    );
+
+    let sig = Secp256k1KeyPair::generate(&mut rng).sign(MESSAGE);
+    assert_eq!(
+        bcs::to_bytes(&sig).unwrap(),
+        [&[65u8][..], sig.as_ref()].concat()
+    );
+
+    let sig = BLS12381KeyPair::generate(&mut rng).sign(MESSAGE);
+    assert_eq!(
+        bcs::to_bytes(&sig).unwrap(),
+        [&[48u8][..], sig.as_ref()].concat()
+    );
+}
+
+#[test]
+fn test_bls12381_uncompressed_signature_rejected_in_bcs() {
+    let sig: BLS12381Signature =
+        BLS12381KeyPair::generate(&mut StdRng::from_seed([0; 32])).sign(MESSAGE);
+
+    // The uncompressed encoding of a valid signature is not canonical and must be rejected.
+    let uncompressed = bcs::to_bytes(&sig.to_uncompressed().to_vec()).unwrap();
+    assert!(bcs::from_bytes::<BLS12381Signature>(&uncompressed).is_err());
+
+    let compressed = bcs::to_bytes(&sig.as_ref().to_vec()).unwrap();
+    assert_eq!(
+        bcs::from_bytes::<BLS12381Signature>(&compressed).unwrap(),
+        sig
+    );
+}
+
+/// Check that `sig` can not be decoded with trailing data: neither with a byte appended to the
+/// signature bytes, nor with a byte appended to the whole encoding (for BCS and
+/// [deserialize_strict]), nor from JSON with an extra field or element.
+fn assert_rejects_trailing_data<T: Serialize + DeserializeOwned + AsRef<[u8]>>(sig: &T) {
+    let extended = [sig.as_ref(), &[0u8]].concat();
+    assert!(bcs::from_bytes::<T>(&bcs::to_bytes(&extended).unwrap()).is_err());
+    assert!(deserialize_strict::<T>(&bincode::serialize(&extended).unwrap()).is_err());
+
+    let mut bytes = bincode::serialize(sig).unwrap();
+    assert_eq!(
+        deserialize_strict::<T>(&bytes).unwrap().as_ref(),
+        sig.as_ref()
+    );
+    bytes.push(0);
+    assert_eq!(
+        deserialize_strict::<T>(&bytes).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    let mut json = serde_json::to_value(sig).unwrap();
+    match &mut json {
+        serde_json::Value::Object(fields) => {
+            fields.insert("extra".to_string(), serde_json::Value::from(0));
+        }
+        serde_json::Value::Array(elements) => elements.push(serde_json::Value::from(0)),
+        _ => panic!("Unexpected JSON encoding of a signature"),
+    }
+    assert!(serde_json::from_value::<T>(json).is_err());
+}
+
+#[test]
+fn test_signatures_reject_trailing_data() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    assert_rejects_trailing_data(&Ed25519KeyPair::generate(&mut rng).sign(MESSAGE));
+    assert_rejects_trailing_data(&Secp256k1KeyPair::generate(&mut rng).sign(MESSAGE));
+    assert_rejects_trailing_data(&min_pk::BLS12381KeyPair::generate(&mut rng).sign(MESSAGE));
+
+    let sig = BLS12381KeyPair::generate(&mut rng).sign(MESSAGE);
+    assert_rejects_trailing_data(&sig);
+
+    let aggregate = BLS12381AggregateSignature::aggregate(vec![sig]).unwrap();
+    let mut json = serde_json::to_value(&aggregate).unwrap();
+    json.as_object_mut()
+        .unwrap()
+        .insert("extra".to_string(), serde_json::Value::from(0));
+    assert!(serde_json::from_value::<BLS12381AggregateSignature>(json).is_err());
+}
+
+#[test]
+fn test_deserialize_strict() {
+    type KeyAndSignature = (Ed25519PublicKey, Ed25519Signature);
+
+    let kp = Ed25519KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let value = (kp.public().clone(), kp.sign(MESSAGE));
+    let bytes = bincode::serialize(&value).unwrap();
+    assert_eq!(
+        deserialize_strict::<KeyAndSignature>(&bytes).unwrap(),
+        value
+    );
+
+    // bincode::deserialize ignores trailing bytes, deserialize_strict does not.
+    let extended = [&bytes[..], &[0]].concat();
+    assert_eq!(
+        bincode::deserialize::<KeyAndSignature>(&extended).unwrap(),
+        value
+    );
+    assert_eq!(
+        deserialize_strict::<KeyAndSignature>(&extended).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    // Truncated input and invalid values are rejected.
+    let truncated = &bytes[..bytes.len() - 1];
+    assert_eq!(
+        deserialize_strict::<KeyAndSignature>(truncated).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+    let short_key = bincode::serialize(&[0u8; 31][..]).unwrap();
+    assert_eq!(
+        deserialize_strict::<Ed25519PublicKey>(&short_key).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
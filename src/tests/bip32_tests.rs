@@ -0,0 +1,232 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::FastCryptoError,
+    hash::{HashFunction, Sha256},
+    secp256k1::bip32::{
+        ExtendedPrivateKey, HARDENED_OFFSET, MAINNET_PRIVATE_VERSION, TESTNET_PRIVATE_VERSION,
+    },
+    traits::KeyPair,
+};
+use signature::{Signer, Verifier};
+
+const H: u32 = HARDENED_OFFSET;
+
+/// The test vectors 1 to 3 from BIP-32: the seed, and the child numbers along a path with the
+/// serialized keys from the master key to the end of the path.
+fn test_vectors() -> Vec<(&'static str, Vec<u32>, Vec<&'static str>)> {
+    vec![
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            vec![H, 1, H + 2, 2, 1000000000],
+            vec![
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+                "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+                "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+                "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+                "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+            ],
+        ),
+        (
+            "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+            vec![0, H + 2147483647, 1, H + 2147483646, 2],
+            vec![
+                "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+                "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+                "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+                "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+                "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+                "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+            ],
+        ),
+        // Leading zeros of the private key are retained.
+        (
+            "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8e1e7d1457df2e5a3c51c73235be",
+            vec![H],
+            vec![
+                "xprv9s21ZrQH143K31iXnh3YSfTEHHBU4M5ccfMjYmp2KA4UYPSWPc3UwKpc4vpdEcFwC2n6fGHKsnouageaaL5dKPLXVuBThPjBqdSpNNTkEMw",
+                "xprv9vALCti2BWcDbu4DaBUtAVyRmwRStR8ZwNMZc5nzULcgL5wjmbLp6H1aSa2PRc8TLvu86dyKrJovwBBsQ6f7jQZyQoHM4LQdmecygrLUZWF",
+            ],
+        ),
+    ]
+}
+
+/// The Base58Check encoding of `payload`.
+fn encode_base58_check(payload: &[u8]) -> String {
+    let checksum = Sha256::digest(Sha256::digest(payload));
+    bs58::encode([payload, &checksum.0[..4]].concat()).into_string()
+}
+
+/// The payload of a Base58Check encoded string, without the checksum.
+fn decode_base58_check(s: &str) -> Vec<u8> {
+    let mut bytes = bs58::decode(s).into_vec().unwrap();
+    bytes.truncate(bytes.len() - 4);
+    bytes
+}
+
+#[test]
+fn test_bip32_test_vectors() {
+    for (seed, path, expected) in test_vectors() {
+        let master = ExtendedPrivateKey::from_seed(&hex::decode(seed).unwrap()).unwrap();
+        assert_eq!(master.to_base58().as_str(), expected[0]);
+
+        // Derive the keys along the path one by one, from the master key and from the parsed
+        // serialization of the parent.
+        let mut key = master.clone();
+        for (i, child_number) in path.iter().enumerate() {
+            let parent = ExtendedPrivateKey::from_base58(expected[i]).unwrap();
+            let child = parent.derive_child(*child_number).unwrap();
+            assert_eq!(child.to_base58().as_str(), expected[i + 1]);
+            assert_eq!(child.depth() as usize, i + 1);
+            assert_eq!(child.child_number(), *child_number);
+            assert_eq!(child.parent_fingerprint(), parent.fingerprint());
+
+            key = key.derive_child(*child_number).unwrap();
+            assert_eq!(key.to_base58().as_str(), expected[i + 1]);
+        }
+        assert_eq!(
+            master.derive_path(&path).unwrap().to_base58().as_str(),
+            expected[path.len()]
+        );
+        assert_eq!(
+            master.derive_path(&[]).unwrap().to_base58(),
+            master.to_base58()
+        );
+    }
+}
+
+#[test]
+fn test_master_key() {
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let master = ExtendedPrivateKey::from_seed(&seed).unwrap();
+    assert_eq!(master.version(), MAINNET_PRIVATE_VERSION);
+    assert_eq!(master.depth(), 0);
+    assert_eq!(master.child_number(), 0);
+    assert_eq!(master.parent_fingerprint(), [0; 4]);
+    assert_eq!(hex::encode(master.fingerprint()), "3442193e");
+    assert_eq!(
+        hex::encode(master.chain_code()),
+        "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508"
+    );
+    assert_eq!(
+        hex::encode(master.private_key()),
+        "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+    );
+    assert_eq!(
+        hex::encode(master.public_key()),
+        "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2"
+    );
+
+    // The key pair signs with the derived key.
+    let kp = master.key_pair();
+    assert_eq!(kp.public(), &master.public_key());
+    let message: &[u8] = b"Hello, world!";
+    assert!(master
+        .public_key()
+        .verify(message, &kp.sign(message))
+        .is_ok());
+
+    // Seeds have 16 to 64 bytes.
+    assert!(ExtendedPrivateKey::from_seed(&[1; 16]).is_ok());
+    assert!(ExtendedPrivateKey::from_seed(&[1; 64]).is_ok());
+    assert_eq!(
+        ExtendedPrivateKey::from_seed(&[1; 15]).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        ExtendedPrivateKey::from_seed(&[1; 65]).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_hardened_and_normal_children() {
+    let master = ExtendedPrivateKey::from_seed(&[7; 32]).unwrap();
+    let normal = master.derive_child(5).unwrap();
+    let hardened = master.derive_child(5 + H).unwrap();
+    assert_ne!(normal.public_key(), hardened.public_key());
+    assert_ne!(normal.chain_code(), hardened.chain_code());
+    assert_eq!(normal.parent_fingerprint(), hardened.parent_fingerprint());
+
+    // Derivation is deterministic.
+    assert_eq!(
+        master.derive_child(5).unwrap().to_base58(),
+        normal.to_base58()
+    );
+
+    // The depth is at most 255.
+    let mut key = master;
+    for _ in 0..255 {
+        key = key.derive_child(0).unwrap();
+    }
+    assert_eq!(key.depth(), 255);
+    assert_eq!(
+        key.derive_child(0).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_testnet_version() {
+    let (_, _, expected) = test_vectors().remove(0);
+    let mut payload = decode_base58_check(expected[1]);
+    payload[..4].copy_from_slice(&TESTNET_PRIVATE_VERSION);
+    let tprv = encode_base58_check(&payload);
+    assert!(tprv.starts_with("tprv"));
+
+    // The version is kept in serializations and children.
+    let key = ExtendedPrivateKey::from_base58(&tprv).unwrap();
+    assert_eq!(key.version(), TESTNET_PRIVATE_VERSION);
+    assert_eq!(key.to_base58().as_str(), tprv);
+    assert!(key.derive_child(1).unwrap().to_base58().starts_with("tprv"));
+}
+
+#[test]
+fn test_invalid_serializations() {
+    let (_, _, expected) = test_vectors().remove(0);
+    let valid = decode_base58_check(expected[0]);
+    assert!(ExtendedPrivateKey::from_base58(&encode_base58_check(&valid)).is_ok());
+
+    let modified = |position: usize, bytes: &[u8]| {
+        let mut payload = valid.clone();
+        payload[position..position + bytes.len()].copy_from_slice(bytes);
+        ExtendedPrivateKey::from_base58(&encode_base58_check(&payload)).err()
+    };
+
+    // Extended public keys and unknown versions are rejected.
+    assert_eq!(
+        modified(0, &[0x04, 0x88, 0xb2, 0x1e]),
+        Some(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(modified(0, &[0; 4]), Some(FastCryptoError::InvalidInput));
+
+    // A master key has no parent fingerprint and child number.
+    assert_eq!(modified(5, &[1]), Some(FastCryptoError::InvalidInput));
+    assert_eq!(modified(12, &[1]), Some(FastCryptoError::InvalidInput));
+    assert!(modified(4, &[1]).is_none());
+
+    // The key must be prefixed by a zero byte and be in the range from 1 to n - 1.
+    assert_eq!(modified(45, &[2]), Some(FastCryptoError::InvalidInput));
+    assert_eq!(modified(46, &[0; 32]), Some(FastCryptoError::InvalidInput));
+    let order =
+        hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+    assert_eq!(modified(46, &order), Some(FastCryptoError::InvalidInput));
+
+    // Wrong checksums and lengths, and invalid Base58.
+    let mut wrong_checksum = bs58::decode(expected[0]).into_vec().unwrap();
+    wrong_checksum[81] ^= 1;
+    for invalid in [
+        bs58::encode(wrong_checksum).into_string(),
+        encode_base58_check(&valid[..77]),
+        encode_base58_check(&[&valid[..], &[0][..]].concat()),
+        expected[0].replace('Z', "0"),
+        String::new(),
+    ] {
+        assert_eq!(
+            ExtendedPrivateKey::from_base58(&invalid).err(),
+            Some(FastCryptoError::InvalidInput)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::{BLS12381KeyPair, BLS12381PrivateKey, BLS12381Signature, DST},
+    groups::{
+        bls12381::{
+            hash_to_g1, hash_to_g2, multi_pairing, pairing, G1Element, G2Element, GTElement,
+            Scalar, G1_HASH_TO_CURVE_SUITE, G2_HASH_TO_CURVE_SUITE,
+        },
+        GroupElement, Scalar as _,
+    },
+    traits::ToFromBytes,
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+use signature::Signer;
+
+// Test vectors from RFC 9380, appendices J.9.1 and J.10.1, in compressed form.
+const G1_DST: &str = "QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+const G2_DST: &str = "QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+#[test]
+fn test_hash_to_g1_vectors() {
+    assert!(G1_DST.ends_with(G1_HASH_TO_CURVE_SUITE));
+    let vectors: [(&[u8], &str); 2] = [
+        (b"", "852926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1"),
+        (b"abc", "83567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903"),
+    ];
+    for (msg, expected) in vectors {
+        let point = hash_to_g1(msg, G1_DST.as_bytes());
+        assert_eq!(hex::encode(point.compress()), expected);
+    }
+}
+
+#[test]
+fn test_hash_to_g2_vectors() {
+    assert!(G2_DST.ends_with(G2_HASH_TO_CURVE_SUITE));
+    let vectors: [(&[u8], &str); 2] = [
+        (b"", "a5cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a"),
+        (b"abc", "939cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd802c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6"),
+    ];
+    for (msg, expected) in vectors {
+        let point = hash_to_g2(msg, G2_DST.as_bytes());
+        assert_eq!(hex::encode(point.compress()), expected);
+    }
+}
+
+#[test]
+fn test_hash_to_g1_matches_signing() {
+    // A BLS signature with the secret key 1 is the hash of the message to G1.
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let sk = BLS12381PrivateKey::from_bytes(&one).unwrap();
+    let kp = BLS12381KeyPair::from(sk);
+    let msg = b"Hello, world!";
+    let sig: BLS12381Signature = kp.sign(msg);
+    assert_eq!(sig.as_ref(), hash_to_g1(msg, DST).compress());
+    assert_ne!(hash_to_g1(msg, DST), hash_to_g1(msg, G1_DST.as_bytes()));
+}
+
+#[test]
+fn test_group_operations() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let a = Scalar::rand(&mut rng);
+    let b = Scalar::rand(&mut rng);
+
+    let g1 = G1Element::generator();
+    assert_eq!(g1 * (a + b), g1 * a + g1 * b);
+    assert_eq!(g1 * a - g1 * a, G1Element::zero());
+    assert_eq!(g1 * Scalar::from(2), g1 + g1);
+    assert_eq!(-(g1 * a), g1 * -a);
+
+    let g2 = G2Element::generator();
+    assert_eq!(g2 * (a * b), (g2 * a) * b);
+    assert_eq!(g2 * (a - a), G2Element::zero());
+}
+
+#[test]
+fn test_compression() {
+    let p = hash_to_g1(b"abc", G1_DST.as_bytes());
+    assert_eq!(G1Element::decompress(&p.compress()).unwrap(), p);
+    let q = hash_to_g2(b"abc", G2_DST.as_bytes());
+    assert_eq!(G2Element::decompress(&q.compress()).unwrap(), q);
+
+    // The compressed encoding of the point with x = 4 is on the curve but not in G1.
+    let mut not_in_subgroup = [0u8; 48];
+    not_in_subgroup[0] = 0x80;
+    not_in_subgroup[47] = 0x04;
+    assert!(G1Element::decompress(&not_in_subgroup).is_err());
+}
+
+#[test]
+fn test_pairing_bilinearity() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (a, b) = (Scalar::rand(&mut rng), Scalar::rand(&mut rng));
+    let p = hash_to_g1(b"P", G1_DST.as_bytes());
+    let q = hash_to_g2(b"Q", G2_DST.as_bytes());
+
+    let e = pairing(&p, &q);
+    assert_eq!(pairing(&(p * a), &(q * b)), e * (a * b));
+    assert_eq!(pairing(&(p * a), &q), pairing(&p, &(q * a)));
+    assert_eq!(pairing(&(p + p), &q), e + e);
+    assert_eq!(pairing(&-p, &q), -e);
+    assert_ne!(e, GTElement::zero());
+
+    // The pairing with the identity is the identity.
+    assert_eq!(pairing(&G1Element::zero(), &q), GTElement::zero());
+    assert_eq!(pairing(&p, &G2Element::zero()), GTElement::zero());
+
+    let g = GTElement::generator();
+    assert_eq!(g, pairing(&G1Element::generator(), &G2Element::generator()));
+    assert_eq!(g * a + g * b, g * (a + b));
+    assert_eq!(g * a - g * a, GTElement::zero());
+    assert_eq!(g * Scalar::from(1), g);
+    assert_eq!(g * Scalar::zero(), GTElement::zero());
+}
+
+#[test]
+fn test_multi_pairing() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let pairs: Vec<_> = (0..4)
+        .map(|_| {
+            (
+                G1Element::generator() * Scalar::rand(&mut rng),
+                G2Element::generator() * Scalar::rand(&mut rng),
+            )
+        })
+        .collect();
+    let sum = pairs
+        .iter()
+        .fold(GTElement::zero(), |sum, (p, q)| sum + pairing(p, q));
+    assert_eq!(multi_pairing(&pairs), sum);
+    assert_eq!(multi_pairing(&[]), GTElement::zero());
+
+    // A BLS signature verifies with e(sig, g2) - e(H(m), pk) = 0.
+    let sk = Scalar::rand(&mut rng);
+    let msg = hash_to_g1(b"Hello, world!", DST);
+    let (sig, pk) = (msg * sk, G2Element::generator() * sk);
+    assert_eq!(
+        multi_pairing(&[(sig, G2Element::generator()), (-msg, pk)]),
+        GTElement::zero()
+    );
+    assert_ne!(
+        multi_pairing(&[(sig, G2Element::generator()), (msg, pk)]),
+        GTElement::zero()
+    );
+}
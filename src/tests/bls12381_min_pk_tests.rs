@@ -0,0 +1,144 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::{
+        min_pk::{
+            BLS12381AggregatePublicKey, BLS12381AggregateSignature, BLS12381KeyPair,
+            BLS12381PrivateKey, BLS12381PublicKey, BLS12381Signature, BLS_PUBLIC_KEY_LENGTH,
+            BLS_SIGNATURE_LENGTH,
+        },
+        min_sig,
+    },
+    error::FastCryptoError,
+    groups::{bls12381::Scalar, Scalar as _},
+    traits::{AggregateAuthenticator, EncodeDecodeBase64, KeyPair, ToFromBytes, VerifyingKey},
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+use signature::{Signer, Verifier};
+
+fn keys() -> Vec<BLS12381KeyPair> {
+    BLS12381KeyPair::generate_many(4, &mut StdRng::from_seed([0; 32]))
+}
+
+#[test]
+fn test_sign_verify_and_lengths() {
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let signature = kp.sign(message);
+    assert!(kp.public().verify(message, &signature).is_ok());
+    assert!(kp.public().verify(b"Other message", &signature).is_err());
+
+    assert_eq!(BLS_PUBLIC_KEY_LENGTH, 48);
+    assert_eq!(BLS_SIGNATURE_LENGTH, 96);
+    assert_eq!(kp.public().as_ref().len(), 48);
+    assert_eq!(signature.as_ref().len(), 96);
+
+    // The same private key gives a min-sig key pair with the sizes swapped.
+    let min_sig_kp = min_sig::BLS12381KeyPair::from(
+        min_sig::BLS12381PrivateKey::from_bytes(kp.private().as_ref()).unwrap(),
+    );
+    let min_sig_signature = min_sig_kp.sign(message);
+    assert!(min_sig_kp
+        .public()
+        .verify(message, &min_sig_signature)
+        .is_ok());
+    assert_eq!(min_sig_kp.public().as_ref().len(), 96);
+    assert_eq!(min_sig_signature.as_ref().len(), 48);
+}
+
+#[test]
+fn test_encodings() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    assert_eq!(
+        <BLS12381Signature as ToFromBytes>::from_bytes(signature.as_ref()).unwrap(),
+        signature
+    );
+    assert_eq!(
+        BLS12381Signature::from_uncompressed(&signature.to_uncompressed()).unwrap(),
+        signature
+    );
+    assert_eq!(
+        &BLS12381PublicKey::from_uncompressed(&kp.public().to_uncompressed()).unwrap(),
+        kp.public()
+    );
+    let decoded = BLS12381KeyPair::decode_base64(&kp.encode_base64()).unwrap();
+    assert_eq!(decoded.public(), kp.public());
+    assert!(BLS12381PrivateKey::from_bytes(kp.private().as_ref()).is_ok());
+
+    let bytes = bincode::serialize(&signature).unwrap();
+    assert_eq!(
+        bincode::deserialize::<BLS12381Signature>(&bytes).unwrap(),
+        signature
+    );
+    let json = serde_json::to_string(&signature).unwrap();
+    assert_eq!(
+        serde_json::from_str::<BLS12381Signature>(&json).unwrap(),
+        signature
+    );
+}
+
+#[test]
+fn test_aggregation() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let message = b"Hello, world!";
+
+    let signature =
+        BLS12381AggregateSignature::aggregate(kps.iter().map(|kp| kp.sign(message)).collect())
+            .unwrap();
+    assert_eq!(signature.as_ref().len(), BLS_SIGNATURE_LENGTH);
+    assert!(signature.verify(&pks, message).is_ok());
+    assert!(signature.verify(&pks[1..], message).is_err());
+
+    let mut aggregate_pk = BLS12381AggregatePublicKey::new(&pks).unwrap();
+    assert!(aggregate_pk.verify(message, &signature).is_ok());
+    aggregate_pk.remove(&pks[0]).unwrap();
+    assert_eq!(
+        aggregate_pk.verify(message, &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    let signature =
+        BLS12381AggregateSignature::aggregate(kps[1..].iter().map(|kp| kp.sign(message)).collect())
+            .unwrap();
+    assert!(aggregate_pk.verify(message, &signature).is_ok());
+
+    // Aggregates of signatures on different messages.
+    let messages: Vec<&[u8]> = vec![b"one".as_slice(), b"two", b"three", b"four"];
+    let signature = BLS12381AggregateSignature::aggregate(
+        kps.iter()
+            .zip(&messages)
+            .map(|(kp, message)| kp.sign(message))
+            .collect(),
+    )
+    .unwrap();
+    assert!(signature.verify_different_msgs(&pks, &messages).is_ok());
+}
+
+#[test]
+fn test_batch_verification() {
+    let kps = keys();
+    let message = b"Hello, world!";
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let mut signatures: Vec<_> = kps.iter().map(|kp| kp.sign(message)).collect();
+    assert!(BLS12381PublicKey::verify_batch_empty_fail(message, &pks, &signatures).is_ok());
+
+    signatures.swap(0, 1);
+    assert!(BLS12381PublicKey::verify_batch_empty_fail(message, &pks, &signatures).is_err());
+}
+
+#[test]
+fn test_rerandomize() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let r = Scalar::rand(&mut rng);
+
+    let signature = kp.sign(message).rerandomize(&r).unwrap();
+    let pk = kp.public().blind(&r).unwrap();
+    assert!(pk.verify(message, &signature).is_ok());
+    assert!(kp.public().verify(message, &signature).is_err());
+    assert_eq!(BLS12381PublicKey::from_bytes(pk.as_ref()).unwrap(), pk);
+}
@@ -3,12 +3,14 @@
 use super::*;
 use crate::{
     bls12381::{
-        BLS12381AggregateSignature, BLS12381KeyPair, BLS12381PrivateKey, BLS12381PublicKey,
-        BLS12381PublicKeyBytes, BLS12381Signature,
+        BLS12381AggregatePublicKey, BLS12381AggregateSignature, BLS12381KeyPair,
+        BLS12381PrivateKey, BLS12381PublicKey, BLS12381PublicKeyBytes, BLS12381Signature,
+        BLS_PRIVATE_KEY_LENGTH, BLS_PUBLIC_KEY_LENGTH, BLS_SIGNATURE_LENGTH, DST,
     },
+    error::FastCryptoError,
     hkdf::hkdf_generate_from_ikm,
     traits::{
-        AggregateAuthenticator, EncodeDecodeBase64, KeyPair, SigningKey, ToFromBytes, VerifyingKey,
+        AggregateAuthenticator, EncodeDecodeBase64, FixedLength, KeyPair, ToFromBytes, VerifyingKey,
     },
 };
 use rand::{rngs::StdRng, SeedableRng as _};
@@ -16,10 +18,7 @@ use sha3::Sha3_256;
 use signature::{Signer, Verifier};
 
 pub fn keys() -> Vec<BLS12381KeyPair> {
-    let mut rng = StdRng::from_seed([0; 32]);
-    (0..4)
-        .map(|_| BLS12381KeyPair::generate(&mut rng))
-        .collect()
+    BLS12381KeyPair::generate_many(4, &mut StdRng::from_seed([0; 32]))
 }
 
 #[test]
@@ -42,6 +41,34 @@ fn import_export_secret_key() {
     assert_eq!(import.unwrap().as_ref(), secret_key.as_ref());
 }
 
+#[test]
+fn test_into_and_from_parts() {
+    let mut keys = keys();
+    let other = keys.pop().unwrap();
+    let kp = keys.pop().unwrap();
+    let public = kp.public().clone();
+
+    let (pk, sk) = kp.into_parts();
+    assert_eq!(pk, public);
+    let kp = BLS12381KeyPair::from_parts(pk, sk).unwrap();
+    assert_eq!(kp.public(), &public);
+
+    // The public key must belong to the private key.
+    let (_, sk) = kp.into_parts();
+    assert_eq!(
+        BLS12381KeyPair::from_parts(other.public().clone(), sk).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_keys_reproducible_from_seed() {
+    let kp1 = BLS12381KeyPair::generate(&mut StdRng::from_seed([7; 32]));
+    let kp2 = BLS12381KeyPair::generate(&mut StdRng::from_seed([7; 32]));
+    assert_eq!(kp1.public(), kp2.public());
+    assert_eq!(kp1.private().as_ref(), kp2.private().as_ref());
+}
+
 #[test]
 fn to_from_bytes_signature() {
     let kpref = keys().pop().unwrap();
@@ -51,6 +78,89 @@ fn to_from_bytes_signature() {
     assert_eq!(rebuilt_sig, signature);
 }
 
+#[test]
+fn test_try_from_bytes() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    let pk: BLS12381PublicKey = kp.public().as_ref().try_into().unwrap();
+    assert_eq!(&pk, kp.public());
+    let sig: BLS12381Signature = signature.as_ref().try_into().unwrap();
+    assert_eq!(sig, signature);
+    let sk_bytes = kp.private().as_ref().to_vec();
+    let sk: BLS12381PrivateKey = sk_bytes.as_slice().try_into().unwrap();
+    assert_eq!(sk.as_ref(), &sk_bytes[..]);
+
+    // Wrong lengths are rejected.
+    assert!(BLS12381PublicKey::try_from(&pk.as_ref()[1..]).is_err());
+    assert!(BLS12381Signature::try_from(&sig.as_ref()[1..]).is_err());
+    assert!(BLS12381PrivateKey::try_from(&sk_bytes[1..]).is_err());
+}
+
+#[test]
+fn test_fixed_lengths() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+    assert_eq!(kp.public().as_ref().len(), BLS12381PublicKey::LENGTH);
+    assert_eq!(signature.as_ref().len(), BLS12381Signature::LENGTH);
+    assert_eq!(kp.private().as_ref().len(), BLS12381PrivateKey::LENGTH);
+
+    let err = BLS12381PublicKey::from_bytes(&[0u8; 1]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InputLengthWrong {
+            expected: BLS12381PublicKey::LENGTH,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn test_error_variants() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    // A well-formed signature on another message.
+    let err = kp.public().verify(b"Bad message!", &signature).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InvalidSignature)
+    );
+
+    // A truncated signature.
+    let err = <BLS12381Signature as ToFromBytes>::from_bytes(&signature.as_ref()[1..]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InputLengthWrong {
+            expected: BLS12381Signature::LENGTH,
+            got: BLS12381Signature::LENGTH - 1
+        })
+    );
+}
+
+#[test]
+fn test_display_from_str() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    let pk = format!("{}", kp.public())
+        .parse::<BLS12381PublicKey>()
+        .unwrap();
+    assert_eq!(&pk, kp.public());
+    let sig = format!("{}", signature)
+        .parse::<BLS12381Signature>()
+        .unwrap();
+    assert_eq!(sig, signature);
+    let sk = kp.private();
+    let parsed_sk = sk.encode_base64().parse::<BLS12381PrivateKey>().unwrap();
+    assert_eq!(parsed_sk.as_ref(), sk.as_ref());
+
+    assert_eq!(
+        "not base64!".parse::<BLS12381PublicKey>(),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
 #[test]
 fn verify_valid_signature() {
     // Get a keypair.
@@ -60,10 +170,10 @@ fn verify_valid_signature() {
     let message: &[u8] = b"Hello, world!";
     let digest = message.digest();
 
-    let signature = kp.sign(&digest.0);
+    let signature = kp.sign(digest.as_ref());
 
     // Verify the signature.
-    assert!(kp.public().verify(&digest.0, &signature).is_ok());
+    assert!(kp.public().verify(digest.as_ref(), &signature).is_ok());
 }
 
 #[test]
@@ -75,13 +185,13 @@ fn verify_invalid_signature() {
     let message: &[u8] = b"Hello, world!";
     let digest = message.digest();
 
-    let signature = kp.sign(&digest.0);
+    let signature = kp.sign(digest.as_ref());
 
     // Verify the signature.
     let bad_message: &[u8] = b"Bad message!";
     let digest = bad_message.digest();
 
-    assert!(kp.public().verify(&digest.0, &signature).is_err());
+    assert!(kp.public().verify(digest.as_ref(), &signature).is_err());
 }
 
 fn signature_test_inputs() -> (Vec<u8>, Vec<BLS12381PublicKey>, Vec<BLS12381Signature>) {
@@ -92,7 +202,7 @@ fn signature_test_inputs() -> (Vec<u8>, Vec<BLS12381PublicKey>, Vec<BLS12381Sign
         .into_iter()
         .take(3)
         .map(|kp| {
-            let sig = kp.sign(&digest.0);
+            let sig = kp.sign(digest.as_ref());
             (kp.public().clone(), sig)
         })
         .unzip();
@@ -108,6 +218,20 @@ fn verify_valid_batch() {
     assert!(res.is_ok(), "{:?}", res);
 }
 
+#[test]
+fn verify_valid_batch_with_seeded_rng() {
+    let (digest, pubkeys, signatures) = signature_test_inputs();
+    let mut rng = StdRng::from_seed([7; 32]);
+
+    let res = BLS12381PublicKey::verify_batch_empty_fail_with_rng(
+        &digest[..],
+        &pubkeys,
+        &signatures,
+        &mut rng,
+    );
+    assert!(res.is_ok(), "{:?}", res);
+}
+
 #[test]
 fn verify_invalid_batch() {
     let (digest, pubkeys, mut signatures) = signature_test_inputs();
@@ -158,7 +282,7 @@ fn verify_batch_aggregate_signature_inputs() -> (
         .into_iter()
         .take(3)
         .map(|kp| {
-            let sig = kp.sign(&digest1.0);
+            let sig = kp.sign(digest1.as_ref());
             (kp.public().clone(), sig)
         })
         .unzip();
@@ -171,7 +295,7 @@ fn verify_batch_aggregate_signature_inputs() -> (
         .into_iter()
         .take(2)
         .map(|kp| {
-            let sig = kp.sign(&digest2.0);
+            let sig = kp.sign(digest2.as_ref());
             (kp.public().clone(), sig)
         })
         .unzip();
@@ -200,6 +324,239 @@ fn verify_batch_aggregate_signature() {
     .is_ok());
 }
 
+// Reference implementation of verify_different_msgs computing a full pairing per pair:
+// e(sig, g2) == e(H(m_1), pk_1) * ... * e(H(m_n), pk_n).
+fn naive_verify_different_msgs(
+    sig: &BLS12381AggregateSignature,
+    pks: &[BLS12381PublicKey],
+    messages: &[&[u8]],
+) -> bool {
+    use ::blst::*;
+
+    unsafe {
+        let mut sig_affine = blst_p1_affine::default();
+        assert_eq!(
+            blst_p1_uncompress(&mut sig_affine, sig.as_ref().as_ptr()),
+            BLST_ERROR::BLST_SUCCESS
+        );
+        let mut miller_loop = blst_fp12::default();
+        blst_miller_loop(&mut miller_loop, blst_p2_affine_generator(), &sig_affine);
+        let mut lhs = blst_fp12::default();
+        blst_final_exp(&mut lhs, &miller_loop);
+
+        let mut rhs = *blst_fp12_one();
+        for (pk, msg) in pks.iter().zip(messages) {
+            let mut pk_affine = blst_p2_affine::default();
+            assert_eq!(
+                blst_p2_uncompress(&mut pk_affine, pk.as_ref().as_ptr()),
+                BLST_ERROR::BLST_SUCCESS
+            );
+            let mut hash = blst_p1::default();
+            blst_hash_to_g1(
+                &mut hash,
+                msg.as_ptr(),
+                msg.len(),
+                DST.as_ptr(),
+                DST.len(),
+                std::ptr::null(),
+                0,
+            );
+            let mut hash_affine = blst_p1_affine::default();
+            blst_p1_to_affine(&mut hash_affine, &hash);
+
+            blst_miller_loop(&mut miller_loop, &pk_affine, &hash_affine);
+            let mut pairing = blst_fp12::default();
+            blst_final_exp(&mut pairing, &miller_loop);
+            let product = rhs;
+            blst_fp12_mul(&mut rhs, &product, &pairing);
+        }
+        blst_fp12_is_equal(&lhs, &rhs)
+    }
+}
+
+#[test]
+fn verify_different_msgs() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let messages: Vec<&[u8]> = vec![b"one", b"two", b"three", b"four"];
+    let sigs: Vec<_> = kps
+        .iter()
+        .zip(&messages)
+        .map(|(kp, msg)| kp.sign(msg))
+        .collect();
+    let aggregate = BLS12381AggregateSignature::aggregate(sigs).unwrap();
+
+    assert!(aggregate.verify_different_msgs(&pks, &messages).is_ok());
+    assert!(naive_verify_different_msgs(&aggregate, &pks, &messages));
+
+    // Swapping two messages invalidates the aggregate for both implementations.
+    let mut swapped = messages.clone();
+    swapped.swap(0, 1);
+    assert_eq!(
+        aggregate.verify_different_msgs(&pks, &swapped),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    assert!(!naive_verify_different_msgs(&aggregate, &pks, &swapped));
+
+    // So does leaving out a signer.
+    assert_eq!(
+        aggregate.verify_different_msgs(&pks[1..], &messages[1..]),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    assert!(!naive_verify_different_msgs(
+        &aggregate,
+        &pks[1..],
+        &messages[1..]
+    ));
+
+    assert_eq!(
+        aggregate.verify_different_msgs(&pks, &messages[1..]),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        aggregate.verify_different_msgs(&[], &[]),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        BLS12381AggregateSignature::default().verify_different_msgs(&pks, &messages),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // Repeated messages are rejected, even if the aggregate is valid.
+    let repeated: Vec<&[u8]> = vec![b"one"; 2];
+    let aggregate =
+        BLS12381AggregateSignature::aggregate(vec![kps[0].sign(b"one"), kps[1].sign(b"one")])
+            .unwrap();
+    assert!(naive_verify_different_msgs(
+        &aggregate,
+        &pks[..2],
+        &repeated
+    ));
+    assert_eq!(
+        aggregate.verify_different_msgs(&pks[..2], &repeated),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn verify_with_aggregate_public_key() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let message = b"Hello, world!";
+    let aggregate_signature = |kps: &[BLS12381KeyPair]| {
+        BLS12381AggregateSignature::aggregate(kps.iter().map(|kp| kp.sign(message)).collect())
+            .unwrap()
+    };
+
+    let mut aggregate_pk = BLS12381AggregatePublicKey::new(&pks[..3]).unwrap();
+    let signature = aggregate_signature(&kps[..3]);
+    assert!(aggregate_pk.verify(message, &signature).is_ok());
+    assert!(signature.verify(&pks[..3], message).is_ok());
+    assert_eq!(
+        aggregate_pk.verify(b"Other message", &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // After adding a member, only signatures by all of the new members verify.
+    aggregate_pk.add(&pks[3]).unwrap();
+    assert_eq!(aggregate_pk.len(), 4);
+    assert_eq!(
+        aggregate_pk.verify(message, &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    let signature = aggregate_signature(&kps);
+    assert!(aggregate_pk.verify(message, &signature).is_ok());
+    assert!(signature.verify(&pks, message).is_ok());
+
+    // Removing a member gives the same result as aggregating the remaining keys from scratch.
+    aggregate_pk.remove(&pks[0]).unwrap();
+    assert!(!aggregate_pk.contains(&pks[0]));
+    let signature = aggregate_signature(&kps[1..]);
+    assert!(aggregate_pk.verify(message, &signature).is_ok());
+    assert!(BLS12381AggregatePublicKey::new(&pks[1..])
+        .unwrap()
+        .verify(message, &signature)
+        .is_ok());
+    assert!(signature.verify(&pks[1..], message).is_ok());
+    assert_eq!(
+        aggregate_pk.verify(message, &aggregate_signature(&kps)),
+        Err(FastCryptoError::InvalidSignature)
+    );
+}
+
+#[test]
+fn test_remove_signature_from_aggregate() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let message = b"Hello, world!";
+    let signatures: Vec<_> = kps[..3].iter().map(|kp| kp.sign(message)).collect();
+
+    // Aggregate A + B + C and remove B.
+    let mut aggregate = BLS12381AggregateSignature::aggregate(signatures.clone()).unwrap();
+    let mut aggregate_pk = BLS12381AggregatePublicKey::new(&pks[..3]).unwrap();
+    aggregate.remove(&signatures[1]).unwrap();
+    aggregate_pk.remove(&pks[1]).unwrap();
+
+    let remaining = [pks[0].clone(), pks[2].clone()];
+    assert!(aggregate.verify(&remaining, message).is_ok());
+    assert!(aggregate_pk.verify(message, &aggregate).is_ok());
+    assert!(aggregate.verify(&pks[..3], message).is_err());
+    assert_eq!(
+        aggregate,
+        BLS12381AggregateSignature::aggregate(vec![signatures[0].clone(), signatures[2].clone()])
+            .unwrap()
+    );
+    assert_eq!(
+        BLS12381AggregateSignature::from_bytes(aggregate.as_ref()).unwrap(),
+        aggregate
+    );
+
+    // Removing the remaining signatures gives the empty aggregate.
+    aggregate.remove(&signatures[0]).unwrap();
+    aggregate.remove(&signatures[2]).unwrap();
+    assert_eq!(aggregate, BLS12381AggregateSignature::default());
+    assert_eq!(
+        aggregate.remove(&signatures[0]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn aggregate_public_key_membership_errors() {
+    let pks: Vec<_> = keys().iter().map(|kp| kp.public().clone()).collect();
+    assert_eq!(
+        BLS12381AggregatePublicKey::new(&[pks[0].clone(), pks[0].clone()]).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    let mut aggregate_pk = BLS12381AggregatePublicKey::new(&pks[..1]).unwrap();
+    assert_eq!(
+        aggregate_pk.add(&pks[0]),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        aggregate_pk.remove(&pks[1]),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // A signature cannot be verified against an empty set of members.
+    let signature =
+        BLS12381AggregateSignature::aggregate(vec![keys()[0].sign(b"message")]).unwrap();
+    assert!(aggregate_pk.verify(b"message", &signature).is_ok());
+    aggregate_pk.remove(&pks[0]).unwrap();
+    assert!(aggregate_pk.is_empty());
+    assert_eq!(
+        aggregate_pk.verify(b"message", &signature),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        BLS12381AggregatePublicKey::new(&pks[..1])
+            .unwrap()
+            .verify(b"message", &BLS12381AggregateSignature::default()),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
 #[test]
 fn verify_batch_missing_parameters_length_mismatch() {
     let (digest1, digest2, pubkeys1, pubkeys2, aggregated_signature1, aggregated_signature2) =
@@ -381,8 +738,33 @@ fn test_public_key_bytes_conversion() {
     let pk_bytes: BLS12381PublicKeyBytes = kp.public().into();
     let rebuilded_pk: BLS12381PublicKey = pk_bytes.try_into().unwrap();
     assert_eq!(kp.public().as_bytes(), rebuilded_pk.as_bytes());
+
+    // The compression flag is not set, and a point outside of the subgroup is rejected too.
+    let invalid_pk: Result<BLS12381PublicKey, _> =
+        BLS12381PublicKeyBytes::new([0u8; 96]).try_into();
+    assert!(invalid_pk.is_err());
+    let invalid_pk: Result<BLS12381PublicKey, _> =
+        BLS12381PublicKeyBytes::new(G2_POINT_NOT_IN_SUBGROUP).try_into();
+    assert!(invalid_pk.is_err());
 }
 
+#[test]
+fn test_is_valid_bytes() {
+    let kp = keys().pop().unwrap();
+    assert!(BLS12381PublicKey::is_valid_bytes(kp.public().as_ref()));
+
+    assert!(!BLS12381PublicKey::is_valid_bytes(&[0u8; 96]));
+    assert!(!BLS12381PublicKey::is_valid_bytes(
+        &kp.public().as_ref()[..95]
+    ));
+    assert!(!BLS12381PublicKey::is_valid_bytes(&[]));
+
+    // The subgroup check is skipped.
+    assert!(BLS12381PublicKey::is_valid_bytes(&G2_POINT_NOT_IN_SUBGROUP));
+    assert!(BLS12381PublicKey::from_bytes(&G2_POINT_NOT_IN_SUBGROUP).is_err());
+}
+
+#[cfg(feature = "signature-service")]
 #[tokio::test]
 async fn signature_service() {
     // Get a keypair.
@@ -401,6 +783,20 @@ async fn signature_service() {
     assert!(pk.verify(digest.as_ref(), &signature).is_ok());
 }
 
+#[test]
+fn test_private_key_debug_is_redacted() {
+    let kp = keys().pop().unwrap();
+    let encoded = kp.private().encode_base64();
+
+    let debug = format!("{:?}", kp.private());
+    assert_eq!(debug, "BLS12381PrivateKey(<redacted>)");
+    assert!(!format!("{:?}", kp).contains(&encoded));
+
+    let copy = BLS12381PrivateKey::from_bytes(kp.private().as_ref()).unwrap();
+    assert_eq!(&copy, kp.private());
+    assert_ne!(&copy, keys()[0].private());
+}
+
 // Checks if the private keys zeroed out
 #[test]
 fn test_sk_zeroization_on_drop() {
@@ -436,3 +832,339 @@ fn test_sk_zeroization_on_drop() {
         unsafe { ::std::slice::from_raw_parts(bytes_ptr, BLS12381PrivateKey::LENGTH) };
     assert_ne!(sk_memory, &sk_bytes[..]);
 }
+
+// Checks that a private key and its cached bytes are zeroed out when dropped on its own, outside
+// of a keypair.
+#[test]
+fn test_standalone_sk_zeroization_on_drop() {
+    let ptr: *const u8;
+    let bytes_ptr: *const u8;
+
+    let mut sk_bytes = Vec::new();
+
+    {
+        let mut rng = StdRng::from_seed([9; 32]);
+        let kp = BLS12381KeyPair::generate(&mut rng);
+        let sk = BLS12381PrivateKey::from_bytes(kp.private().as_ref()).unwrap();
+        sk_bytes.extend_from_slice(sk.as_ref());
+
+        ptr = std::ptr::addr_of!(sk.privkey) as *const u8;
+        bytes_ptr = &sk.as_ref()[0] as *const u8;
+
+        let sk_memory: &[u8] =
+            unsafe { ::std::slice::from_raw_parts(bytes_ptr, BLS12381PrivateKey::LENGTH) };
+        assert_eq!(sk_memory, &sk_bytes[..]);
+    }
+
+    unsafe {
+        for i in 0..BLS12381PrivateKey::LENGTH {
+            assert!(*ptr.add(i) == 0);
+        }
+    }
+
+    let sk_memory: &[u8] =
+        unsafe { ::std::slice::from_raw_parts(bytes_ptr, BLS12381PrivateKey::LENGTH) };
+    assert_ne!(sk_memory, &sk_bytes[..]);
+}
+
+// Compressed points with x = 4 on G1 and x = u on G2. Both are on their curves but lie outside
+// the prime order subgroups.
+const G1_POINT_NOT_IN_SUBGROUP: [u8; 48] = hex_literal::hex!("800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004");
+const G2_POINT_NOT_IN_SUBGROUP: [u8; 96] = hex_literal::hex!("a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+
+#[test]
+fn test_reject_signature_not_in_subgroup() {
+    // The point itself decodes, i.e., it is on the curve.
+    let point = blst::min_sig::Signature::from_bytes(&G1_POINT_NOT_IN_SUBGROUP).unwrap();
+    assert!(point.validate(false).is_err());
+
+    assert!(<BLS12381Signature as ToFromBytes>::from_bytes(&G1_POINT_NOT_IN_SUBGROUP).is_err());
+    assert!(BLS12381AggregateSignature::from_bytes(&G1_POINT_NOT_IN_SUBGROUP).is_err());
+    assert!(bincode::deserialize::<BLS12381Signature>(
+        &bincode::serialize(&G1_POINT_NOT_IN_SUBGROUP.to_vec()).unwrap()
+    )
+    .is_err());
+
+    let signature = keys().pop().unwrap().sign(b"Hello, world!");
+    assert!(signature.is_in_correct_subgroup());
+}
+
+#[test]
+fn test_reject_public_key_not_in_subgroup() {
+    let point = blst::min_sig::PublicKey::from_bytes(&G2_POINT_NOT_IN_SUBGROUP).unwrap();
+    assert!(point.validate().is_err());
+
+    assert!(BLS12381PublicKey::from_bytes(&G2_POINT_NOT_IN_SUBGROUP).is_err());
+    assert!(keys().pop().unwrap().public().is_in_correct_subgroup());
+}
+
+#[cfg(feature = "trusted_bls_inputs")]
+#[test]
+fn test_from_bytes_unchecked() {
+    let pk = BLS12381PublicKey::from_bytes_unchecked(&G2_POINT_NOT_IN_SUBGROUP).unwrap();
+    assert!(!pk.is_in_correct_subgroup());
+    let sig = BLS12381Signature::from_bytes_unchecked(&G1_POINT_NOT_IN_SUBGROUP).unwrap();
+    assert!(!sig.is_in_correct_subgroup());
+}
+
+#[test]
+fn test_uncompressed_public_key() {
+    let kp = keys().pop().unwrap();
+    let pk = kp.public();
+    let uncompressed = pk.to_uncompressed();
+    let compressed = pk.as_ref();
+
+    // The compression flag is only set in the compressed form, and both encode the same x.
+    assert_eq!(compressed[0] & 0x80, 0x80);
+    assert_eq!(uncompressed[0] & 0x80, 0);
+    assert_eq!(compressed[0] & 0x1f, uncompressed[0] & 0x1f);
+    assert_eq!(compressed[1..], uncompressed[1..compressed.len()]);
+
+    let from_uncompressed = BLS12381PublicKey::from_uncompressed(&uncompressed).unwrap();
+    let from_compressed = BLS12381PublicKey::from_bytes(compressed).unwrap();
+    assert_eq!(&from_uncompressed, pk);
+    assert_eq!(from_uncompressed, from_compressed);
+
+    // Lengths are not interchangeable.
+    assert!(BLS12381PublicKey::from_uncompressed(compressed).is_err());
+}
+
+#[test]
+fn test_uncompressed_signature() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+    let uncompressed = signature.to_uncompressed();
+    let compressed = signature.as_ref();
+
+    assert_eq!(compressed[0] & 0x80, 0x80);
+    assert_eq!(uncompressed[0] & 0x80, 0);
+    assert_eq!(compressed[0] & 0x1f, uncompressed[0] & 0x1f);
+    assert_eq!(compressed[1..], uncompressed[1..compressed.len()]);
+
+    let from_uncompressed = BLS12381Signature::from_uncompressed(&uncompressed).unwrap();
+    let from_compressed = <BLS12381Signature as ToFromBytes>::from_bytes(compressed).unwrap();
+    assert_eq!(from_uncompressed, signature);
+    assert_eq!(from_uncompressed, from_compressed);
+    assert!(kp
+        .public()
+        .verify(b"Hello, world!", &from_uncompressed)
+        .is_ok());
+
+    assert!(BLS12381Signature::from_uncompressed(compressed).is_err());
+}
+
+#[test]
+fn test_subgroup_check_cache() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+    assert!(signature.subgroup_checked.get().is_some());
+
+    // The cache is not serialized, so a deserialized signature is checked on its first
+    // verification only.
+    let serialized = bincode::serialize(&signature).unwrap();
+    assert_eq!(serialized, bincode::serialize(&signature.clone()).unwrap());
+    let deserialized: BLS12381Signature = bincode::deserialize(&serialized).unwrap();
+    assert!(deserialized.subgroup_checked.get().is_none());
+    assert_eq!(deserialized, signature);
+
+    // A failed verification does not fill the cache.
+    assert!(kp.public().verify(b"Goodbye", &deserialized).is_err());
+    assert!(deserialized.subgroup_checked.get().is_none());
+
+    assert!(kp.public().verify(b"Hello, world!", &deserialized).is_ok());
+    assert!(deserialized.subgroup_checked.get().is_some());
+    assert!(kp.public().verify(b"Hello, world!", &deserialized).is_ok());
+    assert!(kp.public().verify(b"Goodbye", &deserialized).is_err());
+
+    // Clones share the state of the cache at the time of cloning.
+    assert!(deserialized.clone().subgroup_checked.get().is_some());
+}
+
+// Verification only processes public data, but a verifier used for authentication should not
+// reveal how close a forgery came to being valid either. This compares the time to verify a valid
+// signature with that of signatures which are invalid for different reasons, and only flags gross
+// differences, e.g. an early exit, which would make an invalid signature several times faster.
+//
+// Wall-clock timings on shared CI machines are noisy, so the test is built to be stable there:
+// noise only ever adds time, so each candidate is summarized by a low percentile of many
+// interleaved samples instead of the mean, the tolerated ratio is wide, and the measurement is
+// repeated before the test fails.
+#[test]
+fn test_verification_timing_independent_of_validity() {
+    use std::time::{Duration, Instant};
+
+    const ROUNDS: usize = 101;
+    const ATTEMPTS: usize = 3;
+    const MAX_RATIO: u32 = 3;
+    let message: &[u8] = b"Hello, world!";
+    let kps = keys();
+    let kp = &kps[0];
+    let candidates = [
+        kp.sign(message),
+        kp.sign(b"Goodbye"),
+        kps[1].sign(message),
+        BLS12381Signature::default(),
+    ];
+    assert!(kp.public().verify(message, &candidates[0]).is_ok());
+    assert!(candidates[1..]
+        .iter()
+        .all(|signature| kp.public().verify(message, signature).is_err()));
+
+    // The 10th percentile of the verification times of each candidate.
+    let measure = || -> Vec<Duration> {
+        let mut timings = vec![Vec::with_capacity(ROUNDS); candidates.len()];
+        for round in 0..ROUNDS {
+            // Interleave the candidates and rotate their order, so that noise and warm-up affect
+            // all of them alike.
+            for offset in 0..candidates.len() {
+                let index = (round + offset) % candidates.len();
+                let start = Instant::now();
+                let _ = kp.public().verify(message, &candidates[index]);
+                timings[index].push(start.elapsed());
+            }
+        }
+        timings
+            .iter_mut()
+            .map(|timing| {
+                timing.sort();
+                timing[ROUNDS / 10]
+            })
+            .collect()
+    };
+
+    let mut attempts = Vec::with_capacity(ATTEMPTS);
+    for _ in 0..ATTEMPTS {
+        let percentiles = measure();
+        let fastest = *percentiles.iter().min().unwrap();
+        let slowest = *percentiles.iter().max().unwrap();
+        if slowest <= MAX_RATIO * fastest {
+            return;
+        }
+        attempts.push(percentiles);
+    }
+    panic!("verification times differ too much: {:?}", attempts);
+}
+
+#[test]
+fn test_public_key_bytes() {
+    for kp in keys() {
+        assert_eq!(kp.public_key_bytes(), kp.public().as_bytes());
+        assert_eq!(
+            &BLS12381PublicKey::from_bytes(kp.public_key_bytes()).unwrap(),
+            kp.public()
+        );
+    }
+}
+
+#[test]
+fn test_generate_many() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let expected: Vec<_> = (0..4)
+        .map(|_| BLS12381KeyPair::generate(&mut rng))
+        .collect();
+    let generated = keys();
+    assert_eq!(generated.len(), expected.len());
+    for (kp, expected) in generated.iter().zip(&expected) {
+        assert_eq!(kp.public(), expected.public());
+    }
+    assert!(BLS12381KeyPair::generate_many(0, &mut rng).is_empty());
+}
+
+#[test]
+fn test_rerandomize() {
+    use crate::groups::{bls12381::Scalar, GroupElement, Scalar as _};
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let signature = kp.sign(message);
+
+    let r1 = Scalar::rand(&mut rng);
+    let r2 = Scalar::rand(&mut rng);
+    let sig1 = signature.rerandomize(&r1).unwrap();
+    let sig2 = signature.rerandomize(&r2).unwrap();
+    let pk1 = kp.public().blind(&r1).unwrap();
+    let pk2 = kp.public().blind(&r2).unwrap();
+
+    // Both rerandomizations verify under their blinded key only, and are unlinkable.
+    assert_ne!(sig1, sig2);
+    assert_ne!(sig1, signature);
+    assert!(pk1.verify(message, &sig1).is_ok());
+    assert!(pk2.verify(message, &sig2).is_ok());
+    assert!(pk1.verify(message, &sig2).is_err());
+    assert!(kp.public().verify(message, &sig1).is_err());
+    assert!(pk1.verify(b"Goodbye", &sig1).is_err());
+
+    // The encodings are valid.
+    assert_eq!(
+        <BLS12381Signature as ToFromBytes>::from_bytes(sig1.as_ref()).unwrap(),
+        sig1
+    );
+    assert_eq!(BLS12381PublicKey::from_bytes(pk1.as_ref()).unwrap(), pk1);
+
+    // Rerandomizing with one does nothing, and zero is rejected.
+    assert_eq!(signature.rerandomize(&Scalar::from(1)).unwrap(), signature);
+    assert_eq!(
+        signature.rerandomize(&Scalar::zero()),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        kp.public().blind(&Scalar::zero()),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_lengths() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+    assert_eq!(kp.public().as_ref().len(), BLS_PUBLIC_KEY_LENGTH);
+    assert_eq!(signature.as_ref().len(), BLS_SIGNATURE_LENGTH);
+    assert_eq!(kp.private().as_ref().len(), BLS_PRIVATE_KEY_LENGTH);
+}
+
+#[test]
+fn test_canonicalize() {
+    let kp = keys().pop().unwrap();
+    let mut signature = kp.sign(b"Hello, world!");
+    assert!(signature.is_canonical());
+    let encoded = bincode::serialize(&signature).unwrap();
+    signature.canonicalize();
+    assert!(signature.is_canonical());
+    assert_eq!(bincode::serialize(&signature).unwrap(), encoded);
+}
+
+#[test]
+fn test_verify_batch_constant_time() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let message: &[u8] = b"Hello, world!";
+    let sigs: Vec<_> = kps.iter().map(|kp| kp.sign(message)).collect();
+    assert!(BLS12381PublicKey::verify_batch_constant_time(
+        message, &pks, &sigs
+    ));
+
+    // An invalid signature anywhere in the batch makes it fail.
+    for (i, kp) in kps.iter().enumerate() {
+        let mut invalid = sigs.clone();
+        invalid[i] = kp.sign(b"Other message");
+        assert!(!BLS12381PublicKey::verify_batch_constant_time(
+            message, &pks, &invalid
+        ));
+    }
+    assert!(!BLS12381PublicKey::verify_batch_constant_time(
+        b"Other message",
+        &pks,
+        &sigs
+    ));
+    assert!(!BLS12381PublicKey::verify_batch_constant_time(
+        message,
+        &pks[1..],
+        &sigs
+    ));
+    assert!(!BLS12381PublicKey::verify_batch_constant_time(
+        message,
+        &[],
+        &[]
+    ));
+}
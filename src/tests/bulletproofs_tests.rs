@@ -4,6 +4,7 @@ use crate::{
     bulletproofs::{BulletproofsRangeProof, PedersenCommitment},
     traits::ToFromBytes,
 };
+use rand::{rngs::StdRng, SeedableRng as _};
 
 ///
 /// Test Pedersen Commitments
@@ -78,6 +79,29 @@ fn test_range_proof_valid() {
         .is_ok());
 }
 
+#[test]
+fn test_range_proof_reproducible_from_seed() {
+    let blinding = [7u8; 32];
+    let prove = || {
+        BulletproofsRangeProof::prove_bit_length_with_rng(
+            1u64,
+            blinding,
+            64,
+            TEST_DOMAIN,
+            &mut StdRng::from_seed([7; 32]),
+        )
+        .unwrap()
+    };
+
+    let (commitment, proof) = prove();
+    let (other_commitment, other_proof) = prove();
+    assert_eq!(commitment, other_commitment);
+    assert_eq!(proof.as_ref(), other_proof.as_ref());
+    assert!(proof
+        .verify_bit_length(&commitment, 64, TEST_DOMAIN)
+        .is_ok());
+}
+
 #[test]
 fn test_range_proof_invalid() {
     let upper_bound: usize = 64;
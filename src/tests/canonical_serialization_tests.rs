@@ -0,0 +1,130 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Golden tests pinning the canonical byte layout of keys and signatures. If one of these tests
+//! fails, the wire format has changed.
+
+use crate::{
+    bls12381::{min_pk, min_sig},
+    ed25519::{Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    error::FastCryptoError,
+    hash::{HashFunction, Keccak256},
+    secp256k1::{Secp256k1KeyPair, Secp256k1PrivateKey, Secp256k1PublicKey, Secp256k1Signature},
+    traits::{KeyPair, ToFromBytes},
+};
+use signature::Signer;
+
+/// Check that `value` serializes to `expected`, that `expected` deserializes to `value`, and that
+/// shorter and longer inputs are rejected.
+fn assert_canonical<T: ToFromBytes + PartialEq>(value: &T, expected: &str) {
+    let bytes = hex::decode(expected).unwrap();
+    assert_eq!(hex::encode(value.canonical_serialize()), expected);
+    assert!(T::canonical_deserialize(&bytes).unwrap() == *value);
+    assert!(T::canonical_deserialize(&bytes[1..]).is_err());
+    assert!(T::canonical_deserialize(&[&bytes[..], &[0][..]].concat()).is_err());
+}
+
+/// Parse a private key from its canonical serialization in hex.
+fn private_key<T: ToFromBytes>(hex: &str) -> T {
+    T::canonical_deserialize(&hex::decode(hex).unwrap()).unwrap()
+}
+
+#[test]
+fn test_ed25519_golden_bytes() {
+    // The test 1 from RFC 8032, section 7.1, which signs the empty message.
+    let sk = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    assert_canonical(&private_key::<Ed25519PrivateKey>(sk), sk);
+    let kp = Ed25519KeyPair::from(private_key::<Ed25519PrivateKey>(sk));
+    assert_canonical::<Ed25519PublicKey>(
+        kp.public(),
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+    );
+    assert_canonical::<Ed25519Signature>(
+        &kp.sign(b""),
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    );
+}
+
+#[test]
+fn test_secp256k1_golden_bytes() {
+    // The eth_sign example from the web3.js documentation, which signs the message "Some data"
+    // with the Ethereum message prefix. The recovery id is the last byte.
+    let sk = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    assert_canonical(&private_key::<Secp256k1PrivateKey>(sk), sk);
+    let kp = Secp256k1KeyPair::from(private_key::<Secp256k1PrivateKey>(sk));
+    assert_canonical::<Secp256k1PublicKey>(
+        kp.public(),
+        "024e3b81af9c2234cad09d679ce6035ed1392347ce64ce405f5dcd36228a25de6e",
+    );
+    let digest = Keccak256::digest(b"\x19Ethereum Signed Message:\n9Some data");
+    assert_canonical::<Secp256k1Signature>(
+        &kp.sign_digest(&digest),
+        "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a02901",
+    );
+}
+
+#[test]
+fn test_bls12381_golden_bytes() {
+    // The private key of the first test vector of the Ethereum consensus specs, whose min_pk
+    // public key is given there. The min_sig public key was computed with a reference
+    // implementation.
+    let sk = "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3";
+
+    assert_canonical(&private_key::<min_sig::BLS12381PrivateKey>(sk), sk);
+    let kp = min_sig::BLS12381KeyPair::from(private_key::<min_sig::BLS12381PrivateKey>(sk));
+    assert_canonical::<min_sig::BLS12381PublicKey>(
+        kp.public(),
+        "ac400b70f6f8cd35648f5c126cce5417f3be4d8eefbd42ceb4286a14df7e03135313fe5845e3a575faab3e8b949d248814856c22d8cdb2967c720e963eedc999e738373b14172f06fc915769d3cc5ab7ae0a1b9c38f48b5585fb09d4bd2733bb",
+    );
+
+    assert_canonical(&private_key::<min_pk::BLS12381PrivateKey>(sk), sk);
+    let kp = min_pk::BLS12381KeyPair::from(private_key::<min_pk::BLS12381PrivateKey>(sk));
+    assert_canonical::<min_pk::BLS12381PublicKey>(
+        kp.public(),
+        "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+    );
+
+    // Signatures are deterministic. The expected values were computed with a reference
+    // implementation of hash_to_curve from RFC 9380 using the DST of each variant.
+    let kp = min_sig::BLS12381KeyPair::from(private_key::<min_sig::BLS12381PrivateKey>(sk));
+    assert_canonical::<min_sig::BLS12381Signature>(
+        &kp.sign(b"Hello, world!"),
+        "b51f419284bc0c4c8dbc8531121dd131e3a158d517498a7cdd4d32267fc298fa277707a56760c9ea46c80a66c8b7f04c",
+    );
+    let kp = min_pk::BLS12381KeyPair::from(private_key::<min_pk::BLS12381PrivateKey>(sk));
+    assert_canonical::<min_pk::BLS12381Signature>(
+        &kp.sign(b"Hello, world!"),
+        "b814bff7e562bc15ecda6ad47346d3107075f595d37132ed632a732088e4a66cdf97a6f951f0496e56086440a6b90d0f14118b95c1f2608055180888f389b41aba667d06bb547da0e6540caa4caec7f75bba759e8e13d428357772b30b6b8294",
+    );
+}
+
+/// A type whose [ToFromBytes::from_bytes] accepts non-canonical encodings: it ignores everything
+/// after the first byte.
+#[derive(Debug, PartialEq)]
+struct Lenient([u8; 1]);
+
+impl AsRef<[u8]> for Lenient {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl ToFromBytes for Lenient {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        bytes
+            .first()
+            .map(|b| Lenient([*b]))
+            .ok_or_else(signature::Error::new)
+    }
+}
+
+#[test]
+fn test_canonical_deserialize_rejects_non_canonical_encodings() {
+    assert_eq!(Lenient::canonical_deserialize(&[7]).unwrap(), Lenient([7]));
+    assert!(Lenient::from_bytes(&[7, 0]).is_ok());
+    assert_eq!(
+        Lenient::canonical_deserialize(&[7, 0]),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert!(Lenient::canonical_deserialize(&[]).is_err());
+}
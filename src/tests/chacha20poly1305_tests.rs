@@ -0,0 +1,65 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    aes::{Aes256Gcm, AesKey, Nonce},
+    chacha20poly1305::{ChaCha20Poly1305, ChaCha20Poly1305Key},
+    error::FastCryptoError,
+    traits::{Aead, ToFromBytes},
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+// Test vector from RFC 8439, section 2.8.2.
+#[test]
+fn test_rfc8439_vector() {
+    let key = ChaCha20Poly1305Key::from_bytes(
+        &hex::decode("808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f").unwrap(),
+    )
+    .unwrap();
+    let nonce = Nonce::from_bytes(&hex::decode("070000004041424344454647").unwrap()).unwrap();
+    let aad = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+    let plaintext: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    let ciphertext = ChaCha20Poly1305::encrypt(&key, &nonce, &aad, plaintext);
+    assert_eq!(
+        hex::encode(&ciphertext),
+        "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b61161ae10b594f09e26a7e902ecbd0600691"
+    );
+    assert_eq!(
+        ChaCha20Poly1305::decrypt(&key, &nonce, &aad, &ciphertext).unwrap(),
+        plaintext
+    );
+}
+
+#[test]
+fn test_tampered_tag() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key = ChaCha20Poly1305Key::generate(&mut rng);
+    let nonce = Nonce::generate(&mut rng);
+    let aad = b"header";
+
+    let mut ciphertext = ChaCha20Poly1305::encrypt(&key, &nonce, aad, b"Hello, world!");
+    let last = ciphertext.len() - 1;
+    ciphertext[last] ^= 1;
+    assert_eq!(
+        ChaCha20Poly1305::decrypt(&key, &nonce, aad, &ciphertext),
+        Err(FastCryptoError::GeneralOpaqueError)
+    );
+}
+
+fn round_trip<A: Aead>(key: &A::Key, nonce: &A::Nonce) {
+    let ciphertext = A::encrypt(key, nonce, b"header", b"Hello, world!");
+    assert_eq!(
+        A::decrypt(key, nonce, b"header", &ciphertext).unwrap(),
+        b"Hello, world!"
+    );
+    assert!(A::decrypt(key, nonce, b"other header", &ciphertext).is_err());
+}
+
+#[test]
+fn test_generic_aead() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let nonce = Nonce::generate(&mut rng);
+    round_trip::<ChaCha20Poly1305>(&ChaCha20Poly1305Key::generate(&mut rng), &nonce);
+    round_trip::<Aes256Gcm>(&AesKey::generate(&mut rng), &nonce);
+}
@@ -0,0 +1,156 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::{BLS12381KeyPair, BLS12381PublicKey},
+    committee::{Committee, Stake, TotalStake},
+    ed25519::Ed25519KeyPair,
+    secp256k1::Secp256k1KeyPair,
+    traits::{KeyPair, ToFromBytes},
+};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng as _};
+use signature::Signer;
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
+    hash::Hasher,
+};
+
+fn keys() -> Vec<BLS12381PublicKey> {
+    BLS12381KeyPair::generate_many(4, &mut StdRng::from_seed([0; 32]))
+        .iter()
+        .map(|kp| kp.public().clone())
+        .collect()
+}
+
+// Weights 4, 3, 2, 1 with a total weight of 10 and a quorum threshold of 7.
+fn committee(pks: &[BLS12381PublicKey]) -> Committee<BLS12381PublicKey> {
+    Committee::new(pks.iter().cloned().zip([4, 3, 2, 1]))
+}
+
+#[test]
+fn test_weights() {
+    let pks = keys();
+    let committee = committee(&pks);
+
+    assert_eq!(committee.total_weight(), 10);
+    assert_eq!(committee.quorum_threshold(), 7);
+    assert_eq!(committee.weight(&pks[2]), Some(2));
+    assert_eq!(committee.weight(&BLS12381PublicKey::default()), None);
+    assert_eq!(committee.present_weight(&pks), 10);
+    assert_eq!(committee.present_weight(&[]), 0);
+}
+
+#[test]
+fn test_quorum_just_met_and_just_missed() {
+    let pks = keys();
+    let committee = committee(&pks);
+    let threshold = committee.quorum_threshold();
+
+    // 4 + 3 = 7 just meets the threshold.
+    assert_eq!(committee.present_weight([&pks[0], &pks[1]]), 7);
+    assert!(committee.meets_quorum([&pks[0], &pks[1]], threshold));
+
+    // 4 + 2 = 6 and 3 + 2 + 1 = 6 just miss it.
+    assert!(!committee.meets_quorum([&pks[0], &pks[2]], threshold));
+    assert!(!committee.meets_quorum(&pks[1..], threshold));
+}
+
+#[test]
+fn test_duplicate_and_unknown_signers() {
+    let pks = keys();
+    let committee = committee(&pks);
+    let threshold = committee.quorum_threshold();
+
+    // Repeating a signer does not count its weight twice.
+    assert_eq!(committee.present_weight([&pks[0], &pks[0]]), 4);
+    assert!(!committee.meets_quorum([&pks[0], &pks[2], &pks[2]], threshold));
+
+    // Signers outside of the committee have no weight.
+    let outsider = BLS12381KeyPair::generate(&mut StdRng::from_seed([1; 32]))
+        .public()
+        .clone();
+    assert_eq!(committee.present_weight([&pks[3], &outsider]), 1);
+}
+
+fn assert_sorted_by_bytes<KP: KeyPair>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let mut pks: Vec<KP::PubKey> = KP::generate_many(16, &mut rng)
+        .iter()
+        .map(|kp| kp.public().clone())
+        .collect();
+    let mut expected = pks.clone();
+    expected.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+
+    for _ in 0..4 {
+        pks.shuffle(&mut rng);
+        let mut sorted = pks.clone();
+        sorted.sort();
+        assert_eq!(sorted, expected);
+
+        let map: BTreeMap<_, _> = pks.iter().cloned().zip(0..).collect();
+        assert!(map.keys().eq(expected.iter()));
+    }
+}
+
+#[test]
+fn test_public_keys_ordered_by_bytes() {
+    assert_sorted_by_bytes::<BLS12381KeyPair>();
+    assert_sorted_by_bytes::<Ed25519KeyPair>();
+    assert_sorted_by_bytes::<Secp256k1KeyPair>();
+}
+
+fn assert_hash_consistent<KP: KeyPair>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kps = KP::generate_many(4, &mut rng);
+    let index: HashMap<KP::PubKey, usize> = kps
+        .iter()
+        .enumerate()
+        .map(|(i, kp)| (kp.public().clone(), i))
+        .collect();
+
+    for (i, kp) in kps.iter().enumerate() {
+        // Decoding the same key twice gives equal keys with equal hashes.
+        let decoded = KP::PubKey::from_bytes(kp.public().as_ref()).unwrap();
+        let decoded_again = KP::PubKey::from_bytes(kp.public().as_ref()).unwrap();
+        assert_eq!(decoded, decoded_again);
+        assert_eq!(hash_of(&decoded), hash_of(&decoded_again));
+        assert_eq!(index.get(&decoded), Some(&i));
+
+        // The same holds for signatures, whether or not their encoding has been computed.
+        let signature = kp.sign(b"Hello, world!");
+        let decoded = <KP::Sig as ToFromBytes>::from_bytes(signature.as_ref()).unwrap();
+        let fresh = kp.sign(b"Hello, world!");
+        assert_eq!(signature, fresh);
+        assert_eq!(decoded, fresh);
+        assert_eq!(hash_of(&decoded), hash_of(&fresh));
+        let signatures: HashSet<_> = [signature, decoded, fresh].into_iter().collect();
+        assert_eq!(signatures.len(), 1);
+    }
+    assert_eq!(index.get(&KP::PubKey::default()), None);
+}
+
+fn hash_of<T: std::hash::Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_public_keys_and_signatures_in_hash_maps() {
+    assert_hash_consistent::<BLS12381KeyPair>();
+    assert_hash_consistent::<Ed25519KeyPair>();
+    assert_hash_consistent::<Secp256k1KeyPair>();
+}
+
+#[test]
+fn test_large_stakes() {
+    let pks = keys();
+    let committee = Committee::new(pks.iter().cloned().zip([Stake::MAX; 4]));
+    let max = Stake::MAX as TotalStake;
+
+    assert_eq!(committee.total_weight(), 4 * max);
+    assert_eq!(committee.quorum_threshold(), 8 * max / 3 + 1);
+    assert_eq!(committee.present_weight(&pks[..2]), 2 * max);
+    assert!(!committee.meets_quorum(&pks[..2], committee.quorum_threshold()));
+    assert!(committee.meets_quorum(&pks[..3], committee.quorum_threshold()));
+}
@@ -5,22 +5,34 @@
 use super::*;
 use crate::{
     ed25519::{
-        Ed25519AggregateSignature, Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey,
-        Ed25519PublicKeyBytes, Ed25519Signature, ED25519_PRIVATE_KEY_LENGTH,
+        Ed25519AggregateSignature, Ed25519BatchVerifier, Ed25519KeyPair, Ed25519PrivateKey,
+        Ed25519PublicKey, Ed25519PublicKeyBytes, Ed25519Signature, ED25519_PRIVATE_KEY_LENGTH,
+        ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH,
     },
+    error::FastCryptoError,
+    hash::{HashFunction, Sha512},
     hkdf::hkdf_generate_from_ikm,
-    traits::{AggregateAuthenticator, EncodeDecodeBase64, KeyPair, ToFromBytes, VerifyingKey},
+    traits::{
+        AggregateAuthenticator, EncodeDecodeBase64, FixedLength, KeyPair, ToFromBytes, VerifyingKey,
+    },
 };
+use curve25519_dalek_ng::edwards::CompressedEdwardsY;
 use ed25519_consensus::VerificationKey;
 use rand::{rngs::StdRng, SeedableRng as _};
 use serde_reflection::{Samples, Tracer, TracerConfig};
 use sha3::Sha3_256;
 use signature::{Signer, Verifier};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use wycheproof::{eddsa::TestSet, TestResult};
 
 pub fn keys() -> Vec<Ed25519KeyPair> {
-    let mut rng = StdRng::from_seed([0; 32]);
-    (0..4).map(|_| Ed25519KeyPair::generate(&mut rng)).collect()
+    Ed25519KeyPair::generate_many(4, &mut StdRng::from_seed([0; 32]))
 }
 
 #[test]
@@ -123,6 +135,14 @@ fn import_export_secret_key() {
     assert!(import.is_ok());
     assert_eq!(import.unwrap().as_ref(), secret_key.as_ref());
 }
+
+#[test]
+fn test_keys_reproducible_from_seed() {
+    let kp1 = Ed25519KeyPair::generate(&mut StdRng::from_seed([7; 32]));
+    let kp2 = Ed25519KeyPair::generate(&mut StdRng::from_seed([7; 32]));
+    assert_eq!(kp1.public(), kp2.public());
+    assert_eq!(kp1.private().as_ref(), kp2.private().as_ref());
+}
 #[test]
 fn to_from_bytes_signature() {
     let kpref = keys().pop().unwrap();
@@ -132,6 +152,89 @@ fn to_from_bytes_signature() {
     assert_eq!(rebuilt_sig.as_ref(), signature.as_ref());
 }
 
+#[test]
+fn test_try_from_bytes() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    let pk: Ed25519PublicKey = kp.public().as_ref().try_into().unwrap();
+    assert_eq!(&pk, kp.public());
+    let sig: Ed25519Signature = signature.as_ref().try_into().unwrap();
+    assert_eq!(sig, signature);
+    let sk_bytes = kp.private().as_ref().to_vec();
+    let sk: Ed25519PrivateKey = sk_bytes.as_slice().try_into().unwrap();
+    assert_eq!(sk.as_ref(), &sk_bytes[..]);
+
+    // Wrong lengths are rejected.
+    assert!(Ed25519PublicKey::try_from(&pk.as_ref()[1..]).is_err());
+    assert!(Ed25519Signature::try_from(&sig.as_ref()[1..]).is_err());
+    assert!(Ed25519PrivateKey::try_from(&sk_bytes[1..]).is_err());
+}
+
+#[test]
+fn test_fixed_lengths() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+    assert_eq!(kp.public().as_ref().len(), Ed25519PublicKey::LENGTH);
+    assert_eq!(signature.as_ref().len(), Ed25519Signature::LENGTH);
+    assert_eq!(kp.private().as_ref().len(), Ed25519PrivateKey::LENGTH);
+
+    let err = Ed25519PublicKey::from_bytes(&[0u8; 1]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InputLengthWrong {
+            expected: Ed25519PublicKey::LENGTH,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn test_error_variants() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    // A well-formed signature on another message.
+    let err = kp.public().verify(b"Bad message!", &signature).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InvalidSignature)
+    );
+
+    // A truncated signature.
+    let err = <Ed25519Signature as ToFromBytes>::from_bytes(&signature.as_ref()[1..]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InputLengthWrong {
+            expected: Ed25519Signature::LENGTH,
+            got: Ed25519Signature::LENGTH - 1
+        })
+    );
+}
+
+#[test]
+fn test_display_from_str() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    let pk = format!("{}", kp.public())
+        .parse::<Ed25519PublicKey>()
+        .unwrap();
+    assert_eq!(&pk, kp.public());
+    let sig = format!("{}", signature)
+        .parse::<Ed25519Signature>()
+        .unwrap();
+    assert_eq!(sig, signature);
+    let sk = kp.private();
+    let parsed_sk = sk.encode_base64().parse::<Ed25519PrivateKey>().unwrap();
+    assert_eq!(parsed_sk.as_ref(), sk.as_ref());
+
+    assert_eq!(
+        "not base64!".parse::<Ed25519PublicKey>(),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
 #[test]
 fn verify_valid_signature() {
     // Get a keypair.
@@ -141,10 +244,10 @@ fn verify_valid_signature() {
     let message: &[u8] = b"Hello, world!";
     let digest = message.digest();
 
-    let signature = kp.sign(&digest.0);
+    let signature = kp.sign(digest.as_ref());
 
     // Verify the signature.
-    assert!(kp.public().verify(&digest.0, &signature).is_ok());
+    assert!(kp.public().verify(digest.as_ref(), &signature).is_ok());
 }
 
 #[test]
@@ -156,13 +259,13 @@ fn verify_invalid_signature() {
     let message: &[u8] = b"Hello, world!";
     let digest = message.digest();
 
-    let signature = kp.sign(&digest.0);
+    let signature = kp.sign(digest.as_ref());
 
     // Verify the signature.
     let bad_message: &[u8] = b"Bad message!";
     let digest = bad_message.digest();
 
-    assert!(kp.public().verify(&digest.0, &signature).is_err());
+    assert!(kp.public().verify(digest.as_ref(), &signature).is_err());
 }
 
 fn signature_test_inputs() -> (Vec<u8>, Vec<Ed25519PublicKey>, Vec<Ed25519Signature>) {
@@ -173,7 +276,7 @@ fn signature_test_inputs() -> (Vec<u8>, Vec<Ed25519PublicKey>, Vec<Ed25519Signat
         .into_iter()
         .take(3)
         .map(|kp| {
-            let sig = kp.sign(&digest.0);
+            let sig = kp.sign(digest.as_ref());
             (kp.public().clone(), sig)
         })
         .unzip();
@@ -189,6 +292,20 @@ fn verify_valid_batch() {
     assert!(res.is_ok(), "{:?}", res);
 }
 
+#[test]
+fn verify_valid_batch_with_seeded_rng() {
+    let (digest, pubkeys, signatures) = signature_test_inputs();
+    let mut rng = StdRng::from_seed([7; 32]);
+
+    let res = Ed25519PublicKey::verify_batch_empty_fail_with_rng(
+        &digest[..],
+        &pubkeys,
+        &signatures,
+        &mut rng,
+    );
+    assert!(res.is_ok(), "{:?}", res);
+}
+
 #[test]
 fn verify_invalid_batch() {
     let (digest, pubkeys, mut signatures) = signature_test_inputs();
@@ -199,6 +316,35 @@ fn verify_invalid_batch() {
     assert!(res.is_err(), "{:?}", res);
 }
 
+#[test]
+fn verify_batch_verifier() {
+    let (digest, pubkeys, mut signatures) = signature_test_inputs();
+
+    let mut verifier = Ed25519BatchVerifier::new();
+    for (pk, sig) in pubkeys.iter().zip(&signatures) {
+        verifier.queue(pk, &digest, sig);
+    }
+    assert_eq!(verifier.len(), pubkeys.len());
+    assert!(verifier.verify().is_ok());
+    assert!(Ed25519PublicKey::verify_batch_empty_fail(&digest, &pubkeys, &signatures).is_ok());
+
+    // Same result as the slice-based verification when a signature is invalid.
+    signatures[1] = signatures[0].clone();
+    let mut verifier = Ed25519BatchVerifier::new();
+    for (pk, sig) in pubkeys.iter().zip(&signatures) {
+        verifier.queue(pk, &digest, sig);
+    }
+    assert_eq!(verifier.verify(), Err(FastCryptoError::InvalidSignature));
+    assert!(Ed25519PublicKey::verify_batch_empty_fail(&digest, &pubkeys, &signatures).is_err());
+
+    // Empty batches are rejected by both.
+    assert_eq!(
+        Ed25519BatchVerifier::new().verify(),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert!(Ed25519PublicKey::verify_batch_empty_fail(&digest, &[], &[]).is_err());
+}
+
 #[test]
 fn verify_empty_batch() {
     let (digest, _, _) = signature_test_inputs();
@@ -268,7 +414,7 @@ fn verify_batch_aggregate_signature_inputs() -> (
         .into_iter()
         .take(3)
         .map(|kp| {
-            let sig = kp.sign(&digest1.0);
+            let sig = kp.sign(digest1.as_ref());
             (kp.public().clone(), sig)
         })
         .unzip();
@@ -281,7 +427,7 @@ fn verify_batch_aggregate_signature_inputs() -> (
         .into_iter()
         .take(2)
         .map(|kp| {
-            let sig = kp.sign(&digest2.0);
+            let sig = kp.sign(digest2.as_ref());
             (kp.public().clone(), sig)
         })
         .unzip();
@@ -472,6 +618,29 @@ fn test_public_key_bytes_conversion() {
     let pk_bytes: Ed25519PublicKeyBytes = kp.public().into();
     let rebuilded_pk: Ed25519PublicKey = pk_bytes.try_into().unwrap();
     assert_eq!(kp.public().as_bytes(), rebuilded_pk.as_bytes());
+
+    // y = 2 is not the y-coordinate of a point on the curve.
+    let mut invalid = [0u8; 32];
+    invalid[0] = 2;
+    let invalid_pk: Result<Ed25519PublicKey, _> = Ed25519PublicKeyBytes::new(invalid).try_into();
+    assert!(invalid_pk.is_err());
+}
+
+#[test]
+fn test_is_valid_bytes() {
+    let kp = keys().pop().unwrap();
+    assert!(Ed25519PublicKey::is_valid_bytes(kp.public().as_ref()));
+
+    let mut not_on_curve = [0u8; 32];
+    not_on_curve[0] = 2;
+    assert!(!Ed25519PublicKey::is_valid_bytes(&not_on_curve));
+    assert!(!Ed25519PublicKey::is_valid_bytes(
+        &kp.public().as_ref()[..31]
+    ));
+    assert!(!Ed25519PublicKey::is_valid_bytes(&[]));
+    assert!(!Ed25519PublicKey::is_valid_bytes(
+        &[kp.public().as_ref(), &[0]].concat()
+    ));
 }
 
 #[test]
@@ -483,6 +652,27 @@ fn test_copy_key_pair() {
     assert_eq!(kp.private().0.as_bytes(), kp_copied.private().0.as_bytes());
 }
 
+#[test]
+fn test_into_and_from_parts() {
+    let mut keys = keys();
+    let other = keys.pop().unwrap();
+    let kp = keys.pop().unwrap();
+    let public = kp.public().clone();
+
+    let (pk, sk) = kp.into_parts();
+    assert_eq!(pk, public);
+    let kp = Ed25519KeyPair::from_parts(pk, sk).unwrap();
+    assert_eq!(kp.public(), &public);
+
+    // The public key must belong to the private key.
+    let (_, sk) = kp.into_parts();
+    assert_eq!(
+        Ed25519KeyPair::from_parts(other.public().clone(), sk).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
+
+#[cfg(feature = "signature-service")]
 #[tokio::test]
 async fn signature_service() {
     // Get a keypair.
@@ -501,6 +691,147 @@ async fn signature_service() {
     assert!(pk.verify(digest.as_ref(), &signature).is_ok());
 }
 
+/// A subscriber which records the names of all spans created while it is the default.
+#[cfg(feature = "tracing")]
+struct SpanRecorder {
+    names: Arc<std::sync::Mutex<Vec<String>>>,
+    next_id: std::sync::atomic::AtomicU64,
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::Subscriber for SpanRecorder {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        self.names
+            .lock()
+            .unwrap()
+            .push(span.metadata().name().to_string());
+        tracing::span::Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+    fn event(&self, _: &tracing::Event<'_>) {}
+
+    fn enter(&self, _: &tracing::span::Id) {}
+
+    fn exit(&self, _: &tracing::span::Id) {}
+}
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn signature_service_tracing() {
+    let names = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let _guard = tracing::subscriber::set_default(SpanRecorder {
+        names: names.clone(),
+        next_id: Default::default(),
+    });
+
+    let kp = keys().pop().unwrap();
+    let pk = kp.public().clone();
+    let mut service = SignatureService::new(kp);
+    let message: &[u8] = b"Hello, world!";
+    let digest = message.digest();
+    for _ in 0..3 {
+        let signature = service.request_signature(digest).await;
+        assert!(pk.verify(digest.as_ref(), &signature).is_ok());
+    }
+
+    assert_eq!(*names.lock().unwrap(), vec!["request_signature"; 3]);
+}
+
+/// A signer which blocks until its gate is opened, simulating a signer that is slower than the
+/// rate at which signatures are requested.
+struct GatedSigner {
+    kp: Ed25519KeyPair,
+    open: Arc<AtomicBool>,
+}
+
+impl Signer<Ed25519Signature> for GatedSigner {
+    fn try_sign(&self, msg: &[u8]) -> Result<Ed25519Signature, signature::Error> {
+        while !self.open.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        self.kp.try_sign(msg)
+    }
+}
+
+#[cfg(feature = "signature-service")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn signature_service_backpressure() {
+    let kp = keys().pop().unwrap();
+    let pk = kp.public().clone();
+    let open = Arc::new(AtomicBool::new(false));
+    let service = SignatureService::new_with_capacity(
+        GatedSigner {
+            kp,
+            open: open.clone(),
+        },
+        1,
+    )
+    .unwrap();
+
+    let message: &[u8] = b"Hello, world!";
+    let digest = message.digest();
+
+    // One request is held by the (blocked) signer and one fills the queue.
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let mut service = service.clone();
+            tokio::spawn(async move { service.request_signature(digest).await })
+        })
+        .collect();
+    while service.channel.capacity() > 0 {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+
+    // The queue is full, so the next request cannot be enqueued.
+    assert!(
+        tokio::time::timeout(Duration::from_millis(50), service.channel.reserve())
+            .await
+            .is_err()
+    );
+
+    // Once the signer drains the queue, all pending requests are served.
+    open.store(true, Ordering::SeqCst);
+    for handle in handles {
+        let signature = handle.await.unwrap();
+        assert!(pk.verify(digest.as_ref(), &signature).is_ok());
+    }
+    let mut service = service;
+    let signature = service.request_signature(digest).await;
+    assert!(pk.verify(digest.as_ref(), &signature).is_ok());
+}
+
+#[cfg(feature = "signature-service")]
+#[tokio::test]
+async fn signature_service_zero_capacity() {
+    let kp = keys().pop().unwrap();
+    assert!(matches!(
+        SignatureService::<Ed25519Signature>::new_with_capacity(kp, 0),
+        Err(FastCryptoError::InvalidInput)
+    ));
+}
+
+#[test]
+fn test_private_key_debug_is_redacted() {
+    let kp = keys().pop().unwrap();
+    let encoded = kp.private().encode_base64();
+
+    let debug = format!("{:?}", kp.private());
+    assert_eq!(debug, "Ed25519PrivateKey(<redacted>)");
+    assert!(!format!("{:?}", kp).contains(&encoded));
+
+    let copy = Ed25519PrivateKey::from_bytes(kp.private().as_ref()).unwrap();
+    assert_eq!(&copy, kp.private());
+    assert_ne!(&copy, keys()[0].private());
+}
+
 // Checks if the private keys zeroed out
 #[test]
 fn test_sk_zeroization_on_drop() {
@@ -551,6 +882,202 @@ fn test_sk_zeroization_on_drop() {
     assert_ne!(sk_memory, &sk_bytes[..]);
 }
 
+// Checks that a private key is zeroed out when dropped on its own, outside of a keypair.
+#[test]
+fn test_standalone_sk_zeroization_on_drop() {
+    let ptr: *const u8;
+    let sk_bytes = [7u8; ED25519_PRIVATE_KEY_LENGTH];
+
+    {
+        let sk = Ed25519PrivateKey::from_bytes(&sk_bytes).unwrap();
+        ptr = std::ptr::addr_of!(sk.0) as *const u8;
+
+        // As above, the seed starts at index 192 of the SigningKey.
+        unsafe {
+            for (i, &byte) in sk_bytes.iter().enumerate() {
+                assert_eq!(*ptr.add(i + 192), byte);
+            }
+        }
+    }
+
+    unsafe {
+        for i in 0..ED25519_PRIVATE_KEY_LENGTH {
+            assert_eq!(*ptr.add(i + 192), 0);
+        }
+    }
+}
+
+#[test]
+fn test_public_key_canonical_encoding() {
+    let kp = keys().pop().unwrap();
+    let canonical = kp.public().as_ref();
+    assert!(Ed25519PublicKey::from_bytes(canonical).is_ok());
+    assert!(Ed25519PublicKey::is_valid_bytes(canonical));
+
+    // The canonical encoding of the identity is accepted even though it has small order.
+    let identity =
+        hex::decode("0100000000000000000000000000000000000000000000000000000000000000").unwrap();
+    assert!(Ed25519PublicKey::from_bytes(&identity).is_ok());
+
+    // Encodings of the points with y = 0 and y = 1 as y = p and y = p + 1, and of the identity
+    // with the sign bit of x set.
+    for non_canonical in [
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "0100000000000000000000000000000000000000000000000000000000000080",
+    ] {
+        let bytes = hex::decode(non_canonical).unwrap();
+        let err = Ed25519PublicKey::from_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            FastCryptoError::from_signature_error(&err),
+            Some(&FastCryptoError::InvalidInput)
+        );
+        assert!(!Ed25519PublicKey::is_valid_bytes(&bytes));
+        let pk_bytes = Ed25519PublicKeyBytes::new(bytes.clone().try_into().unwrap());
+        assert!(Ed25519PublicKey::try_from(pk_bytes).is_err());
+        let base64 = crate::encoding::Encoding::Base64.encode(&bytes);
+        assert!(Ed25519PublicKey::decode_base64(&base64).is_err());
+
+        // The permissive parser accepts them.
+        let pk = Ed25519PublicKey::from_bytes_unchecked(&bytes).unwrap();
+        assert_eq!(pk.as_ref(), bytes);
+    }
+}
+
+#[test]
+fn verify_strict() {
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let signature = kp.sign(message);
+    assert!(kp.public().verify_strict(message, &signature).is_ok());
+    assert_eq!(
+        kp.public().verify_strict(b"Goodbye, world!", &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // The identity, canonically encoded and encoded as y = p + 1.
+    let identity =
+        hex::decode("0100000000000000000000000000000000000000000000000000000000000000").unwrap();
+    let non_canonical_identity =
+        hex::decode("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f").unwrap();
+
+    // A = R = identity and s = 0 satisfies the verification equation for any message.
+    let pk = Ed25519PublicKey::from_bytes(&identity).unwrap();
+    let sig = Ed25519Signature::from_bytes(&[identity.clone(), vec![0u8; 32]].concat()).unwrap();
+    assert!(pk.verify(message, &sig).is_ok());
+    assert_eq!(
+        pk.verify_strict(message, &sig),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // The same with a non-canonical encoding of A.
+    let pk = Ed25519PublicKey::from_bytes_unchecked(&non_canonical_identity).unwrap();
+    assert!(pk.verify(message, &sig).is_ok());
+    assert_eq!(
+        pk.verify_strict(message, &sig),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // Adding a point of order 2 to R gives a second signature which is only accepted by the
+    // cofactored verification equation.
+    let sig_bytes = signature.as_ref();
+    let r = CompressedEdwardsY::from_slice(&sig_bytes[..32])
+        .decompress()
+        .unwrap();
+    let order_two = CompressedEdwardsY::from_slice(
+        &hex::decode("ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f").unwrap(),
+    )
+    .decompress()
+    .unwrap();
+    assert!(order_two.is_small_order());
+    let malleated = Ed25519Signature::from_bytes(
+        &[(r + order_two).compress().as_bytes(), &sig_bytes[32..]].concat(),
+    )
+    .unwrap();
+    assert!(kp.public().verify(message, &malleated).is_ok());
+    assert_eq!(
+        kp.public().verify_strict(message, &malleated),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // Adding the group order to s gives a non-canonical scalar, which both reject.
+    let l =
+        hex::decode("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010").unwrap();
+    let mut s = [0u8; 32];
+    let mut carry = 0u16;
+    for ((s_i, x), y) in s.iter_mut().zip(&sig_bytes[32..]).zip(&l) {
+        let sum = *x as u16 + *y as u16 + carry;
+        *s_i = sum as u8;
+        carry = sum >> 8;
+    }
+    let non_canonical_s =
+        Ed25519Signature::from_bytes(&[&sig_bytes[..32], &s[..]].concat()).unwrap();
+    assert!(kp.public().verify(message, &non_canonical_s).is_err());
+    assert_eq!(
+        kp.public().verify_strict(message, &non_canonical_s),
+        Err(FastCryptoError::InvalidSignature)
+    );
+}
+
+#[test]
+fn test_verify_any() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let message: &[u8] = b"Hello, world!";
+
+    let signature = kps[2].sign(message);
+    assert_eq!(
+        Ed25519PublicKey::verify_any(&pks, message, &signature),
+        Ok(2)
+    );
+    assert_eq!(
+        Ed25519PublicKey::verify_any(&pks[..2], message, &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    assert_eq!(
+        Ed25519PublicKey::verify_any(&pks, b"Other message", &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    assert_eq!(
+        Ed25519PublicKey::verify_any(&[], message, &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+}
+
+#[test]
+fn test_canonicalize() {
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let signature = kp.sign(message);
+    assert!(signature.is_canonical());
+
+    // Add the group order to s.
+    let sig_bytes = signature.as_ref();
+    let l =
+        hex::decode("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010").unwrap();
+    let mut s = [0u8; 32];
+    let mut carry = 0u16;
+    for ((s_i, x), y) in s.iter_mut().zip(&sig_bytes[32..]).zip(&l) {
+        let sum = *x as u16 + *y as u16 + carry;
+        *s_i = sum as u8;
+        carry = sum >> 8;
+    }
+    let mut non_canonical =
+        Ed25519Signature::from_bytes(&[&sig_bytes[..32], &s[..]].concat()).unwrap();
+    assert!(!non_canonical.is_canonical());
+
+    non_canonical.canonicalize();
+    assert!(non_canonical.is_canonical());
+    assert_eq!(non_canonical, signature);
+    assert_eq!(non_canonical.as_ref(), sig_bytes);
+    assert!(kp.public().verify(message, &non_canonical).is_ok());
+
+    // Canonicalizing is idempotent.
+    let encoded = bincode::serialize(&non_canonical).unwrap();
+    non_canonical.canonicalize();
+    assert_eq!(bincode::serialize(&non_canonical).unwrap(), encoded);
+}
+
 #[test]
 fn wycheproof_test() {
     let test_set = TestSet::load(wycheproof::eddsa::TestName::Ed25519).unwrap();
@@ -568,6 +1095,320 @@ fn wycheproof_test() {
                 Ok(_) => assert_eq!(test.result, TestResult::Valid),
                 Err(_) => assert_eq!(test.result, TestResult::Invalid),
             }
+            match pk.verify_strict(&test.msg, &sig) {
+                Ok(_) => assert_eq!(test.result, TestResult::Valid),
+                Err(_) => assert_eq!(test.result, TestResult::Invalid),
+            }
         }
     }
 }
+
+// Generated with `openssl genpkey -algorithm ed25519 -outform DER`.
+const OPENSSL_PKCS8_KEY: &str =
+    "302e020100300506032b657004220420b17b2200cf903a6a4b6619f23d71a58a650457a11a93ca5465653fa6c8594d68";
+const OPENSSL_PUBLIC_KEY: &str = "c6036b6cd70b78d7fa81779231cc6cf4c9206ec5f0abd0e481fae814844e543f";
+
+#[test]
+fn test_pkcs8_import_openssl_key() {
+    let der = hex::decode(OPENSSL_PKCS8_KEY).unwrap();
+    let sk = Ed25519PrivateKey::from_pkcs8_der(&der).unwrap();
+    assert_eq!(
+        hex::encode(Ed25519PublicKey::from(&sk).as_ref()),
+        OPENSSL_PUBLIC_KEY
+    );
+    assert_eq!(*sk.to_pkcs8_der(), der);
+}
+
+#[test]
+fn test_pkcs8_round_trip() {
+    let kp = keys().pop().unwrap();
+    let der = kp.private().to_pkcs8_der();
+    let sk = Ed25519PrivateKey::from_pkcs8_der(&der).unwrap();
+    assert_eq!(&sk, kp.private());
+}
+
+#[test]
+fn test_pkcs8_invalid_input() {
+    // A secp256k1 key generated by OpenSSL.
+    let secp256k1_der = hex::decode("308184020100301006072a8648ce3d020106052b8104000a046d306b02010104206fc4a85124f9b6f1871567844e2a555fd1662913cf3426812b8a1005ab0321afa144034200047aaa4b31fc6f83d3184397460846a03f4521026d6e810a401b6658d5a313cf19a94248bc0280f597cfd427b19aad4e46b209de7d4f49f5cd218c082818908ff2").unwrap();
+    assert_eq!(
+        Ed25519PrivateKey::from_pkcs8_der(&secp256k1_der).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    let der = hex::decode(OPENSSL_PKCS8_KEY).unwrap();
+    assert_eq!(
+        Ed25519PrivateKey::from_pkcs8_der(&der[..der.len() - 1]).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+    // X25519 (OID 1.3.101.110) instead of Ed25519 (OID 1.3.101.112).
+    let mut x25519_der = der.clone();
+    x25519_der[11] = 110;
+    assert_eq!(
+        Ed25519PrivateKey::from_pkcs8_der(&x25519_der).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_spki_openssl_public_key() {
+    // Generated with `openssl pkey -pubout -outform DER` from the key above.
+    let der = hex::decode(
+        "302a300506032b6570032100c6036b6cd70b78d7fa81779231cc6cf4c9206ec5f0abd0e481fae814844e543f",
+    )
+    .unwrap();
+    let sk = Ed25519PrivateKey::from_pkcs8_der(&hex::decode(OPENSSL_PKCS8_KEY).unwrap()).unwrap();
+    let pk = Ed25519PublicKey::from_spki_der(&der).unwrap();
+    assert_eq!(pk, Ed25519PublicKey::from(&sk));
+    assert_eq!(pk.to_spki_der(), der);
+}
+
+#[test]
+fn test_spki_round_trip() {
+    let kp = keys().pop().unwrap();
+    let der = kp.public().to_spki_der();
+    assert_eq!(&Ed25519PublicKey::from_spki_der(&der).unwrap(), kp.public());
+
+    // A secp256k1 key is rejected.
+    let secp256k1_der = hex::decode("3036301006072a8648ce3d020106052b8104000a032200027aaa4b31fc6f83d3184397460846a03f4521026d6e810a401b6658d5a313cf19").unwrap();
+    assert_eq!(
+        Ed25519PublicKey::from_spki_der(&secp256k1_der),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        Ed25519PublicKey::from_spki_der(&der[..der.len() - 1]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_public_key_bytes() {
+    for kp in keys() {
+        assert_eq!(kp.public_key_bytes(), kp.public().as_bytes());
+        assert_eq!(
+            &Ed25519PublicKey::from_bytes(kp.public_key_bytes()).unwrap(),
+            kp.public()
+        );
+    }
+}
+
+#[test]
+fn test_generate_many() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let expected: Vec<_> = (0..4).map(|_| Ed25519KeyPair::generate(&mut rng)).collect();
+    let generated = keys();
+    assert_eq!(generated.len(), expected.len());
+    for (kp, expected) in generated.iter().zip(&expected) {
+        assert_eq!(kp.public(), expected.public());
+    }
+    assert!(Ed25519KeyPair::generate_many(0, &mut rng).is_empty());
+}
+
+#[test]
+fn test_lengths() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+    assert_eq!(kp.public().as_ref().len(), ED25519_PUBLIC_KEY_LENGTH);
+    assert_eq!(signature.as_ref().len(), ED25519_SIGNATURE_LENGTH);
+    assert_eq!(kp.private().as_ref().len(), ED25519_PRIVATE_KEY_LENGTH);
+}
+
+#[test]
+fn test_verify_batch_constant_time() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let message: &[u8] = b"Hello, world!";
+    let sigs: Vec<_> = kps.iter().map(|kp| kp.sign(message)).collect();
+    assert!(Ed25519PublicKey::verify_batch_constant_time(
+        message, &pks, &sigs
+    ));
+
+    // An invalid signature anywhere in the batch makes it fail.
+    for (i, kp) in kps.iter().enumerate() {
+        let mut invalid = sigs.clone();
+        invalid[i] = kp.sign(b"Other message");
+        assert!(!Ed25519PublicKey::verify_batch_constant_time(
+            message, &pks, &invalid
+        ));
+    }
+    assert!(!Ed25519PublicKey::verify_batch_constant_time(
+        b"Other message",
+        &pks,
+        &sigs
+    ));
+    assert!(!Ed25519PublicKey::verify_batch_constant_time(
+        message,
+        &pks[1..],
+        &sigs
+    ));
+    assert!(!Ed25519PublicKey::verify_batch_constant_time(
+        message,
+        &[],
+        &[]
+    ));
+}
+
+#[test]
+fn test_sign_prehashed() {
+    // The Ed25519ph test vector "abc" from RFC 8032, section 7.3.
+    let sk =
+        hex::decode("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42").unwrap();
+    let kp = Ed25519KeyPair::from(Ed25519PrivateKey::from_bytes(&sk).unwrap());
+    assert_eq!(
+        hex::encode(kp.public()),
+        "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf"
+    );
+    let message: &[u8] = b"abc";
+    let prehash = Sha512::digest(message).0;
+
+    let signature = kp.sign_prehashed(&prehash, None).unwrap();
+    assert_eq!(hex::encode(&signature), "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406");
+    assert!(kp
+        .public()
+        .verify_prehashed(&prehash, None, &signature)
+        .is_ok());
+    // An empty context is the same as no context.
+    assert!(kp
+        .public()
+        .verify_prehashed(&prehash, Some(b""), &signature)
+        .is_ok());
+
+    // It is not an Ed25519 signature of the message or of its hash.
+    assert!(kp.public().verify(message, &signature).is_err());
+    assert!(kp.public().verify(&prehash, &signature).is_err());
+    assert_eq!(
+        kp.public()
+            .verify_prehashed(&prehash, None, &kp.sign(&prehash)),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // The signature is bound to the context.
+    let context: &[u8] = b"foo";
+    let with_context = kp.sign_prehashed(&prehash, Some(context)).unwrap();
+    assert_eq!(hex::encode(&with_context), "e039702b4c2595a6a541ac8509236e2990474795330c9b34a75f58a660129e08fd736943fb1943a55720b9e0957b1ed6734816619f1388f43f73e6e3baa81c0e");
+    assert!(kp
+        .public()
+        .verify_prehashed(&prehash, Some(context), &with_context)
+        .is_ok());
+    assert_eq!(
+        kp.public().verify_prehashed(&prehash, None, &with_context),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    assert_eq!(
+        kp.public()
+            .verify_prehashed(&prehash, Some(b"bar"), &with_context),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // And to the hash and the key.
+    let other_prehash = Sha512::digest(b"abd").0;
+    assert!(kp
+        .public()
+        .verify_prehashed(&other_prehash, None, &signature)
+        .is_err());
+    let other = keys().pop().unwrap();
+    assert!(other
+        .public()
+        .verify_prehashed(&prehash, None, &signature)
+        .is_err());
+
+    // Contexts are at most 255 bytes long.
+    assert!(kp.sign_prehashed(&prehash, Some(&[0u8; 255])).is_ok());
+    assert_eq!(
+        kp.sign_prehashed(&prehash, Some(&[0u8; 256])),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        kp.public()
+            .verify_prehashed(&prehash, Some(&[0u8; 256]), &signature),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_sign_with_context() {
+    // The Ed25519ctx test vectors from RFC 8032, section 7.2: (secret key, public key, message,
+    // context, signature).
+    let vectors = [
+        (
+            "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+            "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+            "f726936d19c800494e3fdaff20b276a8",
+            "666f6f",
+            "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d",
+        ),
+        (
+            "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+            "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+            "f726936d19c800494e3fdaff20b276a8",
+            "626172",
+            "fc60d5872fc46b3aa69f8b5b4351d5808f92bcc044606db097abab6dbcb1aee3216c48e8b3b66431b5b186d1d28f8ee15a5ca2df6668346291c2043d4eb3e90d",
+        ),
+        (
+            "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+            "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+            "508e9e6882b979fea900f62adceaca35",
+            "666f6f",
+            "8b70c1cc8310e1de20ac53ce28ae6e7207f33c3295e03bb5c0732a1d20dc64908922a8b052cf99b7c4fe107a5abb5b2c4085ae75890d02df26269d8945f84b0b",
+        ),
+        (
+            "ab9c2853ce297ddab85c993b3ae14bcad39b2c682beabc27d6d4eb20711d6560",
+            "0f1d1274943b91415889152e893d80e93275a1fc0b65fd71b4b0dda10ad7d772",
+            "f726936d19c800494e3fdaff20b276a8",
+            "666f6f",
+            "21655b5f1aa965996b3f97b3c849eafba922a0a62992f73b3d1b73106a84ad85e9b86a7b6005ea868337ff2d20a7f5fbd4cd10b0be49a68da2b2e0dc0ad8960f",
+        ),
+    ];
+    for (sk, pk, message, context, expected) in vectors {
+        let kp =
+            Ed25519KeyPair::from(Ed25519PrivateKey::from_bytes(&hex::decode(sk).unwrap()).unwrap());
+        assert_eq!(hex::encode(kp.public()), pk);
+        let message = hex::decode(message).unwrap();
+        let context = hex::decode(context).unwrap();
+
+        let signature = kp.sign_with_context(&message, &context).unwrap();
+        assert_eq!(hex::encode(&signature), expected);
+        assert!(kp
+            .public()
+            .verify_with_context(&message, &context, &signature)
+            .is_ok());
+
+        // It is neither an Ed25519 nor an Ed25519ph signature, and it is bound to the context.
+        assert!(kp.public().verify(&message, &signature).is_err());
+        let prehash = Sha512::digest(&message).0;
+        assert!(kp
+            .public()
+            .verify_prehashed(&prehash, Some(&context), &signature)
+            .is_err());
+        assert_eq!(
+            kp.public()
+                .verify_with_context(&message, b"baz", &signature),
+            Err(FastCryptoError::InvalidSignature)
+        );
+        assert_eq!(
+            kp.public()
+                .verify_with_context(b"other", &context, &signature),
+            Err(FastCryptoError::InvalidSignature)
+        );
+    }
+
+    // An Ed25519 signature does not verify with the empty context.
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"message");
+    assert!(kp
+        .public()
+        .verify_with_context(b"message", b"", &signature)
+        .is_err());
+
+    // Contexts are at most 255 bytes long.
+    assert!(kp.sign_with_context(b"message", &[0u8; 255]).is_ok());
+    assert_eq!(
+        kp.sign_with_context(b"message", &[0u8; 256]),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        kp.public()
+            .verify_with_context(b"message", &[0u8; 256], &signature),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
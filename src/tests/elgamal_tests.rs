@@ -0,0 +1,110 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    elgamal::{decrypt, encrypt, ElGamalPrivateKey, ElGamalPublicKey},
+    groups::{
+        ristretto255::{RistrettoPoint, RistrettoScalar},
+        GroupElement, Scalar,
+    },
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+use zeroize::Zeroize;
+
+#[test]
+fn test_decrypt_inverts_encrypt() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sk = ElGamalPrivateKey::generate(&mut rng);
+    let pk = ElGamalPublicKey::from(&sk);
+
+    let message = RistrettoPoint::generator() * RistrettoScalar::from(42);
+    let ciphertext = encrypt(&pk, &message, &mut rng);
+
+    assert_eq!(decrypt(&sk, &ciphertext), message);
+}
+
+#[test]
+fn test_encryption_is_randomized() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sk = ElGamalPrivateKey::generate(&mut rng);
+    let pk = ElGamalPublicKey::from(&sk);
+
+    let message = RistrettoPoint::generator() * RistrettoScalar::from(42);
+    let ciphertext = encrypt(&pk, &message, &mut rng);
+    let other_ciphertext = encrypt(&pk, &message, &mut rng);
+
+    assert_ne!(ciphertext, other_ciphertext);
+    assert_eq!(decrypt(&sk, &other_ciphertext), message);
+}
+
+#[test]
+fn test_decrypt_with_wrong_key() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sk = ElGamalPrivateKey::generate(&mut rng);
+    let pk = ElGamalPublicKey::from(&sk);
+    let other_sk = ElGamalPrivateKey::generate(&mut rng);
+
+    let message = RistrettoPoint::generator() * RistrettoScalar::from(42);
+    let ciphertext = encrypt(&pk, &message, &mut rng);
+
+    assert_ne!(decrypt(&other_sk, &ciphertext), message);
+}
+
+#[test]
+fn test_additive_homomorphism() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let sk = ElGamalPrivateKey::generate(&mut rng);
+    let pk = ElGamalPublicKey::from(&sk);
+
+    let m1 = RistrettoPoint::generator() * RistrettoScalar::from(3);
+    let m2 = RistrettoPoint::generator() * RistrettoScalar::from(4);
+    let sum = encrypt(&pk, &m1, &mut rng) + encrypt(&pk, &m2, &mut rng);
+
+    assert_eq!(decrypt(&sk, &sum), m1 + m2);
+    assert_eq!(
+        decrypt(&sk, &sum),
+        RistrettoPoint::generator() * RistrettoScalar::from(7)
+    );
+}
+
+#[test]
+fn test_encryption_reproducible_from_seed() {
+    let message = RistrettoPoint::generator() * RistrettoScalar::from(42);
+    let run = || {
+        let mut rng = StdRng::from_seed([7; 32]);
+        let pk = ElGamalPublicKey::from(&ElGamalPrivateKey::generate(&mut rng));
+        (pk, encrypt(&pk, &message, &mut rng))
+    };
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn test_private_key_zeroize() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let mut sk = ElGamalPrivateKey::generate(&mut rng);
+    let pk = ElGamalPublicKey::from(&sk);
+    let ciphertext = encrypt(&pk, &RistrettoPoint::generator(), &mut rng);
+    assert_eq!(format!("{:?}", sk), "ElGamalPrivateKey(<redacted>)");
+
+    // The zeroized key is the zero scalar, which leaves the masked point unchanged.
+    sk.zeroize();
+    assert_eq!(decrypt(&sk, &ciphertext), ciphertext.masked);
+}
+
+#[test]
+fn test_private_key_debug_is_redacted() {
+    let sk = ElGamalPrivateKey::generate(&mut StdRng::from_seed([1; 32]));
+    // The key is the first random scalar drawn from the seeded rng.
+    let secret = RistrettoScalar::rand(&mut StdRng::from_seed([1; 32]));
+
+    let debug = format!("{:?}", sk);
+    assert_eq!(debug, "ElGamalPrivateKey(<redacted>)");
+    assert!(!debug.contains(&format!("{:?}", secret)));
+
+    let copy = ElGamalPrivateKey::generate(&mut StdRng::from_seed([1; 32]));
+    assert_eq!(copy, sk);
+    assert_ne!(
+        ElGamalPrivateKey::generate(&mut StdRng::from_seed([2; 32])),
+        sk
+    );
+}
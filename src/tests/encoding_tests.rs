@@ -0,0 +1,178 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::{BLS12381KeyPair, BLS12381PublicKey},
+    ed25519::{Ed25519KeyPair, Ed25519PublicKey},
+    encoding::{EncodeDecode, Encoding},
+    error::FastCryptoError,
+    secp256k1::{Secp256k1KeyPair, Secp256k1Signature},
+    traits::{EncodeDecodeBase64, KeyPair},
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+use signature::Signer;
+
+const ENCODINGS: [Encoding; 3] = [Encoding::Base64, Encoding::Base58, Encoding::Hex];
+
+#[test]
+fn test_decode_key_in_all_encodings() {
+    let kp = Ed25519KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let pk = kp.public();
+
+    let decoded: Vec<_> = ENCODINGS
+        .iter()
+        .map(|encoding| Ed25519PublicKey::decode(*encoding, &pk.encode(*encoding)).unwrap())
+        .collect();
+    assert!(decoded.iter().all(|d| d == pk));
+
+    // Base64 agrees with the existing encoding.
+    assert_eq!(pk.encode(Encoding::Base64), pk.encode_base64());
+    assert_eq!(pk.encode(Encoding::Hex), hex::encode(pk.as_ref()));
+}
+
+#[test]
+fn test_decode_other_types() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let secp = Secp256k1KeyPair::generate(&mut rng);
+    let bls = BLS12381KeyPair::generate(&mut rng);
+    let sig = secp.sign(b"Hello, world!");
+
+    for encoding in ENCODINGS {
+        let decoded = Secp256k1Signature::decode(encoding, &sig.encode(encoding)).unwrap();
+        assert_eq!(decoded, sig);
+        let decoded = BLS12381PublicKey::decode(encoding, &bls.public().encode(encoding)).unwrap();
+        assert_eq!(&decoded, bls.public());
+    }
+}
+
+#[test]
+fn test_encodings() {
+    let bytes = [0u8, 1, 255];
+    assert_eq!(Encoding::Base64.encode(&bytes), "AAH/");
+    assert_eq!(Encoding::Hex.encode(&bytes), "0001ff");
+    for encoding in ENCODINGS {
+        assert_eq!(encoding.decode(&encoding.encode(&bytes)).unwrap(), bytes);
+    }
+
+    // Base58 encodes leading zero bytes as '1'.
+    assert_eq!(Encoding::Base58.encode(&[0, 0]), "11");
+
+    // Upper case hex is accepted.
+    assert_eq!(Encoding::Hex.decode("0001FF").unwrap(), bytes);
+}
+
+#[test]
+fn test_decode_invalid_input() {
+    let kp = Ed25519KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let hex = kp.public().encode(Encoding::Hex);
+
+    assert_eq!(
+        Encoding::Hex.decode(&format!("0x{}", hex)),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        Encoding::Hex.decode("abc"),
+        Err(FastCryptoError::InvalidInput)
+    );
+    // 0, O, I and l are not in the Base58 alphabet.
+    assert_eq!(
+        Encoding::Base58.decode("0OIl"),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        Encoding::Base64.decode("AAH"),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // A valid encoding of a value of the wrong length.
+    assert_eq!(
+        Ed25519PublicKey::decode(Encoding::Hex, &hex[2..]),
+        Err(FastCryptoError::InputLengthWrong {
+            expected: 32,
+            got: 31
+        })
+    );
+}
+
+#[test]
+fn test_base64_url() {
+    let kps = Ed25519KeyPair::generate_many(8, &mut StdRng::from_seed([0; 32]));
+    for kp in &kps {
+        let pk = kp.public();
+        let encoded = pk.encode_base64_url();
+        assert!(!encoded.contains(|c| matches!(c, '+' | '/' | '=')));
+        assert_eq!(&Ed25519PublicKey::decode_base64_url(&encoded).unwrap(), pk);
+        assert_eq!(
+            encoded,
+            pk.encode_base64()
+                .trim_end_matches('=')
+                .replace('+', "-")
+                .replace('/', "_")
+        );
+
+        // Key pairs have their own standard Base64 encoding.
+        let encoded = kp.encode_base64_url();
+        assert!(!encoded.contains(|c| matches!(c, '+' | '/' | '=')));
+        assert_eq!(
+            Ed25519KeyPair::decode_base64_url(&encoded)
+                .unwrap()
+                .public(),
+            pk
+        );
+    }
+    // Some of the standard encodings use the characters which differ between the alphabets.
+    assert!(kps.iter().any(|kp| kp
+        .public()
+        .encode_base64()
+        .contains(|c| matches!(c, '+' | '/'))));
+
+    // Padded and standard Base64 are rejected.
+    let pk = kps[0].public();
+    assert!(Ed25519PublicKey::decode_base64_url(&pk.encode_base64()).is_err());
+    assert!(Ed25519PublicKey::decode_base64(&pk.encode_base64_url()).is_err());
+}
+
+#[test]
+fn test_base64_batch() {
+    let kps = Ed25519KeyPair::generate_many(4, &mut StdRng::from_seed([0; 32]));
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+
+    let encoded = Ed25519PublicKey::encode_base64_batch(&pks);
+    assert_eq!(encoded.len(), pks.len());
+    assert_eq!(encoded[1], pks[1].encode_base64());
+    assert_eq!(
+        Ed25519PublicKey::decode_base64_batch(&encoded).unwrap(),
+        pks
+    );
+    assert_eq!(
+        Ed25519PublicKey::decode_base64_batch::<String>(&[]).unwrap(),
+        vec![]
+    );
+
+    // Key pairs have their own encoding.
+    let decoded =
+        Ed25519KeyPair::decode_base64_batch(&Ed25519KeyPair::encode_base64_batch(&kps)).unwrap();
+    assert!(decoded
+        .iter()
+        .zip(&kps)
+        .all(|(decoded, kp)| decoded.public() == kp.public()));
+
+    // The position of the first invalid value is returned.
+    let mut corrupted = encoded.clone();
+    corrupted[2] = "not base64!".to_string();
+    assert_eq!(
+        Ed25519PublicKey::decode_base64_batch(&corrupted),
+        Err((2, FastCryptoError::InvalidInput))
+    );
+    corrupted[1] = Encoding::Base64.encode(&pks[1].as_ref()[1..]);
+    assert_eq!(
+        Ed25519PublicKey::decode_base64_batch(&corrupted),
+        Err((
+            1,
+            FastCryptoError::InputLengthWrong {
+                expected: 32,
+                got: 31
+            }
+        ))
+    );
+}
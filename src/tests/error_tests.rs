@@ -0,0 +1,107 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::{FastCryptoError, FastCryptoResult};
+use base64ct::{Base64, Encoding};
+use k256::ecdsa::signature::Verifier as _;
+
+#[cfg(feature = "rust_secp256k1")]
+#[test]
+fn test_secp256k1_errors() {
+    let invalid_key = rust_secp256k1::SecretKey::from_slice(&[0; 32]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from(invalid_key),
+        FastCryptoError::InvalidInput
+    );
+    let invalid_pk = rust_secp256k1::PublicKey::from_slice(&[5; 33]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from(invalid_pk),
+        FastCryptoError::InvalidInput
+    );
+
+    // A signature which does not verify.
+    let secp = rust_secp256k1::Secp256k1::new();
+    let sk = rust_secp256k1::SecretKey::from_slice(&[1; 32]).unwrap();
+    let pk = rust_secp256k1::PublicKey::from_secret_key(&secp, &sk);
+    let signature = secp.sign_ecdsa(&rust_secp256k1::Message::from_slice(&[1; 32]).unwrap(), &sk);
+    let incorrect = secp
+        .verify_ecdsa(
+            &rust_secp256k1::Message::from_slice(&[2; 32]).unwrap(),
+            &signature,
+            &pk,
+        )
+        .unwrap_err();
+    assert_eq!(
+        FastCryptoError::from(incorrect),
+        FastCryptoError::InvalidSignature
+    );
+}
+
+#[test]
+fn test_k256_errors() {
+    let invalid_pk = k256::PublicKey::from_sec1_bytes(&[5; 33]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from(invalid_pk),
+        FastCryptoError::InvalidInput
+    );
+
+    // k256 returns opaque signature errors when a signature does not verify.
+    let sk = k256::ecdsa::SigningKey::from_bytes(&[1; 32]).unwrap();
+    let signature: k256::ecdsa::Signature = k256::ecdsa::signature::Signer::sign(&sk, b"message");
+    let incorrect = sk
+        .verifying_key()
+        .verify(b"another message", &signature)
+        .unwrap_err();
+    assert_eq!(
+        FastCryptoError::from(incorrect),
+        FastCryptoError::InvalidSignature
+    );
+}
+
+#[test]
+fn test_signature_errors() {
+    // The error carried by a signature error is returned.
+    let e = signature::Error::from(FastCryptoError::InputLengthWrong {
+        expected: 32,
+        got: 31,
+    });
+    assert_eq!(
+        FastCryptoError::from(e),
+        FastCryptoError::InputLengthWrong {
+            expected: 32,
+            got: 31
+        }
+    );
+    assert_eq!(
+        FastCryptoError::from(signature::Error::new()),
+        FastCryptoError::InvalidSignature
+    );
+}
+
+#[test]
+fn test_decoding_errors() {
+    fn decode_all(value: &str) -> FastCryptoResult<()> {
+        Base64::decode_vec(value)?;
+        hex::decode(value)?;
+        bs58::decode(value).into_vec()?;
+        Ok(())
+    }
+
+    // "abcd" is valid Base64, hex and Base58.
+    assert_eq!(decode_all("abcd"), Ok(()));
+    for invalid in ["abc", "zz==", "0x00"] {
+        assert_eq!(decode_all(invalid), Err(FastCryptoError::InvalidInput));
+    }
+    assert_eq!(
+        FastCryptoError::from(Base64::decode_vec("a").unwrap_err()),
+        FastCryptoError::InvalidInput
+    );
+    assert_eq!(
+        FastCryptoError::from(hex::decode("0").unwrap_err()),
+        FastCryptoError::InvalidInput
+    );
+    assert_eq!(
+        FastCryptoError::from(bs58::decode("0OIl").into_vec().unwrap_err()),
+        FastCryptoError::InvalidInput
+    );
+}
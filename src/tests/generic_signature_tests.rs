@@ -0,0 +1,140 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::BLS12381KeyPair,
+    ed25519::Ed25519KeyPair,
+    error::FastCryptoError,
+    generic_signature::{
+        verify_all, GenericSignature, MessageAuthenticator, ED25519_FLAG, MULTISIG_FLAG,
+        SECP256K1_FLAG,
+    },
+    multisig::{MemberPublicKey, MemberSignature, MultiSig, MultiSigPublicKey},
+    secp256k1::Secp256k1KeyPair,
+    traits::KeyPair,
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+use signature::Signer;
+
+const MESSAGE: &[u8] = b"Hello, world!";
+
+fn signatures() -> Vec<GenericSignature> {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let ed25519 = Ed25519KeyPair::generate(&mut rng);
+    let secp256k1 = Secp256k1KeyPair::generate(&mut rng);
+    let multisig_pk = MultiSigPublicKey::new(
+        vec![
+            MemberPublicKey::Ed25519(ed25519.public().clone()),
+            MemberPublicKey::Secp256k1(secp256k1.public().clone()),
+        ],
+        vec![1, 1],
+        2,
+    )
+    .unwrap();
+    let multisig = MultiSig::new(
+        vec![
+            MemberSignature::Ed25519(ed25519.sign(MESSAGE)),
+            MemberSignature::Secp256k1(secp256k1.sign(MESSAGE)),
+        ],
+        0b11,
+    );
+
+    vec![
+        GenericSignature::Ed25519 {
+            pk: ed25519.public().clone(),
+            sig: ed25519.sign(MESSAGE),
+        },
+        GenericSignature::Secp256k1 {
+            pk: secp256k1.public().clone(),
+            sig: secp256k1.sign(MESSAGE),
+        },
+        GenericSignature::MultiSig {
+            pk: multisig_pk,
+            sig: multisig,
+        },
+    ]
+}
+
+#[test]
+fn test_verify_all_schemes() {
+    for signature in signatures() {
+        assert!(signature.verify(MESSAGE).is_ok());
+        assert_eq!(
+            signature.verify(b"Other message"),
+            Err(FastCryptoError::InvalidSignature)
+        );
+    }
+}
+
+#[test]
+fn test_verify_key_signature_pairs() {
+    let kp = BLS12381KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let pair = (kp.public().clone(), kp.sign(MESSAGE));
+    assert!(pair.verify(MESSAGE).is_ok());
+    assert_eq!(
+        pair.verify(b"Other message"),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // Pairs of different schemes can be verified through the same trait object.
+    let ed25519 = Ed25519KeyPair::generate(&mut StdRng::from_seed([1; 32]));
+    let authenticators: Vec<Box<dyn MessageAuthenticator>> = vec![
+        Box::new(pair),
+        Box::new((ed25519.public().clone(), ed25519.sign(MESSAGE))),
+        Box::new(signatures().pop().unwrap()),
+    ];
+    assert!(authenticators.iter().all(|a| a.verify(MESSAGE).is_ok()));
+}
+
+#[test]
+fn test_verify_all() {
+    let items: Vec<(GenericSignature, &[u8])> = signatures()
+        .into_iter()
+        .map(|signature| (signature, MESSAGE))
+        .collect();
+    assert_eq!(verify_all(&items), Ok(()));
+    assert_eq!(verify_all(&[]), Ok(()));
+
+    // Replace some of the messages, and one of the keys.
+    let mut items = [items.clone(), items].concat();
+    items[1].1 = b"Goodbye, world!";
+    items[5].1 = b"";
+    if let GenericSignature::Ed25519 { pk, .. } = &mut items[3].0 {
+        *pk = Ed25519KeyPair::generate(&mut StdRng::from_seed([1; 32]))
+            .public()
+            .clone();
+    }
+    assert_eq!(verify_all(&items), Err(vec![1, 3, 5]));
+}
+
+#[test]
+fn test_serialization_starts_with_flag() {
+    let flags = [ED25519_FLAG, SECP256K1_FLAG, MULTISIG_FLAG];
+    for (signature, flag) in signatures().into_iter().zip(flags) {
+        assert_eq!(signature.flag(), flag);
+
+        let bytes = bcs::to_bytes(&signature).unwrap();
+        assert_eq!(bytes[0], flag);
+        let deserialized: GenericSignature = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, signature);
+        assert!(deserialized.verify(MESSAGE).is_ok());
+
+        let json = serde_json::to_string(&signature).unwrap();
+        let deserialized: GenericSignature = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, signature);
+    }
+}
+
+#[test]
+fn test_deserialization_rejects_wrong_flag() {
+    let signature = signatures().remove(0);
+    let mut bytes = bcs::to_bytes(&signature).unwrap();
+
+    // The payload of an Ed25519 signature does not parse as a secp256k1 signature.
+    bytes[0] = SECP256K1_FLAG;
+    assert!(bcs::from_bytes::<GenericSignature>(&bytes).is_err());
+
+    bytes[0] = 0xff;
+    assert!(bcs::from_bytes::<GenericSignature>(&bytes).is_err());
+    assert!(bcs::from_bytes::<GenericSignature>(&[ED25519_FLAG]).is_err());
+}
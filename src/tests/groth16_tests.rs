@@ -0,0 +1,98 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::FastCryptoError,
+    groth16::{verify, Groth16Proof, Groth16VerifyingKey},
+};
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Groth16, Proof};
+use ark_relations::{
+    lc,
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError},
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_snark::{CircuitSpecificSetupSNARK, SNARK};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+/// A trivial circuit proving knowledge of `x` and `y` such that `x * y = z` for a public `z`.
+#[derive(Clone)]
+struct MultiplicationCircuit {
+    x: Option<Fr>,
+    y: Option<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for MultiplicationCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let x = cs.new_witness_variable(|| self.x.ok_or(SynthesisError::AssignmentMissing))?;
+        let y = cs.new_witness_variable(|| self.y.ok_or(SynthesisError::AssignmentMissing))?;
+        let z = cs.new_input_variable(|| {
+            Ok(self.x.ok_or(SynthesisError::AssignmentMissing)?
+                * self.y.ok_or(SynthesisError::AssignmentMissing)?)
+        })?;
+        cs.enforce_constraint(lc!() + x, lc!() + y, lc!() + z)
+    }
+}
+
+/// Produce an arkworks-serialized verifying key and proof for `3 * 5 = 15`.
+fn proof_and_vk() -> (Vec<u8>, Vec<u8>) {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let circuit = MultiplicationCircuit {
+        x: Some(Fr::from(3u64)),
+        y: Some(Fr::from(5u64)),
+    };
+    let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(circuit.clone(), &mut rng).unwrap();
+    let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut rng).unwrap();
+
+    let mut vk_bytes = Vec::new();
+    vk.serialize_compressed(&mut vk_bytes).unwrap();
+    let mut proof_bytes = Vec::new();
+    proof.serialize_compressed(&mut proof_bytes).unwrap();
+    (vk_bytes, proof_bytes)
+}
+
+#[test]
+fn test_verify_valid_proof() {
+    let (vk_bytes, proof_bytes) = proof_and_vk();
+    let vk = Groth16VerifyingKey::from_arkworks_bytes(&vk_bytes).unwrap();
+    let proof = Groth16Proof::from_arkworks_bytes(&proof_bytes).unwrap();
+
+    assert!(verify(&vk, &[Fr::from(15u64)], &proof).is_ok());
+}
+
+#[test]
+fn test_verify_tampered_public_input() {
+    let (vk_bytes, proof_bytes) = proof_and_vk();
+    let vk = Groth16VerifyingKey::from_arkworks_bytes(&vk_bytes).unwrap();
+    let proof = Groth16Proof::from_arkworks_bytes(&proof_bytes).unwrap();
+
+    assert_eq!(
+        verify(&vk, &[Fr::from(16u64)], &proof),
+        Err(FastCryptoError::InvalidProof)
+    );
+    assert_eq!(
+        verify(&vk, &[Fr::from(15u64), Fr::from(1u64)], &proof),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_verify_tampered_proof() {
+    let (vk_bytes, proof_bytes) = proof_and_vk();
+    let vk = Groth16VerifyingKey::from_arkworks_bytes(&vk_bytes).unwrap();
+    let proof = Proof::<Bn254>::deserialize_compressed(&proof_bytes[..]).unwrap();
+
+    // Swapping A and C gives a well-formed proof which does not verify.
+    let tampered = Groth16Proof::new(proof.c, proof.b, proof.a).unwrap();
+    assert_eq!(
+        verify(&vk, &[Fr::from(15u64)], &tampered),
+        Err(FastCryptoError::InvalidProof)
+    );
+}
+
+#[test]
+fn test_invalid_encodings() {
+    let (vk_bytes, proof_bytes) = proof_and_vk();
+    assert!(Groth16VerifyingKey::from_arkworks_bytes(&vk_bytes[1..]).is_err());
+    assert!(Groth16Proof::from_arkworks_bytes(&proof_bytes[..proof_bytes.len() - 1]).is_err());
+}
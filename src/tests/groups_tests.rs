@@ -0,0 +1,57 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::groups::{
+    bls12381::{G1Element, Scalar},
+    hash_to_scalar,
+    ristretto255::{RistrettoPoint, RistrettoScalar},
+    GroupElement,
+};
+
+const DST: &[u8] = b"FASTCRYPTO-HASH-TO-SCALAR-TEST";
+
+/// The scalar with the given big-endian hex encoding, computed with the group operations only.
+fn scalar_from_hex<S: GroupElement + From<u64>>(hex: &str) -> S {
+    hex::decode(hex)
+        .unwrap()
+        .iter()
+        .fold(S::zero(), |acc, byte| {
+            acc * S::from(256) + S::from(*byte as u64)
+        })
+}
+
+// The expected values are expand_message_xmd with SHA-256 of b"abc" to 64 bytes, read as a
+// big-endian (BLS12-381) or little-endian (ristretto255) integer and reduced modulo the group
+// order, computed independently with Python's hashlib and arbitrary precision integers.
+
+#[test]
+fn test_hash_to_bls12381_scalar() {
+    assert_eq!(
+        hash_to_scalar::<G1Element>(b"abc", DST),
+        scalar_from_hex::<Scalar>(
+            "1375f5a100836ae9827e43704abd9ddadf372f46af0744a8e8503b5c5bfa1a16"
+        )
+    );
+    assert_ne!(
+        hash_to_scalar::<G1Element>(b"abc", DST),
+        hash_to_scalar::<G1Element>(b"abc", b"OTHER-DST")
+    );
+    assert_ne!(
+        hash_to_scalar::<G1Element>(b"abc", DST),
+        hash_to_scalar::<G1Element>(b"abd", DST)
+    );
+}
+
+#[test]
+fn test_hash_to_ristretto_scalar() {
+    assert_eq!(
+        hash_to_scalar::<RistrettoPoint>(b"abc", DST),
+        scalar_from_hex::<RistrettoScalar>(
+            "0bca44293cfb94029216f87f247cfa80b54847029de4aad6c79983480870d1f3"
+        )
+    );
+    assert_ne!(
+        hash_to_scalar::<RistrettoPoint>(b"abc", DST),
+        hash_to_scalar::<RistrettoPoint>(b"abc", b"OTHER-DST")
+    );
+}
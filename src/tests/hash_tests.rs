@@ -0,0 +1,262 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::FastCryptoError,
+    hash::{
+        expand_message_xmd, hash_with_domain, Blake2b256, Blake3, Digest, HashFunction, Keccak256,
+        Sha256, Sha3_256, Sha512, Sha512_256,
+    },
+};
+
+#[test]
+fn test_sha256() {
+    let digest = Sha256::digest(b"abc");
+    assert_eq!(
+        digest.as_ref(),
+        hex::decode("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad").unwrap()
+    );
+}
+
+#[test]
+fn test_sha512() {
+    let digest: Digest<64> = Sha512::digest(b"abc");
+    assert_eq!(
+        digest.as_ref(),
+        hex::decode("ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f").unwrap()
+    );
+
+    let digest = Sha512::digest(b"");
+    assert_eq!(
+        digest.as_ref(),
+        hex::decode("cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e").unwrap()
+    );
+}
+
+#[test]
+fn test_sha512_256() {
+    let digest: Digest<32> = Sha512_256::digest(b"abc");
+    assert_eq!(
+        digest.as_ref(),
+        hex::decode("53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23").unwrap()
+    );
+
+    // Not a prefix of SHA-512 on the same input.
+    assert_ne!(digest.as_ref(), &Sha512::digest(b"abc").as_ref()[..32]);
+}
+
+#[test]
+fn test_sha3_256() {
+    let digest = Sha3_256::digest(b"abc");
+    assert_eq!(
+        digest.as_ref(),
+        hex::decode("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532").unwrap()
+    );
+}
+
+#[test]
+fn test_keccak256() {
+    let digest = Keccak256::digest(b"");
+    assert_eq!(
+        digest.as_ref(),
+        hex::decode("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").unwrap()
+    );
+}
+
+// From the official BLAKE3 test vectors, where the input of length n is the bytes 0, 1, ..., 250,
+// 0, 1, ... truncated to n bytes.
+#[test]
+fn test_blake3() {
+    assert_eq!(
+        Blake3::digest(b"").as_ref(),
+        hex::decode("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262").unwrap()
+    );
+    assert_eq!(
+        Blake3::digest([0u8]).as_ref(),
+        hex::decode("2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213").unwrap()
+    );
+
+    let key = b"whats the Elvish word for friend";
+    assert_eq!(
+        Blake3::new_keyed(key).finalize().as_ref(),
+        hex::decode("92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26").unwrap()
+    );
+
+    let context = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+    assert_eq!(
+        Blake3::derive_key(context).finalize().as_ref(),
+        hex::decode("2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d").unwrap()
+    );
+}
+
+#[test]
+fn test_blake3_streaming() {
+    let input: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+    let key = [7u8; 32];
+    for (mut streaming, mut one_shot) in [
+        (Blake3::default(), Blake3::default()),
+        (Blake3::new_keyed(&key), Blake3::new_keyed(&key)),
+        (Blake3::derive_key("context"), Blake3::derive_key("context")),
+    ] {
+        for chunk in input.chunks(1000) {
+            streaming.update(chunk);
+        }
+        one_shot.update(&input);
+        assert_eq!(streaming.finalize(), one_shot.finalize());
+    }
+
+    // The three modes give different outputs on the same input.
+    let digest = Blake3::digest(&input);
+    let mut keyed = Blake3::new_keyed(&key);
+    keyed.update(&input);
+    let mut derived = Blake3::derive_key("context");
+    derived.update(&input);
+    assert_ne!(digest, keyed.finalize());
+    assert_ne!(digest, derived.finalize());
+}
+
+#[test]
+fn test_incremental_update() {
+    let mut hasher = Blake2b256::default();
+    hasher.update(b"Hello, ");
+    hasher.update(b"world!");
+    assert_eq!(hasher.finalize(), Blake2b256::digest(b"Hello, world!"));
+}
+
+#[test]
+fn test_digest_display() {
+    let digest = Digest::from([0xab; 32]);
+    assert_eq!(format!("{}", digest), "ab".repeat(32));
+
+    let digest = Digest::new([0, 1, 2, 255]);
+    assert_eq!(digest.to_string(), "000102ff");
+}
+
+#[test]
+fn test_digest_truncated() {
+    let digest = Sha256::digest(b"Hello, world!");
+    let full = digest.to_string();
+    assert_eq!(full.len(), 64);
+    assert_eq!(digest.truncated(8), format!("{}...", &full[..16]));
+    assert_eq!(digest.truncated(1), format!("{}...", &full[..2]));
+    assert_eq!(digest.truncated(0), "...");
+
+    // Nothing is omitted if `n` is at least the length of the digest.
+    assert_eq!(digest.truncated(32), full);
+    assert_eq!(digest.truncated(100), full);
+
+    let digest = Digest::new([0, 1, 2, 255]);
+    assert_eq!(digest.truncated(2), "0001...");
+    assert_eq!(digest.truncated(4), "000102ff");
+}
+
+#[test]
+fn test_digest_serde() {
+    let digest = Sha256::digest(b"Hello, world!");
+
+    let bytes = bincode::serialize(&digest).unwrap();
+    let deserialized: Digest<32> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(deserialized, digest);
+
+    let json = serde_json::to_string(&digest).unwrap();
+    let deserialized: Digest<32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, digest);
+}
+
+#[test]
+fn test_digest_serde_format() {
+    // A digest is serialized like the plain byte array, as the 32-byte digest always was, so
+    // bincode adds no length prefix and JSON has no wrapping object.
+    let digest = Sha256::digest(b"Hello, world!");
+    assert_eq!(bincode::serialize(&digest).unwrap(), digest.0.to_vec());
+    assert_eq!(
+        serde_json::to_string(&digest).unwrap(),
+        serde_json::to_string(&digest.0).unwrap()
+    );
+
+    // The same holds for digests longer than 32 bytes.
+    let digest = Sha512::digest(b"Hello, world!");
+    let bytes = bincode::serialize(&digest).unwrap();
+    assert_eq!(bytes, digest.0.to_vec());
+    assert_eq!(bincode::deserialize::<Digest<64>>(&bytes).unwrap(), digest);
+}
+
+#[test]
+fn test_hash_with_domain() {
+    let digest = hash_with_domain::<Sha256, 32>(b"fastcrypto", b"Hello, world!");
+    assert_eq!(
+        digest.as_ref(),
+        hex::decode("5c138e41d5ca25b6b182c771bfe76900bd14de2a6ac19eb806b6ad8d04b635dc").unwrap()
+    );
+    assert_ne!(digest, Sha256::digest(b"fastcryptoHello, world!"));
+
+    // Different domains give different digests of the same message.
+    assert_ne!(
+        hash_with_domain::<Sha256, 32>(b"transaction", b"Hello, world!"),
+        hash_with_domain::<Sha256, 32>(b"intent", b"Hello, world!")
+    );
+    assert_ne!(
+        hash_with_domain::<Blake2b256, 32>(b"", b"Hello, world!"),
+        hash_with_domain::<Blake2b256, 32>(b"\x00", b"Hello, world!")
+    );
+
+    // Moving bytes between the domain and the message changes the digest.
+    assert_ne!(
+        hash_with_domain::<Sha256, 32>(b"ab", b"c"),
+        hash_with_domain::<Sha256, 32>(b"a", b"bc")
+    );
+    assert_ne!(
+        hash_with_domain::<Sha3_256, 32>(b"ab", b""),
+        hash_with_domain::<Sha3_256, 32>(b"", b"ab")
+    );
+}
+
+#[test]
+fn test_expand_message_xmd() {
+    // Test vectors from RFC 9380, Appendix K.1.
+    const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    let vectors: [(&[u8], usize, &str); 4] = [
+        (
+            b"",
+            0x20,
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+        ),
+        (
+            b"abc",
+            0x20,
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+        ),
+        (
+            b"abcdef0123456789",
+            0x20,
+            "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+        ),
+        (
+            b"abc",
+            0x80,
+            "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40",
+        ),
+    ];
+    for (msg, len, expected) in vectors {
+        let uniform_bytes = expand_message_xmd::<Sha256, 32>(msg, DST, len).unwrap();
+        assert_eq!(hex::encode(uniform_bytes), expected);
+    }
+
+    let long_msg = [b"a512_".as_slice(), &[b'a'; 512]].concat();
+    assert_eq!(
+        hex::encode(expand_message_xmd::<Sha256, 32>(&long_msg, DST, 0x20).unwrap()),
+        "4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c"
+    );
+
+    // At most 255 digests can be output, and the tag is at most 255 bytes.
+    assert!(expand_message_xmd::<Sha256, 32>(b"abc", DST, 255 * 32).is_ok());
+    assert_eq!(
+        expand_message_xmd::<Sha256, 32>(b"abc", DST, 255 * 32 + 1),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert!(expand_message_xmd::<Sha512, 64>(b"abc", DST, 255 * 64 + 1).is_err());
+    assert_eq!(
+        expand_message_xmd::<Sha256, 32>(b"abc", &[0u8; 256], 32),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
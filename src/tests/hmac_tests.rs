@@ -0,0 +1,62 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::hmac::{hmac_sha256, hmac_sha512, verify_hmac};
+
+// Test vectors from RFC 4231, test cases 1, 2 and 6.
+#[test]
+fn test_rfc4231_vectors() {
+    let key = [0x0b; 20];
+    let msg = b"Hi There";
+    assert_eq!(
+        hex::encode(hmac_sha256(&key, msg)),
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+    );
+    assert_eq!(
+        hex::encode(hmac_sha512(&key, msg)),
+        "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+    );
+
+    let key = b"Jefe";
+    let msg = b"what do ya want for nothing?";
+    assert_eq!(
+        hex::encode(hmac_sha256(key, msg)),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+    assert_eq!(
+        hex::encode(hmac_sha512(key, msg)),
+        "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+    );
+
+    // Key larger than the block size.
+    let key = [0xaa; 131];
+    let msg = b"Test Using Larger Than Block-Size Key - Hash Key First";
+    assert_eq!(
+        hex::encode(hmac_sha256(&key, msg)),
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+    );
+    assert_eq!(
+        hex::encode(hmac_sha512(&key, msg)),
+        "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+    );
+}
+
+#[test]
+fn test_verify_hmac() {
+    let tag = hmac_sha256(b"key", b"message");
+    assert!(verify_hmac(&tag, &hmac_sha256(b"key", b"message")));
+    assert!(!verify_hmac(&tag, &hmac_sha256(b"other key", b"message")));
+
+    // Tags differing only in their first or only in their last byte are both rejected, as every
+    // byte is compared.
+    let mut forged = tag;
+    forged[0] ^= 1;
+    assert!(!verify_hmac(&tag, &forged));
+    let mut forged = tag;
+    forged[31] ^= 1;
+    assert!(!verify_hmac(&tag, &forged));
+
+    // Truncated tags are rejected.
+    assert!(!verify_hmac(&tag, &tag[..16]));
+    assert!(!verify_hmac(&tag, &[]));
+}
@@ -0,0 +1,95 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::BLS12381KeyPair,
+    ed25519::Ed25519KeyPair,
+    error::FastCryptoError,
+    keystore::{decrypt, encrypt},
+    secp256k1::Secp256k1KeyPair,
+    traits::KeyPair,
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+// A low cost parameter keeps the tests fast. The format does not depend on it.
+const TEST_LOG_N: u8 = 10;
+
+fn round_trip<K: KeyPair>() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = K::generate(&mut rng);
+    let json = encrypt(&kp, "correct horse battery staple", TEST_LOG_N, &mut rng);
+    let decrypted = K::from_keystore_json(&json, "correct horse battery staple").unwrap();
+    assert_eq!(kp.public(), decrypted.public());
+
+    // Encrypting again uses a fresh salt and nonce.
+    assert_ne!(
+        json,
+        encrypt(&kp, "correct horse battery staple", TEST_LOG_N, &mut rng)
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    round_trip::<Ed25519KeyPair>();
+    round_trip::<Secp256k1KeyPair>();
+    round_trip::<BLS12381KeyPair>();
+}
+
+#[test]
+fn test_wrong_password() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = Ed25519KeyPair::generate(&mut rng);
+    let json = encrypt(&kp, "password", TEST_LOG_N, &mut rng);
+    assert_eq!(
+        Ed25519KeyPair::from_keystore_json(&json, "Password").err(),
+        Some(FastCryptoError::InvalidPassword)
+    );
+    assert_eq!(
+        Ed25519KeyPair::from_keystore_json(&json, "").err(),
+        Some(FastCryptoError::InvalidPassword)
+    );
+}
+
+#[test]
+fn test_invalid_keystore() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let kp = Ed25519KeyPair::generate(&mut rng);
+    let json = encrypt(&kp, "password", TEST_LOG_N, &mut rng);
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    // Modifying the ciphertext is detected like a wrong password.
+    let mut modified = value.clone();
+    let ciphertext = modified["crypto"]["ciphertext"].as_str().unwrap();
+    let flipped = format!(
+        "{}{}",
+        if &ciphertext[..1] == "0" { "1" } else { "0" },
+        &ciphertext[1..]
+    );
+    modified["crypto"]["ciphertext"] = flipped.into();
+    assert_eq!(
+        decrypt::<Ed25519KeyPair>(&modified.to_string(), "password").err(),
+        Some(FastCryptoError::InvalidPassword)
+    );
+
+    // Unsupported parameters, and a key pair of a different scheme, are rejected.
+    let mut modified = value.clone();
+    modified["crypto"]["kdf"] = "pbkdf2".into();
+    assert_eq!(
+        decrypt::<Ed25519KeyPair>(&modified.to_string(), "password").err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+    let mut modified = value;
+    modified["crypto"]["kdfparams"]["n"] = (1u64 << 30).into();
+    assert_eq!(
+        decrypt::<Ed25519KeyPair>(&modified.to_string(), "password").err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        decrypt::<Secp256k1KeyPair>(&json, "password").err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        decrypt::<Ed25519KeyPair>("{}", "password").err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
@@ -0,0 +1,178 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::FastCryptoError,
+    hash::{Digest, HashFunction, Keccak256, Sha256},
+    merkle::{MerkleProof, MerkleTree},
+};
+
+fn leaves(n: u8) -> Vec<Digest<32>> {
+    (0..n).map(|i| Sha256::digest([i])).collect()
+}
+
+#[test]
+fn test_proofs_at_every_index() {
+    for n in 1..=17 {
+        let leaves = leaves(n);
+        let tree = MerkleTree::from_leaves::<Sha256>(&leaves).unwrap();
+        assert_eq!(tree.leaf_count(), n as usize);
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(proof.verify::<Sha256>(&tree.root(), leaf, index).is_ok());
+        }
+        assert_eq!(tree.proof(n as usize), Err(FastCryptoError::InvalidInput));
+    }
+}
+
+#[test]
+fn test_root() {
+    let leaves = leaves(3);
+    let hash = |prefix: u8, data: &[&[u8]]| {
+        let mut h = Sha256::default();
+        h.update([prefix]);
+        data.iter().for_each(|d| h.update(d));
+        h.finalize()
+    };
+    let root = |leaf_count: u64, top: &Digest<32>| hash(2, &[&leaf_count.to_be_bytes(), &top.0]);
+
+    // A single leaf is hashed with the leaf prefix.
+    let tree = MerkleTree::from_leaves::<Sha256>(&leaves[..1]).unwrap();
+    assert_eq!(tree.root(), root(1, &hash(0, &[&leaves[0].0])));
+
+    // The third leaf is moved up unchanged and is then combined with the node of the first two.
+    let tree = MerkleTree::from_leaves::<Sha256>(&leaves).unwrap();
+    let [h0, h1, h2] = [0, 1, 2].map(|i| hash(0, &[&leaves[i].0]));
+    let top = hash(1, &[&hash(1, &[&h0.0, &h1.0]).0, &h2.0]);
+    assert_eq!(tree.root(), root(3, &top));
+
+    // The root depends on the hash function and the order of the leaves.
+    assert_ne!(
+        tree.root(),
+        MerkleTree::from_leaves::<Keccak256>(&leaves)
+            .unwrap()
+            .root()
+    );
+    let reversed: Vec<_> = leaves.iter().rev().copied().collect();
+    assert_ne!(
+        tree.root(),
+        MerkleTree::from_leaves::<Sha256>(&reversed).unwrap().root()
+    );
+
+    assert_eq!(
+        MerkleTree::from_leaves::<Sha256>(&[]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_tampered_proof() {
+    let leaves = leaves(7);
+    let tree = MerkleTree::from_leaves::<Sha256>(&leaves).unwrap();
+    let root = tree.root();
+    let proof = tree.proof(4).unwrap();
+    assert!(proof.verify::<Sha256>(&root, &leaves[4], 4).is_ok());
+
+    // Wrong leaf, position, root or hash function.
+    assert_eq!(
+        proof.verify::<Sha256>(&root, &leaves[5], 4),
+        Err(FastCryptoError::InvalidProof)
+    );
+    assert_eq!(
+        proof.verify::<Sha256>(&root, &leaves[4], 5),
+        Err(FastCryptoError::InvalidProof)
+    );
+    assert_eq!(
+        proof.verify::<Sha256>(&leaves[0], &leaves[4], 4),
+        Err(FastCryptoError::InvalidProof)
+    );
+    assert_eq!(
+        proof.verify::<Keccak256>(&root, &leaves[4], 4),
+        Err(FastCryptoError::InvalidProof)
+    );
+    assert_eq!(
+        proof.verify::<Sha256>(&root, &leaves[4], 7),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // Every modification of the serialized proof is detected.
+    let bytes = bcs::to_bytes(&proof).unwrap();
+    for i in 0..bytes.len() {
+        let mut tampered = bytes.clone();
+        tampered[i] ^= 1;
+        if let Ok(tampered) = bcs::from_bytes::<MerkleProof<32>>(&tampered) {
+            assert!(tampered.verify::<Sha256>(&root, &leaves[4], 4).is_err());
+        }
+    }
+
+    // A leaf hash cannot be passed off as an inner node, so the internal node over the first two
+    // leaves is not a leaf of the tree.
+    let inner = MerkleTree::from_leaves::<Sha256>(&leaves[..2])
+        .unwrap()
+        .root();
+    let proof = tree.proof(0).unwrap();
+    assert!(proof.verify::<Sha256>(&root, &inner, 0).is_err());
+
+    // The proof of another tree size does not verify.
+    let proof = MerkleTree::from_leaves::<Sha256>(&leaves[..6])
+        .unwrap()
+        .proof(4)
+        .unwrap();
+    assert_eq!(
+        proof.verify::<Sha256>(&root, &leaves[4], 4),
+        Err(FastCryptoError::InvalidProof)
+    );
+}
+
+#[test]
+fn test_tampered_leaf_count() {
+    // With three leaves, the top node is H(H(l0, l1), l2). Claiming that the tree has two leaves
+    // with the node over the first two leaves as sibling must not prove the third leaf at
+    // position 1.
+    let leaves = leaves(3);
+    let tree = MerkleTree::from_leaves::<Sha256>(&leaves).unwrap();
+    let hash = |prefix: u8, left: &Digest<32>, right: Option<&Digest<32>>| {
+        let mut h = Sha256::default();
+        h.update([prefix]);
+        h.update(left);
+        right.iter().for_each(|r| h.update(r));
+        h.finalize()
+    };
+    let node = hash(
+        1,
+        &hash(0, &leaves[0], None),
+        Some(&hash(0, &leaves[1], None)),
+    );
+    let bytes = [
+        2u64.to_le_bytes().to_vec(),
+        bcs::to_bytes(&vec![node]).unwrap(),
+    ]
+    .concat();
+    let forged = bcs::from_bytes::<MerkleProof<32>>(&bytes).unwrap();
+    assert_eq!(
+        forged.verify::<Sha256>(&tree.root(), &leaves[2], 1),
+        Err(FastCryptoError::InvalidProof)
+    );
+
+    // Changing only the number of leaves of an honest proof is also detected.
+    let mut bytes = bcs::to_bytes(&tree.proof(2).unwrap()).unwrap();
+    bytes[..8].copy_from_slice(&4u64.to_le_bytes());
+    let proof = bcs::from_bytes::<MerkleProof<32>>(&bytes).unwrap();
+    assert_eq!(
+        proof.verify::<Sha256>(&tree.root(), &leaves[2], 2),
+        Err(FastCryptoError::InvalidProof)
+    );
+}
+
+#[test]
+fn test_proof_with_maximal_leaf_count() {
+    let leaves = leaves(4);
+    let tree = MerkleTree::from_leaves::<Sha256>(&leaves).unwrap();
+    let mut bytes = bcs::to_bytes(&tree.proof(3).unwrap()).unwrap();
+    bytes[..8].copy_from_slice(&(usize::MAX as u64).to_le_bytes());
+    let proof = bcs::from_bytes::<MerkleProof<32>>(&bytes).unwrap();
+    assert_eq!(
+        proof.verify::<Sha256>(&tree.root(), &leaves[3], usize::MAX - 1),
+        Err(FastCryptoError::InvalidProof)
+    );
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ed25519::Ed25519KeyPair,
+    error::FastCryptoError,
+    multisig::{
+        MemberPublicKey, MemberSignature, MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit,
+        MAX_SIGNERS_IN_MULTISIG,
+    },
+    secp256k1::Secp256k1KeyPair,
+    traits::KeyPair,
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+use serde::Serialize;
+use signature::Signer;
+
+const MESSAGE: &[u8] = b"Hello, world!";
+
+fn members() -> (Ed25519KeyPair, Secp256k1KeyPair, Ed25519KeyPair) {
+    let mut rng = StdRng::from_seed([0; 32]);
+    (
+        Ed25519KeyPair::generate(&mut rng),
+        Secp256k1KeyPair::generate(&mut rng),
+        Ed25519KeyPair::generate(&mut rng),
+    )
+}
+
+fn two_of_three() -> (MultiSigPublicKey, Vec<MemberSignature>) {
+    let (kp1, kp2, kp3) = members();
+    let pk = MultiSigPublicKey::new(
+        vec![
+            MemberPublicKey::Ed25519(kp1.public().clone()),
+            MemberPublicKey::Secp256k1(kp2.public().clone()),
+            MemberPublicKey::Ed25519(kp3.public().clone()),
+        ],
+        vec![1, 1, 1],
+        2,
+    )
+    .unwrap();
+    let sigs = vec![
+        MemberSignature::Ed25519(kp1.sign(MESSAGE)),
+        MemberSignature::Secp256k1(kp2.sign(MESSAGE)),
+        MemberSignature::Ed25519(kp3.sign(MESSAGE)),
+    ];
+    (pk, sigs)
+}
+
+#[test]
+fn test_verify_two_of_three() {
+    let (pk, sigs) = two_of_three();
+
+    let multisig = MultiSig::new(vec![sigs[1].clone(), sigs[2].clone()], 0b110);
+    assert!(multisig.verify(&pk, MESSAGE).is_ok());
+    assert_eq!(
+        multisig.verify(&pk, b"Other message"),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    let multisig = MultiSig::new(sigs, 0b111);
+    assert!(multisig.verify(&pk, MESSAGE).is_ok());
+}
+
+#[test]
+fn test_verify_below_threshold() {
+    let (pk, sigs) = two_of_three();
+
+    let multisig = MultiSig::new(vec![sigs[0].clone()], 0b001);
+    assert_eq!(
+        multisig.verify(&pk, MESSAGE),
+        Err(FastCryptoError::InvalidSignature)
+    );
+}
+
+#[test]
+fn test_verify_bitmap_mismatch() {
+    let (pk, sigs) = two_of_three();
+
+    // More signatures than bits set.
+    let multisig = MultiSig::new(vec![sigs[0].clone(), sigs[1].clone()], 0b001);
+    assert_eq!(
+        multisig.verify(&pk, MESSAGE),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // Bit set for a member which does not exist.
+    let multisig = MultiSig::new(vec![sigs[0].clone(), sigs[1].clone()], 0b1001);
+    assert_eq!(
+        multisig.verify(&pk, MESSAGE),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // Signatures attributed to the wrong members.
+    let multisig = MultiSig::new(vec![sigs[0].clone(), sigs[2].clone()], 0b011);
+    assert!(multisig.verify(&pk, MESSAGE).is_err());
+}
+
+#[test]
+fn test_invalid_public_key() {
+    let (kp1, kp2, _) = members();
+    let pk1 = MemberPublicKey::Ed25519(kp1.public().clone());
+    let pk2 = MemberPublicKey::Secp256k1(kp2.public().clone());
+
+    // Duplicate keys.
+    assert!(MultiSigPublicKey::new(vec![pk1.clone(), pk1.clone()], vec![1, 1], 1).is_err());
+    // Zero weight.
+    assert!(MultiSigPublicKey::new(vec![pk1.clone(), pk2.clone()], vec![1, 0], 1).is_err());
+    // Mismatching number of weights.
+    assert!(MultiSigPublicKey::new(vec![pk1.clone(), pk2.clone()], vec![1], 1).is_err());
+    // Unreachable or zero threshold.
+    assert!(MultiSigPublicKey::new(vec![pk1.clone(), pk2.clone()], vec![1, 1], 3).is_err());
+    assert!(MultiSigPublicKey::new(vec![pk1, pk2], vec![1, 1], 0).is_err());
+    // No keys.
+    assert!(MultiSigPublicKey::new(vec![], vec![], 1).is_err());
+}
+
+#[test]
+fn test_deserialize_invalid_public_key() {
+    #[derive(Serialize)]
+    struct RawMultiSigPublicKey {
+        keys: Vec<MemberPublicKey>,
+        weights: Vec<WeightUnit>,
+        threshold: ThresholdUnit,
+    }
+
+    let (pk, sigs) = two_of_three();
+    let bytes = bcs::to_bytes(&pk).unwrap();
+    assert_eq!(bcs::from_bytes::<MultiSigPublicKey>(&bytes).unwrap(), pk);
+
+    let mut rng = StdRng::from_seed([1; 32]);
+    let many_keys = (0..MAX_SIGNERS_IN_MULTISIG + 17)
+        .map(|_| MemberPublicKey::Ed25519(Ed25519KeyPair::generate(&mut rng).public().clone()))
+        .collect::<Vec<_>>();
+    let malformed = [
+        // Too many keys for the bitmap.
+        RawMultiSigPublicKey {
+            weights: vec![1; many_keys.len()],
+            keys: many_keys,
+            threshold: 1,
+        },
+        // Fewer weights than keys.
+        RawMultiSigPublicKey {
+            keys: pk.keys().to_vec(),
+            weights: vec![1],
+            threshold: 1,
+        },
+        // Unreachable threshold.
+        RawMultiSigPublicKey {
+            keys: pk.keys().to_vec(),
+            weights: pk.weights().to_vec(),
+            threshold: 4,
+        },
+    ];
+    for raw in malformed {
+        let bytes = bcs::to_bytes(&raw).unwrap();
+        assert!(bcs::from_bytes::<MultiSigPublicKey>(&bytes).is_err());
+    }
+
+    // Signatures referring to members beyond the bitmap are rejected without panicking.
+    let multisig = MultiSig::new(sigs, 0b1000_0000_0000_0011);
+    assert_eq!(
+        multisig.verify(&pk, MESSAGE),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
@@ -0,0 +1,51 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests which only use verification and deserialization, so they are also compiled in the
+//! verify-only build without default features, where most other tests are not, e.g. with
+//! `cargo test --no-default-features --lib`. They check that signatures created elsewhere can be
+//! deserialized and verified without key generation, signing, rand and tokio.
+
+use crate::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    hash::Digest,
+    secp256k1::{Secp256k1PublicKey, Secp256k1Signature},
+    traits::{ToFromBytes, VerifyingKey},
+};
+use signature::Verifier;
+
+#[test]
+fn verify_precomputed_ed25519_signature() {
+    // Test 1 from RFC 8032, section 7.1.
+    let pk =
+        hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a").unwrap();
+    let sig = hex::decode("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b").unwrap();
+
+    let pk = Ed25519PublicKey::from_bytes(&pk).unwrap();
+    let sig = <Ed25519Signature as ToFromBytes>::from_bytes(&sig).unwrap();
+    assert!(pk.verify(b"", &sig).is_ok());
+    assert!(pk.verify(b"Hello, world!", &sig).is_err());
+
+    // Batch verification draws its randomness from the OS without rand.
+    assert!(Ed25519PublicKey::verify_batch_empty_fail(b"", &[pk.clone()], &[sig]).is_ok());
+
+    // Keys can also be deserialized from their serde representation.
+    let json = serde_json::to_string(&pk).unwrap();
+    assert_eq!(serde_json::from_str::<Ed25519PublicKey>(&json).unwrap(), pk);
+}
+
+#[test]
+fn verify_precomputed_secp256k1_signature() {
+    // The eth_sign signature of "Some data" from the web3.js documentation, over the Keccak-256
+    // hash of the message with the Ethereum prefix.
+    let pk =
+        hex::decode("024e3b81af9c2234cad09d679ce6035ed1392347ce64ce405f5dcd36228a25de6e").unwrap();
+    let digest = Digest::new(hex_literal::hex!(
+        "1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655"
+    ));
+    let sig = Secp256k1Signature::from_eth_rsv_hex("0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c").unwrap();
+
+    let pk = Secp256k1PublicKey::from_bytes(&pk).unwrap();
+    assert!(pk.verify_digest(&digest, &sig).is_ok());
+    assert!(pk.verify_digest(&Digest::new([0; 32]), &sig).is_err());
+}
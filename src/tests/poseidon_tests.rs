@@ -0,0 +1,49 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::FastCryptoError,
+    poseidon::{hash, PoseidonHash, POSEIDON_MAX_INPUTS},
+};
+use ark_bn254::Fr;
+use std::str::FromStr;
+
+// Reference outputs of circomlib's Poseidon.
+#[test]
+fn test_circomlib_vectors() {
+    assert_eq!(
+        hash(&[Fr::from(1u64)]).unwrap(),
+        Fr::from_str(
+            "18586133768512220936620570745912940619677854269274689475585506675881198879027"
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        hash(&[Fr::from(1u64), Fr::from(2u64)]).unwrap(),
+        Fr::from_str(
+            "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_fixed_arity_instance() {
+    let inputs = [Fr::from(1u64), Fr::from(2u64)];
+    let mut poseidon = PoseidonHash::new(2).unwrap();
+    assert_eq!(poseidon.hash(&inputs).unwrap(), hash(&inputs).unwrap());
+    assert_eq!(
+        poseidon.hash(&inputs[..1]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_arity_bounds() {
+    assert_eq!(hash(&[]).err(), Some(FastCryptoError::InvalidInput));
+    assert!(hash(&vec![Fr::from(1u64); POSEIDON_MAX_INPUTS]).is_ok());
+    assert_eq!(
+        hash(&vec![Fr::from(1u64); POSEIDON_MAX_INPUTS + 1]).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
@@ -3,7 +3,12 @@
 
 use std::str::FromStr;
 
-use crate::{ed25519::Ed25519PublicKeyBytes, traits::ToFromBytes};
+use crate::{
+    ed25519::Ed25519PublicKeyBytes,
+    secp256k1::{Secp256k1KeyPair, Secp256k1PublicKey, Secp256k1PublicKeyBytes},
+    traits::{FixedLength, KeyPair, ToFromBytes},
+};
+use rand::{rngs::StdRng, SeedableRng as _};
 
 #[test]
 fn test_public_key_bytes_to_from() {
@@ -28,3 +33,32 @@ fn test_from_str() {
     let pubkey_bytes = Ed25519PublicKeyBytes::from_str(format!("0x{}", HEX_STR).as_str()).unwrap();
     assert_eq!(pubkey_bytes.as_ref(), &bytes);
 }
+
+#[test]
+fn test_serialize_deserialize() {
+    let kp = Secp256k1KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    let pk_bytes: Secp256k1PublicKeyBytes = kp.public().into();
+
+    // Binary formats use the raw bytes.
+    let bytes = bincode::serialize(&pk_bytes).unwrap();
+    assert_eq!(
+        bytes[bytes.len() - Secp256k1PublicKeyBytes::LENGTH..],
+        *pk_bytes.as_ref()
+    );
+    let deserialized: Secp256k1PublicKeyBytes = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(deserialized, pk_bytes);
+    assert_eq!(bincode::serialize(&deserialized).unwrap(), bytes);
+
+    // Human readable formats use base64, as the public key does.
+    let json = serde_json::to_string(&pk_bytes).unwrap();
+    assert_eq!(json, serde_json::to_string(kp.public()).unwrap());
+    let deserialized: Secp256k1PublicKeyBytes = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, pk_bytes);
+    let pk: Secp256k1PublicKey = deserialized.try_into().unwrap();
+    assert_eq!(&pk, kp.public());
+
+    // Wrong lengths are rejected in both forms.
+    assert!(serde_json::from_str::<Secp256k1PublicKeyBytes>("\"AAAA\"").is_err());
+    let short = bincode::serialize(&[0u8; 32].to_vec()).unwrap();
+    assert!(bincode::deserialize::<Secp256k1PublicKeyBytes>(&short).is_err());
+}
@@ -0,0 +1,301 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    hash::{HashFunction, Sha256},
+    secp256k1::{Secp256k1KeyPair, Secp256k1PrivateKey},
+    traits::{KeyPair, ToFromBytes},
+};
+
+// Signatures of the SHA-256 hash of messages computed with libsecp256k1. The first two keys are
+// the RFC 6979 test vectors for secp256k1 used by most Bitcoin libraries.
+const VECTORS: [(&str, &str, &[(&[u8], &str)]); 3] = [
+    (
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        &[
+            (b"Satoshi Nakamoto", "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d82442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e501"),
+            (b"Hello, world!", "b85d62928d63583f52b14995c9444a92e1b7998a3fcfd0c134f327d61b162c6e7ea40adb783bd4c00f9cfdb829c7e7d5b8d8e25a797d8548aec6f41df461fab901"),
+            (b"", "77c8d336572f6f466055b5f70f433851f8f535f6c4fc71133a6cfd71079d03b70ed9f5eb8aa5b266abac35d416c3207e7a538bf5f37649727d7a9823b106957701"),
+        ],
+    ),
+    (
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        "0379be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        &[
+            (b"Satoshi Nakamoto", "fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d06b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed500"),
+            (b"Hello, world!", "361906623edfba1ce706ab30bca4d1b39e07a2949525ba96628eac1f4c3076ff493fe59375bae3d59963428345f069d0a0244f2058c9ebc67ea65de9e926ffcd00"),
+            (b"", "ea045bf0962ecc4d5aa84c8e716c87c9d5f49fba8e1ff0300ab2631de3d83b4351270ec8105346fddf35da5958d99ff55a0c0f720d6ae7f3e3eadd40a9ccfe0e01"),
+        ],
+    ),
+    (
+        "47a02bc67ef9e1fa96270e4692379fc27e55ed4d7b6b6fa933e34191443a7179",
+        "022b212890fc5666a86aa8aa967690a1fc7862e3506f3432a57bb6534a644eeedb",
+        &[
+            (b"Satoshi Nakamoto", "a390b4281ccf3e0c2609a487e76b6185a8b93804b8cb6292d160d5fd9595b4c377d57ee5cf672d8ed4dc03e961d51c3f3c3c7448e068a6969c604cc5bd370f6601"),
+            (b"Hello, world!", "dab212d2091e7e7927d217779018de51a8c22dbe721f287ab16f127d2da0b8243b9a051e5e43ac18919b54c99331d3a32fa04af0ac02180d771687d298ea32d701"),
+            (b"", "29d6389c0a1c8f18cdf60c608c45a0cf20630ca284ee8f810e9de2bd33633648217041288dbeff0b4d575df116932a8c1c4756bc0c89c48f85f4d636483336d000"),
+        ],
+    ),
+];
+
+// Runs with both backends, so it checks that they give the same signatures.
+#[test]
+fn test_libsecp256k1_vectors() {
+    for (sk, pk, signatures) in VECTORS {
+        let sk = Secp256k1PrivateKey::from_bytes(&hex::decode(sk).unwrap()).unwrap();
+        let kp = Secp256k1KeyPair::from(sk);
+        assert_eq!(hex::encode(kp.public().as_ref()), pk);
+        for (message, expected) in signatures {
+            let signature = kp.sign_with::<Sha256>(message);
+            assert_eq!(hex::encode(signature.as_ref()), *expected);
+            assert!(kp
+                .public()
+                .verify_with::<Sha256>(message, &signature)
+                .is_ok());
+            assert_eq!(
+                signature.recover(Sha256::digest(message).as_ref()).unwrap(),
+                *kp.public()
+            );
+        }
+    }
+}
+
+// Runs with both backends, so it checks that the accessors of the underlying types exist with both.
+#[test]
+fn test_backend_accessors() {
+    let sk = Secp256k1PrivateKey::from_bytes(&hex::decode(VECTORS[2].0).unwrap()).unwrap();
+    assert_eq!(hex::encode(sk.privkey().secret_bytes()), VECTORS[2].0);
+    let kp = Secp256k1KeyPair::from(sk);
+    assert_eq!(hex::encode(kp.public().pubkey().serialize()), VECTORS[2].1);
+    let (message, expected) = VECTORS[2].2[0];
+    let (recovery_id, compact) = kp.sign_with::<Sha256>(message).sig().serialize_compact();
+    assert_eq!(
+        hex::encode([&compact[..], &[recovery_id.to_i32() as u8]].concat()),
+        expected
+    );
+}
+
+// Compares the k256 backend with libsecp256k1 directly, so this needs rust_secp256k1. It runs with
+// the pure-rust feature too, since the backend used by the secp256k1 module does not matter here.
+#[cfg(feature = "rust_secp256k1")]
+mod libsecp256k1 {
+    use crate::secp256k1::pure_rust;
+    use k256::{elliptic_curve::PrimeField, FieldBytes};
+    use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+    fn negate(s: &[u8]) -> [u8; 32] {
+        let s = k256::Scalar::from_repr(*FieldBytes::from_slice(s)).unwrap();
+        let mut negated = [0u8; 32];
+        negated.copy_from_slice(&(-s).to_repr());
+        negated
+    }
+
+    #[test]
+    fn test_keys_match() {
+        let libsecp = rust_secp256k1::Secp256k1::new();
+        let pure = pure_rust::Secp256k1::new();
+        for seed in 0..16u8 {
+            let (sk, pk) = libsecp.generate_keypair(&mut StdRng::from_seed([seed; 32]));
+            let (pure_sk, pure_pk) = pure.generate_keypair(&mut StdRng::from_seed([seed; 32]));
+            assert_eq!(sk.secret_bytes(), pure_sk.secret_bytes());
+            assert_eq!(pk.serialize(), pure_pk.serialize());
+            assert_eq!(
+                pk.serialize_uncompressed(),
+                pure_pk.serialize_uncompressed()
+            );
+
+            // Tweaking keys.
+            let mut tweak = [0u8; 32];
+            StdRng::from_seed([seed ^ 0xff; 32]).fill_bytes(&mut tweak);
+            let libsecp_tweak = rust_secp256k1::Scalar::from_be_bytes(tweak).unwrap();
+            let pure_tweak = pure_rust::Scalar::from_be_bytes(tweak).unwrap();
+            assert_eq!(
+                sk.add_tweak(&libsecp_tweak).unwrap().secret_bytes(),
+                pure_sk.add_tweak(&pure_tweak).unwrap().secret_bytes()
+            );
+            assert_eq!(
+                pk.add_exp_tweak(&libsecp, &libsecp_tweak)
+                    .unwrap()
+                    .serialize(),
+                pure_pk
+                    .add_exp_tweak(&pure, &pure_tweak)
+                    .unwrap()
+                    .serialize()
+            );
+
+            // Tweaking a key by its negation gives zero, which both reject.
+            let negated = negate(&sk.secret_bytes());
+            assert!(sk
+                .add_tweak(&rust_secp256k1::Scalar::from_be_bytes(negated).unwrap())
+                .is_err());
+            assert!(pure_sk
+                .add_tweak(&pure_rust::Scalar::from_be_bytes(negated).unwrap())
+                .is_err());
+
+            // Diffie-Hellman with the key of the next seed.
+            let (other_sk, other_pk) =
+                libsecp.generate_keypair(&mut StdRng::from_seed([seed + 1; 32]));
+            let pure_other_pk = pure_rust::PublicKey::from_slice(&other_pk.serialize()).unwrap();
+            let pure_other_sk = pure_rust::SecretKey::from_slice(&other_sk.secret_bytes()).unwrap();
+            assert_eq!(
+                rust_secp256k1::ecdh::SharedSecret::new(&other_pk, &sk).secret_bytes(),
+                pure_rust::ecdh::SharedSecret::new(&pure_other_pk, &pure_sk).secret_bytes()
+            );
+            assert_eq!(
+                pure_rust::ecdh::SharedSecret::new(&pure_pk, &pure_other_sk).secret_bytes(),
+                pure_rust::ecdh::SharedSecret::new(&pure_other_pk, &pure_sk).secret_bytes()
+            );
+        }
+
+        // Zero is not a valid private key, and the group order is not a valid private key or tweak.
+        assert!(rust_secp256k1::SecretKey::from_slice(&[0u8; 32]).is_err());
+        assert!(pure_rust::SecretKey::from_slice(&[0u8; 32]).is_err());
+        let order = hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+            .unwrap();
+        assert!(rust_secp256k1::SecretKey::from_slice(&order).is_err());
+        assert!(pure_rust::SecretKey::from_slice(&order).is_err());
+        let order: [u8; 32] = order.try_into().unwrap();
+        assert!(rust_secp256k1::Scalar::from_be_bytes(order).is_err());
+        assert!(pure_rust::Scalar::from_be_bytes(order).is_err());
+    }
+
+    #[test]
+    fn test_public_key_parsing_matches() {
+        let mut rng = StdRng::from_seed([1; 32]);
+        let (_, pk) = rust_secp256k1::Secp256k1::new().generate_keypair(&mut rng);
+        let uncompressed = pk.serialize_uncompressed();
+        let mut inputs = vec![pk.serialize().to_vec(), uncompressed.to_vec()];
+        // The hybrid encodings, where only the one matching the parity of y is valid.
+        for tag in [0x06, 0x07] {
+            inputs.push([&[tag], &uncompressed[1..]].concat());
+        }
+        // Random x-coordinates, about half of which are on the curve, and invalid tags.
+        for _ in 0..32 {
+            let mut bytes = [0u8; 33];
+            rng.fill_bytes(&mut bytes);
+            for tag in [0x00, 0x02, 0x03, 0x04, 0x05] {
+                bytes[0] = tag;
+                inputs.push(bytes.to_vec());
+            }
+        }
+        inputs.push(vec![0x00]);
+        inputs.push(vec![]);
+        for input in inputs {
+            let libsecp = rust_secp256k1::PublicKey::from_slice(&input);
+            let pure = pure_rust::PublicKey::from_slice(&input);
+            assert_eq!(libsecp.is_ok(), pure.is_ok(), "{}", hex::encode(&input));
+            if let (Ok(libsecp), Ok(pure)) = (libsecp, pure) {
+                assert_eq!(libsecp.serialize(), pure.serialize());
+            }
+        }
+    }
+
+    #[test]
+    fn test_signatures_match() {
+        let libsecp = rust_secp256k1::Secp256k1::new();
+        let pure = pure_rust::Secp256k1::new();
+        let mut rng = StdRng::from_seed([2; 32]);
+        for _ in 0..64 {
+            let (sk, pk) = libsecp.generate_keypair(&mut rng);
+            let pure_sk = pure_rust::SecretKey::from_slice(&sk.secret_bytes()).unwrap();
+            let pure_pk = pure_rust::PublicKey::from_slice(&pk.serialize()).unwrap();
+            let mut digest = [0u8; 32];
+            rng.fill_bytes(&mut digest);
+            let msg = rust_secp256k1::Message::from_slice(&digest).unwrap();
+            let pure_msg = pure_rust::Message::from_slice(&digest).unwrap();
+
+            let (recovery_id, compact) = libsecp
+                .sign_ecdsa_recoverable(&msg, &sk)
+                .serialize_compact();
+            let (pure_recovery_id, pure_compact) = pure
+                .sign_ecdsa_recoverable(&pure_msg, &pure_sk)
+                .serialize_compact();
+            assert_eq!(compact, pure_compact);
+            assert_eq!(recovery_id.to_i32(), pure_recovery_id.to_i32());
+
+            // Signatures in the low-S form verify with both, those in the high-S form with neither.
+            let signature = rust_secp256k1::ecdsa::Signature::from_compact(&compact).unwrap();
+            let pure_signature = pure_rust::ecdsa::Signature::from_compact(&compact).unwrap();
+            assert!(libsecp.verify_ecdsa(&msg, &signature, &pk).is_ok());
+            assert!(pure
+                .verify_ecdsa(&pure_msg, &pure_signature, &pure_pk)
+                .is_ok());
+            let high_s = [&compact[..32], &negate(&compact[32..])[..]].concat();
+            let signature = rust_secp256k1::ecdsa::Signature::from_compact(&high_s).unwrap();
+            let mut pure_signature = pure_rust::ecdsa::Signature::from_compact(&high_s).unwrap();
+            assert!(libsecp.verify_ecdsa(&msg, &signature, &pk).is_err());
+            assert!(pure
+                .verify_ecdsa(&pure_msg, &pure_signature, &pure_pk)
+                .is_err());
+            pure_signature.normalize_s();
+            assert_eq!(pure_signature.serialize_compact(), compact);
+
+            // A signature on another message verifies with neither.
+            let other = rust_secp256k1::Message::from_slice(&[1u8; 32]).unwrap();
+            let pure_other = pure_rust::Message::from_slice(&[1u8; 32]).unwrap();
+            let signature = rust_secp256k1::ecdsa::Signature::from_compact(&compact).unwrap();
+            let pure_signature = pure_rust::ecdsa::Signature::from_compact(&compact).unwrap();
+            assert!(libsecp.verify_ecdsa(&other, &signature, &pk).is_err());
+            assert!(pure
+                .verify_ecdsa(&pure_other, &pure_signature, &pure_pk)
+                .is_err());
+
+            // DER encoded signatures are parsed alike.
+            let der = signature.serialize_der();
+            assert_eq!(
+                pure_rust::ecdsa::Signature::from_der(&der)
+                    .unwrap()
+                    .serialize_compact(),
+                compact
+            );
+        }
+    }
+
+    #[test]
+    fn test_recovery_matches() {
+        let mut rng = StdRng::from_seed([3; 32]);
+        let mut digest = [0u8; 32];
+        rng.fill_bytes(&mut digest);
+        let msg = rust_secp256k1::Message::from_slice(&digest).unwrap();
+        let pure_msg = pure_rust::Message::from_slice(&digest).unwrap();
+        // Small values of r are also tried with recovery ids 2 and 3, where the x-coordinate of R
+        // is r plus the group order. k256 does not support these, so they are rejected. The zero
+        // signature is rejected by both.
+        let mut compacts = vec![[0u8; 64]];
+        for r in 1..16u8 {
+            let mut compact = [0u8; 64];
+            compact[31] = r;
+            rng.fill_bytes(&mut compact[32..]);
+            compacts.push(compact);
+        }
+        for _ in 0..16 {
+            let mut compact = [0u8; 64];
+            rng.fill_bytes(&mut compact);
+            compacts.push(compact);
+        }
+        for compact in compacts {
+            for id in 0..4 {
+                let libsecp = rust_secp256k1::ecdsa::RecoverableSignature::from_compact(
+                    &compact,
+                    rust_secp256k1::ecdsa::RecoveryId::from_i32(id).unwrap(),
+                )
+                .and_then(|signature| signature.recover(&msg));
+                let pure = pure_rust::ecdsa::RecoverableSignature::from_compact(
+                    &compact,
+                    pure_rust::ecdsa::RecoveryId::from_i32(id).unwrap(),
+                )
+                .and_then(|signature| signature.recover(&pure_msg));
+                if id >= 2 {
+                    assert!(pure.is_err());
+                    continue;
+                }
+                assert_eq!(libsecp.is_ok(), pure.is_ok());
+                if let (Ok(libsecp), Ok(pure)) = (libsecp, pure) {
+                    assert_eq!(libsecp.serialize(), pure.serialize());
+                }
+            }
+        }
+        assert!(pure_rust::ecdsa::RecoveryId::from_i32(4).is_err());
+    }
+}
@@ -3,25 +3,25 @@
 
 use super::*;
 use crate::{
+    error::FastCryptoError,
+    hash::{HashFunction, Keccak256, Sha256},
     secp256k1::{
-        Secp256k1KeyPair, Secp256k1PrivateKey, Secp256k1PublicKey, Secp256k1PublicKeyBytes,
-        Secp256k1Signature,
+        backend::{constants, ecdsa::Signature},
+        ecrecover, Secp256k1KeyPair, Secp256k1PrivateKey, Secp256k1PublicKey,
+        Secp256k1PublicKeyBytes, Secp256k1Signature, SECP256K1_PRIVATE_KEY_LENGTH,
+        SECP256K1_PUBLIC_KEY_LENGTH, SECP256K1_SIGNATURE_LENGTH,
     },
-    traits::{EncodeDecodeBase64, KeyPair, ToFromBytes, VerifyingKey},
+    traits::{EncodeDecodeBase64, FixedLength, KeyPair, ToFromBytes, VerifyingKey},
 };
 
+use base64ct::Encoding as _;
 use digest::Digest;
 use rand::{rngs::StdRng, SeedableRng as _};
-use rust_secp256k1::{constants, ecdsa::Signature};
 use signature::{Signer, Verifier};
 use wycheproof::ecdsa::{TestName::EcdsaSecp256k1Sha256, TestSet};
 
 pub fn keys() -> Vec<Secp256k1KeyPair> {
-    let mut rng = StdRng::from_seed([0; 32]);
-
-    (0..4)
-        .map(|_| Secp256k1KeyPair::generate(&mut rng))
-        .collect()
+    Secp256k1KeyPair::generate_many(4, &mut StdRng::from_seed([0; 32]))
 }
 
 #[test]
@@ -68,6 +68,264 @@ fn test_public_key_bytes_conversion() {
     let pk_bytes: Secp256k1PublicKeyBytes = kp.public().into();
     let rebuilded_pk: Secp256k1PublicKey = pk_bytes.try_into().unwrap();
     assert_eq!(kp.public().as_bytes(), rebuilded_pk.as_bytes());
+
+    // 0x00 is not a valid prefix of a compressed point.
+    let invalid_pk: Result<Secp256k1PublicKey, _> =
+        Secp256k1PublicKeyBytes::new([0u8; 33]).try_into();
+    assert!(invalid_pk.is_err());
+}
+
+#[test]
+fn test_is_valid_bytes() {
+    let kp = keys().pop().unwrap();
+    assert!(Secp256k1PublicKey::is_valid_bytes(kp.public().as_ref()));
+
+    // The uncompressed encoding is accepted, as by from_bytes, but not the hybrid encoding.
+    let mut uncompressed = kp.public().to_uncompressed();
+    assert!(Secp256k1PublicKey::is_valid_bytes(&uncompressed));
+    uncompressed[0] = 0x06 | (uncompressed[64] & 1);
+    assert!(!Secp256k1PublicKey::is_valid_bytes(&uncompressed));
+
+    // An invalid prefix, and an x-only key which has the wrong length.
+    let mut invalid_prefix = kp.public().to_compressed();
+    invalid_prefix[0] = 0x05;
+    assert!(!Secp256k1PublicKey::is_valid_bytes(&invalid_prefix));
+    assert!(!Secp256k1PublicKey::is_valid_bytes(
+        &kp.public().as_ref()[1..]
+    ));
+    assert!(!Secp256k1PublicKey::is_valid_bytes(&[0u8; 33]));
+    assert!(!Secp256k1PublicKey::is_valid_bytes(&[]));
+}
+
+#[test]
+fn test_public_key_bytes_eq() {
+    let mut kps = keys();
+    let kp = kps.pop().unwrap();
+    let other = kps.pop().unwrap();
+    let pk_bytes: Secp256k1PublicKeyBytes = kp.public().into();
+
+    assert_eq!(kp.public(), &pk_bytes);
+    assert_eq!(&pk_bytes, kp.public());
+    assert_ne!(other.public(), &pk_bytes);
+    assert_ne!(&pk_bytes, other.public());
+    assert_ne!(kp.public(), &Secp256k1PublicKeyBytes::default());
+}
+
+#[test]
+fn test_compressed_and_uncompressed_public_key() {
+    let kp = keys().pop().unwrap();
+    let pk = kp.public();
+
+    let compressed = pk.to_compressed();
+    assert_eq!(&compressed[..], pk.as_bytes());
+    let uncompressed = pk.to_uncompressed();
+    assert_eq!(uncompressed[0], 0x04);
+    assert_eq!(compressed[1..], uncompressed[1..33]);
+
+    let from_compressed = Secp256k1PublicKey::from_compressed(&compressed).unwrap();
+    let from_uncompressed = Secp256k1PublicKey::from_uncompressed(&uncompressed).unwrap();
+    assert_eq!(&from_compressed, pk);
+    assert_eq!(from_compressed, from_uncompressed);
+    assert_eq!(from_uncompressed.as_bytes(), pk.as_bytes());
+
+    // Not a point on the curve.
+    let mut invalid = uncompressed;
+    invalid[64] ^= 1;
+    assert_eq!(
+        Secp256k1PublicKey::from_uncompressed(&invalid),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // The hybrid encodings, whose prefix also encodes the parity of y, are rejected.
+    for prefix in [0x06, 0x07] {
+        let mut hybrid = uncompressed;
+        hybrid[0] = prefix;
+        assert_eq!(
+            Secp256k1PublicKey::from_uncompressed(&hybrid),
+            Err(FastCryptoError::InvalidInput)
+        );
+        assert!(Secp256k1PublicKey::from_bytes(&hybrid).is_err());
+        assert!(!Secp256k1PublicKey::is_valid_bytes(&hybrid));
+    }
+}
+
+#[test]
+fn test_from_bytes_lengths() {
+    let kp = keys().pop().unwrap();
+    let pk = kp.public();
+
+    let compressed = pk.to_compressed();
+    let uncompressed = pk.to_uncompressed();
+    assert_eq!(&Secp256k1PublicKey::from_bytes(&compressed).unwrap(), pk);
+    assert_eq!(&Secp256k1PublicKey::from_bytes(&uncompressed).unwrap(), pk);
+
+    // Only the compressed encoding is canonical.
+    assert_eq!(
+        &Secp256k1PublicKey::canonical_deserialize(&compressed).unwrap(),
+        pk
+    );
+    assert_eq!(
+        Secp256k1PublicKey::canonical_deserialize(&uncompressed).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    // An x-only key is the point with the given x-coordinate and even y-coordinate.
+    let mut x = [0u8; 32];
+    x.copy_from_slice(&compressed[1..]);
+    let x_only = Secp256k1PublicKey::from_x_only(&x).unwrap();
+    assert_eq!(x_only.to_compressed()[0], 0x02);
+    assert_eq!(x_only.to_compressed()[1..], x);
+    if compressed[0] == 0x02 {
+        assert_eq!(&x_only, pk);
+    }
+    // x = 5 is not the x-coordinate of a point on the curve.
+    let mut not_on_curve = [0u8; 32];
+    not_on_curve[31] = 5;
+    assert_eq!(
+        Secp256k1PublicKey::from_x_only(&not_on_curve),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // A 32-byte x-only key, a 34-byte input and a 66-byte input are all rejected by from_bytes.
+    for bytes in [
+        x.to_vec(),
+        [&compressed[..], &[0u8][..]].concat(),
+        [&uncompressed[..], &[0u8][..]].concat(),
+    ] {
+        let err = Secp256k1PublicKey::from_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            FastCryptoError::from_signature_error(&err),
+            Some(&FastCryptoError::InputLengthWrong {
+                expected: Secp256k1PublicKey::LENGTH,
+                got: bytes.len()
+            })
+        );
+    }
+}
+
+#[test]
+fn test_add_tweak() {
+    let kp = keys().pop().unwrap();
+    let pk = kp.public().clone();
+    let sk = kp.private();
+    let tweak = [7u8; 32];
+
+    let tweaked_kp = Secp256k1KeyPair::from(sk.add_tweak(&tweak).unwrap());
+    let tweaked_pk = pk.add_tweak(&tweak).unwrap();
+    assert_eq!(tweaked_kp.public(), &tweaked_pk);
+    assert_ne!(tweaked_pk, pk);
+
+    // The tweaked key signs like any other key.
+    let signature = tweaked_kp.sign(b"Hello, world!");
+    assert!(tweaked_pk.verify(b"Hello, world!", &signature).is_ok());
+
+    // Tweaks not smaller than the group order are rejected.
+    assert!(matches!(
+        sk.add_tweak(&[0xff; 32]),
+        Err(FastCryptoError::InvalidInput)
+    ));
+    assert_eq!(
+        pk.add_tweak(&[0xff; 32]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_aggregate_musig() {
+    // The key aggregation test vectors from BIP-327. The expected keys are x-only there, and the
+    // parity of the aggregate keys was computed with a reference implementation.
+    let keys: Vec<Secp256k1PublicKey> = [
+        "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+        "03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+        "023590a94e768f8e1815c2f24b4d80a8e3149316c3518ce7b7ad338368d038ca66",
+    ]
+    .iter()
+    .map(|pk| Secp256k1PublicKey::from_bytes(&hex::decode(pk).unwrap()).unwrap())
+    .collect();
+    let vectors: [(&[usize], &str); 4] = [
+        (
+            &[0, 1, 2],
+            "0290539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c",
+        ),
+        (
+            &[2, 1, 0],
+            "036204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b",
+        ),
+        (
+            &[0, 0, 0],
+            "02b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935",
+        ),
+        (
+            &[0, 0, 1, 1],
+            "0369bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e",
+        ),
+    ];
+    for (indices, expected) in vectors {
+        let list: Vec<_> = indices.iter().map(|i| keys[*i].clone()).collect();
+        let aggregate = Secp256k1PublicKey::aggregate_musig(&list).unwrap();
+        assert_eq!(hex::encode(aggregate.as_ref()), expected);
+    }
+
+    // A single key is multiplied by its coefficient, so the aggregate key differs from it.
+    let aggregate = Secp256k1PublicKey::aggregate_musig(&keys[..1]).unwrap();
+    assert_ne!(aggregate, keys[0]);
+
+    assert_eq!(
+        Secp256k1PublicKey::aggregate_musig(&[]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_add_tweak_to_zero() {
+    // Tweaking the key 1 by n - 1 gives zero, which is rejected.
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let sk = Secp256k1PrivateKey::from_bytes(&one).unwrap();
+    let pk = Secp256k1KeyPair::from(Secp256k1PrivateKey::from_bytes(&one).unwrap())
+        .public()
+        .clone();
+    let minus_one =
+        hex_literal::hex!("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+
+    assert!(matches!(
+        sk.add_tweak(&minus_one),
+        Err(FastCryptoError::InvalidInput)
+    ));
+    assert_eq!(pk.add_tweak(&minus_one), Err(FastCryptoError::InvalidInput));
+}
+
+#[test]
+fn test_diffie_hellman() {
+    let kps = keys();
+    let (alice, bob) = (&kps[0], &kps[1]);
+    let alice_secret = alice.private().diffie_hellman(bob.public());
+    let bob_secret = bob.private().diffie_hellman(alice.public());
+    assert_eq!(alice_secret.as_bytes(), bob_secret.as_bytes());
+
+    let eve_secret = kps[2].private().diffie_hellman(bob.public());
+    assert_ne!(alice_secret.as_bytes(), eve_secret.as_bytes());
+}
+
+#[test]
+fn test_diffie_hellman_vector() {
+    // 2 * (3 * G) = 6 * G, and the secret is the SHA-256 hash of its compressed encoding.
+    let mut two = [0u8; 32];
+    two[31] = 2;
+    let sk = Secp256k1PrivateKey::from_bytes(&two).unwrap();
+    let pk = Secp256k1PublicKey::from_bytes(
+        &hex::decode("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9").unwrap(),
+    )
+    .unwrap();
+    let six_g =
+        hex::decode("03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556").unwrap();
+
+    let secret = sk.diffie_hellman(&pk);
+    assert_eq!(secret.as_bytes()[..], sha2::Sha256::digest(six_g)[..]);
+    assert_eq!(
+        hex::encode(secret.as_bytes()),
+        "c7d9ba2fa1496c81be20038e5c608f2fd5d0246d8643783730df6c2bbb855cb2"
+    );
 }
 
 #[test]
@@ -81,6 +339,66 @@ fn test_public_key_recovery() {
     assert_eq!(*kp.public(), recovered_key);
 }
 
+#[test]
+fn test_split_signature() {
+    let message: &[u8] = b"Hello, world!";
+    for kp in keys() {
+        let signature = kp.sign(message);
+        let (compact, recovery_id) = signature.split();
+        assert_eq!(compact[..], signature.as_ref()[..64]);
+        assert_eq!(recovery_id, signature.as_ref()[64]);
+
+        let rebuilt = Secp256k1Signature::from_parts(&compact, recovery_id).unwrap();
+        assert_eq!(rebuilt, signature);
+        assert!(kp.public().verify(message, &rebuilt).is_ok());
+
+        // Recovery ids 2 and 3 are valid but recover a different key, 4 and above are rejected.
+        for recovery_id in 0..4 {
+            assert!(Secp256k1Signature::from_parts(&compact, recovery_id).is_ok());
+        }
+        for recovery_id in [4, 27, 255] {
+            assert_eq!(
+                Secp256k1Signature::from_parts(&compact, recovery_id),
+                Err(FastCryptoError::InvalidInput)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_verify_and_find_recovery_id() {
+    let digest = Keccak256::digest(b"Hello, world!");
+    let kps = keys();
+    for (i, kp) in kps.iter().enumerate() {
+        let (compact, recovery_id) = kp.sign_digest(&digest).split();
+        assert_eq!(
+            kp.public().verify_and_find_recovery_id(&digest, &compact),
+            Some(recovery_id)
+        );
+
+        // Another key, another digest or a modified signature is rejected.
+        let other = &kps[(i + 1) % kps.len()];
+        assert_eq!(
+            other
+                .public()
+                .verify_and_find_recovery_id(&digest, &compact),
+            None
+        );
+        let other_digest = Keccak256::digest(b"Goodbye, world!");
+        assert_eq!(
+            kp.public()
+                .verify_and_find_recovery_id(&other_digest, &compact),
+            None
+        );
+        let mut modified = compact;
+        modified[63] ^= 1;
+        assert_eq!(
+            kp.public().verify_and_find_recovery_id(&digest, &modified),
+            None
+        );
+    }
+}
+
 #[test]
 fn test_public_key_recovery_error() {
     // incorrect length
@@ -99,6 +417,132 @@ fn test_public_key_recovery_error() {
     let signature_2: Secp256k1Signature = kp.sign(message);
     assert!(signature_2.recover(message).is_err());
 }
+
+#[test]
+fn test_verify_any() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let message: &[u8] = b"Hello, world!";
+
+    let signature = kps[2].sign(message);
+    assert_eq!(
+        Secp256k1PublicKey::verify_any(&pks, message, &signature),
+        Ok(2)
+    );
+    assert_eq!(
+        Secp256k1PublicKey::verify_any(&pks[..2], message, &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    assert_eq!(
+        Secp256k1PublicKey::verify_any(&pks, b"Other message", &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    assert_eq!(
+        Secp256k1PublicKey::verify_any(&[], message, &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+}
+
+#[test]
+fn test_canonicalize() {
+    use k256::elliptic_curve::PrimeField;
+
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let signature: Secp256k1Signature = kp.sign(message);
+    assert!(signature.is_canonical());
+
+    let bytes = signature.as_ref();
+    let s = k256::Scalar::from_repr(*k256::FieldBytes::from_slice(&bytes[32..64])).unwrap();
+    let mut high_s = bytes.to_vec();
+    high_s[32..64].copy_from_slice(&(-s).to_bytes());
+    high_s[64] ^= 1;
+    let mut malleated = Secp256k1Signature::from_bytes_unchecked(&high_s).unwrap();
+    assert!(!malleated.is_canonical());
+    assert_eq!(malleated.as_ref(), &high_s[..]);
+
+    // Canonicalizing gives back the original signature, which still recovers the same key, and
+    // doing it again changes nothing.
+    malleated.canonicalize();
+    assert!(malleated.is_canonical());
+    assert_eq!(malleated.as_ref(), signature.as_ref());
+    assert!(kp.public().verify(message, &malleated).is_ok());
+    malleated.canonicalize();
+    assert_eq!(malleated.as_ref(), signature.as_ref());
+    assert_eq!(
+        bcs::to_bytes(&malleated).unwrap(),
+        bcs::to_bytes(&signature).unwrap()
+    );
+}
+
+#[test]
+fn test_reject_non_canonical_signatures() {
+    use k256::elliptic_curve::PrimeField;
+
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let signature: Secp256k1Signature = kp.sign(message);
+    let bytes = signature.as_ref();
+
+    // Replacing s with n - s and flipping the recovery id gives another valid signature.
+    let s = k256::Scalar::from_repr(*k256::FieldBytes::from_slice(&bytes[32..64])).unwrap();
+    let mut high_s = bytes.to_vec();
+    high_s[32..64].copy_from_slice(&(-s).to_bytes());
+    high_s[64] ^= 1;
+    assert!(<Secp256k1Signature as ToFromBytes>::from_bytes(&high_s).is_err());
+    let unchecked = Secp256k1Signature::from_bytes_unchecked(&high_s).unwrap();
+    assert!(kp.public().verify(message, &unchecked).is_ok());
+
+    // Components which are not smaller than the group order are rejected by both parsers.
+    let order =
+        hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+    let mut overflowing_r = bytes.to_vec();
+    overflowing_r[..32].copy_from_slice(&order);
+    assert!(<Secp256k1Signature as ToFromBytes>::from_bytes(&overflowing_r).is_err());
+    assert!(Secp256k1Signature::from_bytes_unchecked(&overflowing_r).is_err());
+
+    // So are overlong encodings, e.g. with a zero byte prepended to a component.
+    let mut overlong = vec![0u8];
+    overlong.extend_from_slice(bytes);
+    assert!(<Secp256k1Signature as ToFromBytes>::from_bytes(&overlong).is_err());
+    assert!(Secp256k1Signature::from_bytes_unchecked(&overlong).is_err());
+}
+
+#[test]
+fn test_from_bytes_normalized() {
+    use k256::elliptic_curve::PrimeField;
+
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let signature: Secp256k1Signature = kp.sign(message);
+    let bytes = signature.as_ref();
+
+    // A signature in the low-S form is parsed unchanged.
+    let normalized = Secp256k1Signature::from_bytes_normalized(bytes).unwrap();
+    assert_eq!(normalized.as_ref(), bytes);
+
+    // A signature in the high-S form is rejected by the strict parser, while the normalized
+    // parser gives the equivalent low-S signature, which still verifies.
+    let s = k256::Scalar::from_repr(*k256::FieldBytes::from_slice(&bytes[32..64])).unwrap();
+    let mut high_s = bytes.to_vec();
+    high_s[32..64].copy_from_slice(&(-s).to_bytes());
+    high_s[64] ^= 1;
+    assert!(<Secp256k1Signature as ToFromBytes>::from_bytes(&high_s).is_err());
+    let normalized = Secp256k1Signature::from_bytes_normalized(&high_s).unwrap();
+    assert!(normalized.is_canonical());
+    assert_eq!(normalized.as_ref(), bytes);
+    assert_eq!(normalized, signature);
+    assert!(kp.public().verify(message, &normalized).is_ok());
+    assert!(<Secp256k1Signature as ToFromBytes>::from_bytes(normalized.as_ref()).is_ok());
+
+    // Invalid encodings are still rejected.
+    let order =
+        hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+    let mut overflowing_s = bytes.to_vec();
+    overflowing_s[32..64].copy_from_slice(&order);
+    assert!(Secp256k1Signature::from_bytes_normalized(&overflowing_s).is_err());
+    assert!(Secp256k1Signature::from_bytes_normalized(&bytes[..64]).is_err());
+}
 #[test]
 fn import_export_secret_key() {
     let kpref = keys().pop().unwrap();
@@ -109,6 +553,14 @@ fn import_export_secret_key() {
     assert_eq!(import.unwrap().as_ref(), secret_key.as_ref());
 }
 
+#[test]
+fn test_keys_reproducible_from_seed() {
+    let kp1 = Secp256k1KeyPair::generate(&mut StdRng::from_seed([7; 32]));
+    let kp2 = Secp256k1KeyPair::generate(&mut StdRng::from_seed([7; 32]));
+    assert_eq!(kp1.public(), kp2.public());
+    assert_eq!(kp1.private().as_ref(), kp2.private().as_ref());
+}
+
 #[test]
 fn test_copy_key_pair() {
     let kp = keys().pop().unwrap();
@@ -118,6 +570,26 @@ fn test_copy_key_pair() {
     assert_eq!(kp.private().as_bytes(), kp_copied.private().as_bytes());
 }
 
+#[test]
+fn test_into_and_from_parts() {
+    let mut keys = keys();
+    let other = keys.pop().unwrap();
+    let kp = keys.pop().unwrap();
+    let public = kp.public().clone();
+
+    let (pk, sk) = kp.into_parts();
+    assert_eq!(pk, public);
+    let kp = Secp256k1KeyPair::from_parts(pk, sk).unwrap();
+    assert_eq!(kp.public(), &public);
+
+    // The public key must belong to the private key.
+    let (_, sk) = kp.into_parts();
+    assert_eq!(
+        Secp256k1KeyPair::from_parts(other.public().clone(), sk).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
+
 #[test]
 fn to_from_bytes_signature() {
     let kpref = keys().pop().unwrap();
@@ -127,6 +599,90 @@ fn to_from_bytes_signature() {
     assert_eq!(rebuilt_sig.as_ref(), signature.as_ref())
 }
 
+#[test]
+fn test_try_from_bytes() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    let pk: Secp256k1PublicKey = kp.public().as_ref().try_into().unwrap();
+    assert_eq!(&pk, kp.public());
+    let sig: Secp256k1Signature = signature.as_ref().try_into().unwrap();
+    assert_eq!(sig, signature);
+    let sk_bytes = kp.private().as_ref().to_vec();
+    let sk: Secp256k1PrivateKey = sk_bytes.as_slice().try_into().unwrap();
+    assert_eq!(sk.as_ref(), &sk_bytes[..]);
+
+    // Wrong lengths are rejected.
+    assert!(Secp256k1PublicKey::try_from(&pk.as_ref()[1..]).is_err());
+    assert!(Secp256k1Signature::try_from(&sig.as_ref()[1..]).is_err());
+    assert!(Secp256k1PrivateKey::try_from(&sk_bytes[1..]).is_err());
+}
+
+#[test]
+fn test_fixed_lengths() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+    assert_eq!(kp.public().as_ref().len(), Secp256k1PublicKey::LENGTH);
+    assert_eq!(signature.as_ref().len(), Secp256k1Signature::LENGTH);
+    assert_eq!(kp.private().as_ref().len(), Secp256k1PrivateKey::LENGTH);
+
+    let err = Secp256k1PublicKey::from_bytes(&[0u8; 1]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InputLengthWrong {
+            expected: Secp256k1PublicKey::LENGTH,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn test_error_variants() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    // A well-formed signature on another message.
+    let err = kp.public().verify(b"Bad message!", &signature).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InvalidSignature)
+    );
+
+    // A truncated signature.
+    let err =
+        <Secp256k1Signature as ToFromBytes>::from_bytes(&signature.as_ref()[1..]).unwrap_err();
+    assert_eq!(
+        FastCryptoError::from_signature_error(&err),
+        Some(&FastCryptoError::InputLengthWrong {
+            expected: Secp256k1Signature::LENGTH,
+            got: Secp256k1Signature::LENGTH - 1
+        })
+    );
+}
+
+#[test]
+fn test_display_from_str() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+
+    let pk = format!("{}", kp.public())
+        .parse::<Secp256k1PublicKey>()
+        .unwrap();
+    assert_eq!(&pk, kp.public());
+    let sig = format!("{}", signature)
+        .parse::<Secp256k1Signature>()
+        .unwrap();
+    assert_eq!(sig, signature);
+    let sk = kp.private();
+    let parsed_sk = sk.encode_base64().parse::<Secp256k1PrivateKey>().unwrap();
+    assert_eq!(parsed_sk.as_ref(), sk.as_ref());
+
+    assert_eq!(
+        "not base64!".parse::<Secp256k1PublicKey>(),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
 #[test]
 fn verify_valid_signature() {
     // Get a keypair.
@@ -136,10 +692,10 @@ fn verify_valid_signature() {
     let message: &[u8] = b"Hello, world!";
     let digest = message.digest();
 
-    let signature = kp.sign(&digest.0);
+    let signature = kp.sign(digest.as_ref());
 
     // Verify the signature.
-    assert!(kp.public().verify(&digest.0, &signature).is_ok());
+    assert!(kp.public().verify(digest.as_ref(), &signature).is_ok());
 }
 
 #[test]
@@ -161,6 +717,240 @@ fn verify_valid_signature_against_hashed_msg() {
         .is_ok());
 }
 
+#[test]
+fn sign_digest_matches_sign() {
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+    let digest = Keccak256::digest(message);
+
+    // Signing the digest directly is the same as signing the message (hashed internally).
+    let signature = kp.sign_digest(&digest);
+    assert_eq!(signature, kp.sign(message));
+    assert!(kp.public().verify_digest(&digest, &signature).is_ok());
+    assert!(kp.public().verify(message, &signature).is_ok());
+
+    // Signing the digest of a different message does not verify.
+    let other_digest = Keccak256::digest(b"Bad message!");
+    assert!(kp
+        .public()
+        .verify_digest(&other_digest, &signature)
+        .is_err());
+}
+
+#[test]
+fn sign_with_hash_function() {
+    let message: &[u8] = b"Hello, world!";
+    for kp in keys() {
+        let signature = kp.sign_with::<Sha256>(message);
+        assert_eq!(signature, kp.sign_digest(&Sha256::digest(message)));
+        assert!(kp
+            .public()
+            .verify_with::<Sha256>(message, &signature)
+            .is_ok());
+        assert!(kp
+            .public()
+            .verify_with::<Keccak256>(message, &signature)
+            .is_err());
+        assert_ne!(signature, kp.sign_with::<Keccak256>(message));
+
+        // A standard ECDSA verifier hashing the message with SHA-256 accepts the signature.
+        let k256_pk = k256::ecdsa::VerifyingKey::from(kp.public());
+        let k256_signature = k256::ecdsa::Signature::try_from(&signature.as_ref()[..64]).unwrap();
+        assert!(k256_pk.verify(message, &k256_signature).is_ok());
+    }
+}
+
+#[test]
+fn recover_with_hash_function() {
+    let message: &[u8] = b"Hello, world!";
+    for kp in keys() {
+        // A signature made with SHA-256 recovers the signer from the SHA-256 digest.
+        let signature = kp.sign_with::<Sha256>(message);
+        let recovered = signature.recover(Sha256::digest(message).as_ref()).unwrap();
+        assert_eq!(&recovered, kp.public());
+
+        // The digest under another hash gives another key.
+        let recovered = signature.recover(Keccak256::digest(message).as_ref());
+        assert!(recovered.map_or(true, |pk| &pk != kp.public()));
+
+        // The same holds for the default Keccak-256 signatures.
+        let signature = kp.sign(message);
+        let recovered = signature
+            .recover(Keccak256::digest(message).as_ref())
+            .unwrap();
+        assert_eq!(&recovered, kp.public());
+        let recovered = signature.recover(Sha256::digest(message).as_ref());
+        assert!(recovered.map_or(true, |pk| &pk != kp.public()));
+    }
+}
+
+#[test]
+fn sign_is_deterministic() {
+    let kp = keys().pop().unwrap();
+    let message: &[u8] = b"Hello, world!";
+
+    let signature = kp.sign_deterministic(message);
+    assert_eq!(signature.as_ref(), kp.sign_deterministic(message).as_ref());
+    assert_eq!(signature.as_ref(), kp.sign(message).as_ref());
+}
+
+#[test]
+fn sign_deterministic_rfc6979_test_vector() {
+    // Private key 1 and SHA-256("Satoshi Nakamoto"), with the nonce derived as in RFC 6979.
+    let mut sk_bytes = [0u8; 32];
+    sk_bytes[31] = 1;
+    let kp = Secp256k1KeyPair::from(Secp256k1PrivateKey::from_bytes(&sk_bytes).unwrap());
+    let digest = Sha256::digest(b"Satoshi Nakamoto");
+
+    let signature = kp.sign_digest(&digest);
+    assert_eq!(
+        signature.as_ref()[..64],
+        hex_literal::hex!(
+            "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8"
+            "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"
+        )
+    );
+}
+
+#[test]
+fn ecrecover_eip155_test_vector() {
+    // The example transaction from EIP-155, signed with the private key 0x4646...46. Its v = 37
+    // encodes the chain id 1 and the recovery id 0.
+    let hash =
+        hex_literal::hex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+    let r = hex_literal::hex!("28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276");
+    let s = hex_literal::hex!("67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83");
+    let address = hex_literal::hex!("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f");
+
+    let kp = Secp256k1KeyPair::from(Secp256k1PrivateKey::from_bytes(&[0x46; 32]).unwrap());
+    let signature = kp.sign_digest(&crate::hash::Digest::new(hash));
+    assert_eq!(signature.as_ref(), [&r[..], &s[..], &[0]].concat());
+
+    assert_eq!(ecrecover(&hash, 27, &r, &s), Some(address));
+    assert_ne!(ecrecover(&hash, 28, &r, &s), Some(address));
+    assert_ne!(ecrecover(&[0u8; 32], 27, &r, &s), Some(address));
+
+    // Only 27 and 28 are valid values of v.
+    for v in [0, 1, 26, 29, 37] {
+        assert_eq!(ecrecover(&hash, v, &r, &s), None);
+    }
+
+    // (r, n - s) with the other recovery id is also a valid signature for the same key, but it is
+    // in the high-S form and is rejected.
+    let n = hex_literal::hex!("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+    let mut high_s = [0u8; 32];
+    let mut borrow = 0i16;
+    for ((high_s_i, n_i), s_i) in high_s.iter_mut().zip(&n).zip(&s).rev() {
+        let diff = *n_i as i16 - *s_i as i16 - borrow;
+        *high_s_i = diff.rem_euclid(256) as u8;
+        borrow = (diff < 0) as i16;
+    }
+    let malleated =
+        Secp256k1Signature::from_bytes_unchecked(&[&r[..], &high_s[..], &[1]].concat()).unwrap();
+    assert_eq!(malleated.recover(&hash).unwrap(), *kp.public());
+    assert_eq!(ecrecover(&hash, 28, &r, &high_s), None);
+
+    // The zero signature is invalid.
+    assert_eq!(ecrecover(&hash, 27, &[0u8; 32], &[0u8; 32]), None);
+}
+
+#[test]
+fn recover_compact_bitcoin_test_vector() {
+    // A signmessage signature by the private key sha256(""), from the bitcoinjs-message
+    // documentation. The header 31 = 27 + 4 means recovery id 0 and a compressed key.
+    let message = b"This is an example of a signed message.";
+    let signature: [u8; 65] = base64ct::Base64::decode_vec(
+        "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=",
+    )
+    .unwrap()
+    .try_into()
+    .unwrap();
+    let pk =
+        hex_literal::hex!("03a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd");
+    let kp = Secp256k1KeyPair::from(
+        Secp256k1PrivateKey::from_bytes(Sha256::digest(b"").as_ref()).unwrap(),
+    );
+    assert_eq!(kp.public().as_bytes(), pk);
+
+    let recovered = Secp256k1PublicKey::recover_compact(&signature, message).unwrap();
+    assert_eq!(&recovered, kp.public());
+
+    // The same signature with the header for an uncompressed key recovers the same key.
+    let mut uncompressed = signature;
+    uncompressed[0] = 27;
+    assert_eq!(
+        &Secp256k1PublicKey::recover_compact(&uncompressed, message).unwrap(),
+        kp.public()
+    );
+
+    // Another message or recovery id gives another key.
+    assert_ne!(
+        &Secp256k1PublicKey::recover_compact(&signature, b"Another message.").unwrap(),
+        kp.public()
+    );
+    let mut other_recovery_id = signature;
+    other_recovery_id[0] = 32;
+    assert_ne!(
+        Secp256k1PublicKey::recover_compact(&other_recovery_id, message).ok(),
+        Some(kp.public().clone())
+    );
+
+    // Only headers from 27 to 34 are valid.
+    for header in [0, 26, 35, 255] {
+        let mut invalid = signature;
+        invalid[0] = header;
+        assert_eq!(
+            Secp256k1PublicKey::recover_compact(&invalid, message),
+            Err(FastCryptoError::InvalidInput)
+        );
+    }
+}
+
+#[test]
+fn eth_rsv_hex_test_vector() {
+    // The output of eth_sign for the message "Some data" with the private key 0x4c08...2318, from
+    // the web3.js documentation.
+    let rsv = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+    let sk = hex_literal::hex!("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+    let message = b"Some data";
+    let hash = Keccak256::digest(
+        [
+            format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes(),
+            &message[..],
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        hash.as_ref(),
+        hex_literal::hex!("1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655")
+    );
+
+    let signature = Secp256k1Signature::from_eth_rsv_hex(rsv).unwrap();
+    assert_eq!(signature.split().1, 1);
+    assert_eq!(signature.to_eth_rsv_hex(), rsv);
+
+    let kp = Secp256k1KeyPair::from(Secp256k1PrivateKey::from_bytes(&sk).unwrap());
+    assert_eq!(signature.recover(hash.as_ref()).unwrap(), *kp.public());
+    assert_eq!(kp.sign_digest(&hash), signature);
+
+    // Missing prefix, wrong lengths, invalid hex and invalid values of v are rejected.
+    let (prefix, hex) = rsv.split_at(2);
+    let v_at = |v: &str| format!("{}{}{}", prefix, &hex[..128], v);
+    for invalid in [
+        hex.to_string(),
+        rsv[..rsv.len() - 2].to_string(),
+        format!("{}00", rsv),
+        rsv.replace('b', "g"),
+        v_at("00"),
+        v_at("01"),
+        v_at("1d"),
+        v_at("25"),
+    ] {
+        assert!(Secp256k1Signature::from_eth_rsv_hex(&invalid).is_err());
+    }
+    assert!(Secp256k1Signature::from_eth_rsv_hex(&v_at("1b")).is_ok());
+}
+
 fn signature_test_inputs() -> (Vec<u8>, Vec<Secp256k1PublicKey>, Vec<Secp256k1Signature>) {
     // Make signatures.
     let message: &[u8] = b"Hello, world!";
@@ -169,7 +959,7 @@ fn signature_test_inputs() -> (Vec<u8>, Vec<Secp256k1PublicKey>, Vec<Secp256k1Si
         .into_iter()
         .take(3)
         .map(|kp| {
-            let sig = kp.sign(&digest.0);
+            let sig = kp.sign(digest.as_ref());
             (kp.public().clone(), sig)
         })
         .unzip();
@@ -185,6 +975,82 @@ fn verify_valid_batch() {
     assert!(res.is_ok(), "{:?}", res);
 }
 
+#[test]
+fn verify_batch_digest_matches_per_signature_verification() {
+    let (message, pubkeys, mut signatures) = signature_test_inputs();
+    let digest = Keccak256::digest(&message);
+
+    assert!(Secp256k1PublicKey::verify_batch_digest(&digest, &pubkeys, &signatures).is_ok());
+    assert!(pubkeys
+        .iter()
+        .zip(&signatures)
+        .all(|(pk, sig)| pk.verify(&message, sig).is_ok()));
+
+    // Both fail if one of the signatures is invalid.
+    signatures[1] = keys()[3].sign(&message);
+    assert_eq!(
+        Secp256k1PublicKey::verify_batch_digest(&digest, &pubkeys, &signatures),
+        Err(FastCryptoError::InvalidSignature)
+    );
+    assert!(pubkeys[1].verify(&message, &signatures[1]).is_err());
+    assert!(Secp256k1PublicKey::verify_batch_empty_fail(&message, &pubkeys, &signatures).is_err());
+
+    assert_eq!(
+        Secp256k1PublicKey::verify_batch_digest(&digest, &pubkeys[1..], &signatures),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        Secp256k1PublicKey::verify_batch_digest(&digest, &[], &[]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn verify_batch_single_signer() {
+    let kp = keys().pop().unwrap();
+    let messages: Vec<Vec<u8>> = (0..10u8)
+        .map(|i| format!("log entry {}", i).into_bytes())
+        .collect();
+    let msgs: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+    let mut sigs: Vec<Secp256k1Signature> = msgs.iter().map(|msg| kp.sign(msg)).collect();
+    assert!(kp.public().verify_batch_single_signer(&msgs, &sigs).is_ok());
+
+    // Signatures by another key are rejected.
+    let other = keys().remove(0);
+    assert_eq!(
+        other.public().verify_batch_single_signer(&msgs, &sigs),
+        Err(FastCryptoError::InvalidBatchElement { index: 0 })
+    );
+
+    // A single bad signature is detected at its position, e.g. when two signatures are swapped.
+    sigs.swap(3, 4);
+    assert_eq!(
+        kp.public().verify_batch_single_signer(&msgs, &sigs),
+        Err(FastCryptoError::InvalidBatchElement { index: 3 })
+    );
+    sigs.swap(3, 4);
+    sigs[7] = kp.sign(b"another message");
+    assert_eq!(
+        kp.public().verify_batch_single_signer(&msgs, &sigs),
+        Err(FastCryptoError::InvalidBatchElement { index: 7 })
+    );
+
+    // Empty batches and batches of different lengths are invalid.
+    assert_eq!(
+        kp.public()
+            .verify_batch_single_signer(&msgs[..7], &sigs[..8]),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        kp.public().verify_batch_single_signer(&msgs, &sigs[..9]),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        kp.public().verify_batch_single_signer(&[], &[]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
 #[test]
 fn verify_invalid_batch() {
     let (digest, pubkeys, mut signatures) = signature_test_inputs();
@@ -243,16 +1109,17 @@ fn verify_invalid_signature() {
     let digest = message.digest();
 
     // Verify the signature against good digest passes.
-    let signature = kp.sign(&digest.0);
-    assert!(kp.public().verify(&digest.0, &signature).is_ok());
+    let signature = kp.sign(digest.as_ref());
+    assert!(kp.public().verify(digest.as_ref(), &signature).is_ok());
 
     // Verify the signature against bad digest fails.
     let bad_message: &[u8] = b"Bad message!";
     let digest = bad_message.digest();
 
-    assert!(kp.public().verify(&digest.0, &signature).is_err());
+    assert!(kp.public().verify(digest.as_ref(), &signature).is_err());
 }
 
+#[cfg(feature = "signature-service")]
 #[tokio::test]
 async fn signature_service() {
     // Get a keypair.
@@ -271,6 +1138,20 @@ async fn signature_service() {
     assert!(pk.verify(digest.as_ref(), &signature).is_ok());
 }
 
+#[test]
+fn test_private_key_debug_is_redacted() {
+    let kp = keys().pop().unwrap();
+    let encoded = kp.private().encode_base64();
+
+    let debug = format!("{:?}", kp.private());
+    assert_eq!(debug, "Secp256k1PrivateKey(<redacted>)");
+    assert!(!format!("{:?}", kp).contains(&encoded));
+
+    let copy = Secp256k1PrivateKey::from_bytes(kp.private().as_ref()).unwrap();
+    assert_eq!(&copy, kp.private());
+    assert_ne!(&copy, keys()[0].private());
+}
+
 #[test]
 fn test_sk_zeroization_on_drop() {
     let ptr: *const u8;
@@ -307,6 +1188,108 @@ fn test_sk_zeroization_on_drop() {
     assert_ne!(sk_memory, &sk_bytes[..]);
 }
 
+// Unlike the proptest below, this does not need the copy_key feature, so it runs under any set of
+// features.
+#[test]
+fn test_signatures_match_k256() {
+    let messages: [&[u8]; 3] = [b"", b"Hello, world!", &[0xff; 1000]];
+    for kp in keys() {
+        let k256_sk = k256::ecdsa::SigningKey::from_bytes(kp.private().as_ref()).unwrap();
+        assert_eq!(
+            kp.public().as_ref(),
+            k256_sk.verifying_key().to_bytes().as_slice()
+        );
+
+        for message in messages {
+            // Both sign the Keccak256 digest of the message with an RFC 6979 nonce.
+            let signature = kp.sign(message);
+            let k256_signature: k256::ecdsa::recoverable::Signature = k256_sk.sign(message);
+            assert_eq!(signature.as_ref(), k256_signature.as_ref());
+
+            let digest = Keccak256::digest(message);
+            assert_eq!(kp.sign_digest(&digest).as_ref(), k256_signature.as_ref());
+
+            // Each verifies the signature of the other.
+            let converted = Secp256k1Signature::try_from(&k256_signature).unwrap();
+            assert!(kp.public().verify(message, &converted).is_ok());
+            let converted = k256::ecdsa::recoverable::Signature::try_from(&signature).unwrap();
+            assert!(k256_sk.verifying_key().verify(message, &converted).is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_k256_conversions() {
+    let message: &[u8] = b"Hello, world!";
+    for kp in keys() {
+        let k256_pk = k256::ecdsa::VerifyingKey::from(kp.public());
+        assert_eq!(k256_pk.to_bytes().as_slice(), kp.public().as_ref());
+        assert_eq!(Secp256k1PublicKey::from(&k256_pk), *kp.public());
+
+        let signature = kp.sign(message);
+        let k256_signature = k256::ecdsa::recoverable::Signature::try_from(&signature).unwrap();
+        assert_eq!(k256_signature.as_ref(), signature.as_ref());
+        assert_eq!(
+            Secp256k1Signature::try_from(&k256_signature).unwrap(),
+            signature
+        );
+
+        // Verification works across the boundary.
+        assert!(k256_pk.verify(message, &k256_signature).is_ok());
+        let recovered = k256_signature.recover_verifying_key(message).unwrap();
+        assert_eq!(Secp256k1PublicKey::from(&recovered), *kp.public());
+    }
+
+    // Signatures with a zero r and s are parsed, but rejected by k256.
+    let zero = <Secp256k1Signature as ToFromBytes>::from_bytes(&[0u8; 65]).unwrap();
+    assert!(k256::ecdsa::recoverable::Signature::try_from(&zero).is_err());
+}
+
+#[test]
+fn test_verify_compact() {
+    use k256::elliptic_curve::PrimeField;
+    use signature::DigestSigner;
+
+    let message: &[u8] = b"Hello, world!";
+    let other = keys().remove(0);
+    for kp in keys().into_iter().skip(1) {
+        // A plain ECDSA signature over the Keccak256 digest of the message, without recovery id.
+        let k256_sk = k256::ecdsa::SigningKey::from_bytes(kp.private().as_ref()).unwrap();
+        let k256_signature: k256::ecdsa::Signature =
+            k256_sk.sign_digest(<sha3::Keccak256 as Digest>::new_with_prefix(message));
+        let sig64: [u8; 64] = k256_signature.as_ref().try_into().unwrap();
+        assert!(kp.public().verify_compact(message, &sig64).is_ok());
+        assert_eq!(
+            kp.public().verify_compact(b"Goodbye", &sig64),
+            Err(FastCryptoError::InvalidSignature)
+        );
+        assert!(other.public().verify_compact(message, &sig64).is_err());
+
+        // Our own signatures verify without their recovery id.
+        let signature = kp.sign(message);
+        assert_eq!(signature.as_ref()[..64], sig64);
+        let sig64: [u8; 64] = signature.as_ref()[..64].try_into().unwrap();
+        assert!(kp.public().verify_compact(message, &sig64).is_ok());
+
+        // The high-S form of the signature is rejected.
+        let mut high_s = sig64;
+        let s = k256::Scalar::from_repr(*k256::FieldBytes::from_slice(&sig64[32..])).unwrap();
+        high_s[32..].copy_from_slice(&(-s).to_bytes());
+        assert!(kp.public().verify_compact(message, &high_s).is_err());
+    }
+
+    // Components which are not smaller than the group order are rejected when parsing.
+    let kp = keys().pop().unwrap();
+    let order =
+        hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").unwrap();
+    let mut overflowing_s = [1u8; 64];
+    overflowing_s[32..].copy_from_slice(&order);
+    assert_eq!(
+        kp.public().verify_compact(message, &overflowing_s),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
 use proptest::arbitrary::Arbitrary;
 use wycheproof::TestResult;
 
@@ -316,7 +1299,7 @@ proptest::proptest! {
         r in <[u8; 32]>::arbitrary()
 ) {
         let message: &[u8] = b"hello world!";
-        let hashed_msg = rust_secp256k1::Message::from_slice(<sha3::Keccak256 as sha3::digest::Digest>::digest(message).as_slice()).unwrap();
+        let hashed_msg = crate::secp256k1::backend::Message::from_slice(<sha3::Keccak256 as sha3::digest::Digest>::digest(message).as_slice()).unwrap();
 
         // contruct private key with bytes and signs message
         let priv_key = <Secp256k1PrivateKey as ToFromBytes>::from_bytes(&r).unwrap();
@@ -327,15 +1310,8 @@ proptest::proptest! {
         assert!(key_pair.public().verify(message, &signature).is_ok());
 
         // construct a signature with r, s, v where v is flipped from the original signature.
-        let bytes = ToFromBytes::as_bytes(&signature);
-        let mut flipped_bytes = [0u8; 65];
-        flipped_bytes[..64].copy_from_slice(&bytes[..64]);
-        if bytes[64] == 0 {
-            flipped_bytes[64] = 1;
-        } else {
-            flipped_bytes[64] = 0;
-        }
-        let malleated_signature: Secp256k1Signature = <Secp256k1Signature as signature::Signature>::from_bytes(&flipped_bytes).unwrap();
+        let (compact, recovery_id) = signature.split();
+        let malleated_signature = Secp256k1Signature::from_parts(&compact, recovery_id ^ 1).unwrap();
 
         // malleated signature with opposite sign fails to verify
         assert!(key_pair.public().verify(message, &malleated_signature).is_err());
@@ -364,12 +1340,11 @@ proptest::proptest! {
         assert_eq!(signature.as_ref(), ToFromBytes::as_bytes(&signature_1));
 
         // use ffi-implemented keypair to verify sig constructed by k256
-        let sig_bytes_1 = bincode::serialize(&signature_1.as_ref()).unwrap();
-        let secp_sig1 = bincode::deserialize::<Secp256k1Signature>(&sig_bytes_1).unwrap();
+        let secp_sig1 = Secp256k1Signature::try_from(&signature_1).unwrap();
         assert!(key_pair_copied_2.public().verify(message, &secp_sig1).is_ok());
 
         // use k256 keypair to verify sig constructed by ffi-implementation
-        let typed_sig = k256::ecdsa::recoverable::Signature::try_from(signature.as_ref()).unwrap();
+        let typed_sig = k256::ecdsa::recoverable::Signature::try_from(&signature).unwrap();
         assert!(pub_key_1.verify(message, &typed_sig).is_ok());
     }
 }
@@ -378,7 +1353,8 @@ proptest::proptest! {
 fn wycheproof_test() {
     let test_set = TestSet::load(EcdsaSecp256k1Sha256).unwrap();
     for test_group in test_set.test_groups {
-        let pk = Secp256k1PublicKey::from_uncompressed(&test_group.key.key);
+        let key: &[u8] = &test_group.key.key;
+        let pk = Secp256k1PublicKey::from_uncompressed(key.try_into().unwrap()).unwrap();
         for test in test_group.tests {
             let bytes = match Signature::from_der(&test.sig) {
                 Ok(s) => s.serialize_compact(),
@@ -387,25 +1363,28 @@ fn wycheproof_test() {
                     continue;
                 }
             };
-
-            // Wycheproof tests do not provide a recovery id, iterate over all possible ones to verify.
-            let mut n_bytes = [0u8; 65];
-            n_bytes[..64].copy_from_slice(&bytes[..]);
-            let mut res = TestResult::Invalid;
-
-            for i in 0..4 {
-                n_bytes[64] = i;
-                let sig = <Secp256k1Signature as ToFromBytes>::from_bytes(&n_bytes).unwrap();
-                if pk
-                    .verify_hashed(&k256::sha2::Sha256::digest(&test.msg), &sig)
-                    .is_ok()
-                {
-                    res = TestResult::Valid;
-                    break;
-                } else {
-                    continue;
+            let mut normalized = Signature::from_compact(&bytes).unwrap();
+            normalized.normalize_s();
+            let is_low_s = normalized.serialize_compact() == bytes;
+
+            // Wycheproof tests do not provide a recovery id, so try all possible ones.
+            let digest = crate::hash::Digest::new(k256::sha2::Sha256::digest(&test.msg).into());
+            let res = match pk.verify_and_find_recovery_id(&digest, &bytes) {
+                Some(recovery_id) => {
+                    // Signatures in the high-S form are only accepted by the unchecked parser.
+                    assert_eq!(
+                        Secp256k1Signature::from_parts(&bytes, recovery_id).is_ok(),
+                        is_low_s
+                    );
+                    let sig = Secp256k1Signature::from_bytes_unchecked(
+                        &[&bytes[..], &[recovery_id]].concat(),
+                    )
+                    .unwrap();
+                    assert!(pk.verify_digest(&digest, &sig).is_ok());
+                    TestResult::Valid
                 }
-            }
+                None => TestResult::Invalid,
+            };
             assert_eq!(map_result(test.result), res);
         }
     }
@@ -417,3 +1396,259 @@ fn map_result(t: TestResult) -> TestResult {
         _ => TestResult::Invalid, // Treat Acceptable as Invalid
     }
 }
+
+// Generated with `openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:secp256k1` and
+// converted with `openssl pkcs8 -topk8 -nocrypt -outform DER`.
+const OPENSSL_PKCS8_KEY: &str = "308184020100301006072a8648ce3d020106052b8104000a046d306b02010104206fc4a85124f9b6f1871567844e2a555fd1662913cf3426812b8a1005ab0321afa144034200047aaa4b31fc6f83d3184397460846a03f4521026d6e810a401b6658d5a313cf19a94248bc0280f597cfd427b19aad4e46b209de7d4f49f5cd218c082818908ff2";
+const OPENSSL_PUBLIC_KEY: &str =
+    "027aaa4b31fc6f83d3184397460846a03f4521026d6e810a401b6658d5a313cf19";
+
+#[test]
+fn test_pkcs8_import_openssl_key() {
+    let der = hex::decode(OPENSSL_PKCS8_KEY).unwrap();
+    let sk = Secp256k1PrivateKey::from_pkcs8_der(&der).unwrap();
+    let kp = Secp256k1KeyPair::from(sk);
+    assert_eq!(hex::encode(kp.public().as_ref()), OPENSSL_PUBLIC_KEY);
+    assert_eq!(*kp.private().to_pkcs8_der(), der);
+}
+
+#[test]
+fn test_pkcs8_round_trip() {
+    let kp = keys().pop().unwrap();
+    let der = kp.private().to_pkcs8_der();
+    let sk = Secp256k1PrivateKey::from_pkcs8_der(&der).unwrap();
+    assert_eq!(&sk, kp.private());
+}
+
+#[test]
+fn test_pkcs8_invalid_input() {
+    // An Ed25519 key generated by OpenSSL.
+    let ed25519_der = hex::decode(
+        "302e020100300506032b657004220420b17b2200cf903a6a4b6619f23d71a58a650457a11a93ca5465653fa6c8594d68",
+    )
+    .unwrap();
+    assert_eq!(
+        Secp256k1PrivateKey::from_pkcs8_der(&ed25519_der).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    let der = hex::decode(OPENSSL_PKCS8_KEY).unwrap();
+    // The curve secp256r1 (OID 1.2.840.10045.3.1.7) instead of secp256k1 (OID 1.3.132.0.10).
+    let mut secp256r1_der = der.clone();
+    secp256r1_der.splice(17..24, hex::decode("06082a8648ce3d030107").unwrap());
+    secp256r1_der[7] += 3;
+    secp256r1_der[2] += 3;
+    assert_eq!(
+        Secp256k1PrivateKey::from_pkcs8_der(&secp256r1_der).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    // The embedded public key does not match the private key.
+    let mut wrong_public_key = der.clone();
+    *wrong_public_key.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        Secp256k1PrivateKey::from_pkcs8_der(&wrong_public_key).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_spki_openssl_public_key() {
+    // Generated with `openssl pkey -pubout -outform DER` from the key above, with and without
+    // `-ec_conv_form compressed`.
+    let uncompressed = hex::decode("3056301006072a8648ce3d020106052b8104000a034200047aaa4b31fc6f83d3184397460846a03f4521026d6e810a401b6658d5a313cf19a94248bc0280f597cfd427b19aad4e46b209de7d4f49f5cd218c082818908ff2").unwrap();
+    let compressed = hex::decode("3036301006072a8648ce3d020106052b8104000a032200027aaa4b31fc6f83d3184397460846a03f4521026d6e810a401b6658d5a313cf19").unwrap();
+
+    let pk = Secp256k1PublicKey::from_spki_der(&uncompressed).unwrap();
+    assert_eq!(hex::encode(pk.as_ref()), OPENSSL_PUBLIC_KEY);
+    assert_eq!(Secp256k1PublicKey::from_spki_der(&compressed).unwrap(), pk);
+    assert_eq!(pk.to_spki_der(), uncompressed);
+}
+
+#[test]
+fn test_spki_round_trip() {
+    let kp = keys().pop().unwrap();
+    let der = kp.public().to_spki_der();
+    assert_eq!(
+        &Secp256k1PublicKey::from_spki_der(&der).unwrap(),
+        kp.public()
+    );
+
+    // An Ed25519 key is rejected.
+    let ed25519_der = hex::decode(
+        "302a300506032b6570032100c6036b6cd70b78d7fa81779231cc6cf4c9206ec5f0abd0e481fae814844e543f",
+    )
+    .unwrap();
+    assert_eq!(
+        Secp256k1PublicKey::from_spki_der(&ed25519_der),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        Secp256k1PublicKey::from_spki_der(&der[..der.len() - 1]),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_public_key_bytes() {
+    for kp in keys() {
+        assert_eq!(kp.public_key_bytes(), kp.public().as_bytes());
+        assert_eq!(
+            &Secp256k1PublicKey::from_bytes(kp.public_key_bytes()).unwrap(),
+            kp.public()
+        );
+    }
+}
+
+#[test]
+fn test_generate_many() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let expected: Vec<_> = (0..4)
+        .map(|_| Secp256k1KeyPair::generate(&mut rng))
+        .collect();
+    let generated = keys();
+    assert_eq!(generated.len(), expected.len());
+    for (kp, expected) in generated.iter().zip(&expected) {
+        assert_eq!(kp.public(), expected.public());
+    }
+    assert!(Secp256k1KeyPair::generate_many(0, &mut rng).is_empty());
+}
+
+#[test]
+fn test_lengths() {
+    let kp = keys().pop().unwrap();
+    let signature = kp.sign(b"Hello, world!");
+    assert_eq!(kp.public().as_ref().len(), SECP256K1_PUBLIC_KEY_LENGTH);
+    assert_eq!(signature.as_ref().len(), SECP256K1_SIGNATURE_LENGTH);
+    assert_eq!(kp.private().as_ref().len(), SECP256K1_PRIVATE_KEY_LENGTH);
+}
+
+/// A reader which returns at most `chunk_size` bytes per call, and fails at the end if `fail`.
+struct ChunkedReader<'a> {
+    data: &'a [u8],
+    chunk_size: usize,
+    fail: bool,
+}
+
+impl std::io::Read for ChunkedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.data.is_empty() && self.fail {
+            return Err(std::io::Error::new(std::io::ErrorKind::Other, "failed"));
+        }
+        let n = self.chunk_size.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_verify_reader() {
+    let kp = keys().pop().unwrap();
+    let message: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    let signature = kp.sign(&message);
+    assert!(kp.public().verify(&message, &signature).is_ok());
+
+    for chunk_size in [1, 7, 8192, 100_000] {
+        let mut reader = ChunkedReader {
+            data: &message,
+            chunk_size,
+            fail: false,
+        };
+        assert!(kp.public().verify_reader(&mut reader, &signature).is_ok());
+    }
+    assert!(kp
+        .public()
+        .verify_reader(&mut std::io::Cursor::new(&message), &signature)
+        .is_ok());
+
+    // A different message, e.g. a truncated one, does not verify.
+    assert_eq!(
+        kp.public()
+            .verify_reader(&mut &message[..message.len() - 1], &signature),
+        Err(FastCryptoError::InvalidSignature)
+    );
+
+    // Neither does a message which cannot be read completely.
+    let mut reader = ChunkedReader {
+        data: &message,
+        chunk_size: 1000,
+        fail: true,
+    };
+    assert_eq!(
+        kp.public().verify_reader(&mut reader, &signature),
+        Err(FastCryptoError::GeneralOpaqueError)
+    );
+}
+
+#[test]
+fn test_verify_batch_constant_time() {
+    let kps = keys();
+    let pks: Vec<_> = kps.iter().map(|kp| kp.public().clone()).collect();
+    let message: &[u8] = b"Hello, world!";
+    let sigs: Vec<_> = kps.iter().map(|kp| kp.sign(message)).collect();
+    assert!(Secp256k1PublicKey::verify_batch_constant_time(
+        message, &pks, &sigs
+    ));
+
+    // An invalid signature anywhere in the batch makes it fail.
+    for (i, kp) in kps.iter().enumerate() {
+        let mut invalid = sigs.clone();
+        invalid[i] = kp.sign(b"Other message");
+        assert!(!Secp256k1PublicKey::verify_batch_constant_time(
+            message, &pks, &invalid
+        ));
+    }
+    assert!(!Secp256k1PublicKey::verify_batch_constant_time(
+        b"Other message",
+        &pks,
+        &sigs
+    ));
+    assert!(!Secp256k1PublicKey::verify_batch_constant_time(
+        message,
+        &pks[1..],
+        &sigs
+    ));
+    assert!(!Secp256k1PublicKey::verify_batch_constant_time(
+        message,
+        &[],
+        &[]
+    ));
+}
+
+#[test]
+fn test_recover_batch() {
+    let kps = keys();
+    let digests: Vec<_> = (0..kps.len() as u8)
+        .map(|i| crate::hash::Digest::new([i + 1; 32]))
+        .collect();
+    let mut sigs: Vec<_> = kps
+        .iter()
+        .zip(&digests)
+        .map(|(kp, digest)| kp.sign_digest(digest))
+        .collect();
+
+    let recovered = Secp256k1Signature::recover_batch(&sigs, &digests).unwrap();
+    for (kp, pk) in kps.iter().zip(&recovered) {
+        assert_eq!(kp.public(), pk);
+    }
+    assert_eq!(
+        Secp256k1Signature::recover_batch(&[], &[]).unwrap(),
+        Vec::<Secp256k1PublicKey>::new()
+    );
+    assert_eq!(
+        Secp256k1Signature::recover_batch(&sigs[1..], &digests),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // No point on the curve has the x-coordinate 5, so no key can be recovered from r = 5.
+    let mut r = [0u8; 32];
+    r[31] = 5;
+    let mut s = [0u8; 32];
+    s[31] = 1;
+    sigs[2] = Secp256k1Signature::from_bytes_unchecked(&[&r[..], &s[..], &[0]].concat()).unwrap();
+    assert_eq!(
+        Secp256k1Signature::recover_batch(&sigs, &digests),
+        Err(FastCryptoError::InvalidBatchElement { index: 2 })
+    );
+}
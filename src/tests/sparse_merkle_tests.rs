@@ -0,0 +1,115 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::FastCryptoError,
+    hash::{Keccak256, Sha256},
+    merkle::sparse::{SmtProof, SparseMerkleTree},
+};
+
+fn key(i: u8) -> [u8; 32] {
+    let mut key = [i; 32];
+    // Keys which share a long prefix with other keys.
+    key[31] = i % 4;
+    key
+}
+
+fn tree(n: u8) -> SparseMerkleTree<Sha256, 32> {
+    let mut tree = SparseMerkleTree::new();
+    for i in 0..n {
+        assert_eq!(tree.insert(key(i), &[i]), None);
+    }
+    tree
+}
+
+#[test]
+fn test_inclusion() {
+    let tree = tree(8);
+    let root = tree.root();
+    for i in 0..8 {
+        assert_eq!(tree.get(&key(i)), Some(&[i][..]));
+        let proof = tree.get_proof(&key(i));
+        assert!(proof.verify::<Sha256>(&root, &key(i), Some(&[i])).is_ok());
+
+        // Wrong value, no value, wrong hash function or another key.
+        assert_eq!(
+            proof.verify::<Sha256>(&root, &key(i), Some(&[i + 1])),
+            Err(FastCryptoError::InvalidProof)
+        );
+        assert!(proof.verify::<Sha256>(&root, &key(i), None).is_err());
+        assert!(proof
+            .verify::<Keccak256>(&root, &key(i), Some(&[i]))
+            .is_err());
+        assert!(proof
+            .verify::<Sha256>(&root, &key(i + 1), Some(&[i]))
+            .is_err());
+    }
+}
+
+#[test]
+fn test_non_inclusion() {
+    let tree = tree(8);
+    let root = tree.root();
+    for i in 8..16 {
+        assert_eq!(tree.get(&key(i)), None);
+        let proof = tree.get_proof(&key(i));
+        assert!(proof.verify::<Sha256>(&root, &key(i), None).is_ok());
+        assert!(proof.verify::<Sha256>(&root, &key(i), Some(&[i])).is_err());
+    }
+
+    // A key which is in the tree cannot be shown to be absent with the proof of another key.
+    let proof = tree.get_proof(&key(8));
+    assert!(proof.verify::<Sha256>(&root, &key(0), None).is_err());
+
+    // In the empty tree every key is absent.
+    let empty = SparseMerkleTree::<Sha256, 32>::new();
+    let proof = empty.get_proof(&key(0));
+    assert!(proof.verify::<Sha256>(&empty.root(), &key(0), None).is_ok());
+    assert!(proof
+        .verify::<Sha256>(&empty.root(), &key(0), Some(&[0]))
+        .is_err());
+
+    // Proofs of the wrong length are rejected.
+    let bytes = bcs::to_bytes(&proof).unwrap();
+    let truncated: SmtProof<32> =
+        bcs::from_bytes(&[&[255, 1][..], &bytes[2..bytes.len() - 1]].concat()).unwrap();
+    assert_eq!(
+        truncated.verify::<Sha256>(&empty.root(), &key(0), None),
+        Err(FastCryptoError::InvalidProof)
+    );
+}
+
+#[test]
+fn test_update_changes_root() {
+    let mut tree = tree(8);
+    let root = tree.root();
+    assert_ne!(root, SparseMerkleTree::<Sha256, 32>::new().root());
+
+    // The root only depends on the key-value map, not on the order of insertion.
+    let mut reversed = SparseMerkleTree::<Sha256, 32>::new();
+    for i in (0..8).rev() {
+        reversed.insert(key(i), &[i]);
+    }
+    assert_eq!(reversed.root(), root);
+
+    assert_eq!(tree.insert(key(3), b"updated"), Some(vec![3]));
+    let updated = tree.root();
+    assert_ne!(updated, root);
+    let proof = tree.get_proof(&key(3));
+    assert!(proof
+        .verify::<Sha256>(&updated, &key(3), Some(b"updated"))
+        .is_ok());
+    assert!(proof
+        .verify::<Sha256>(&updated, &key(3), Some(&[3]))
+        .is_err());
+    assert!(tree
+        .get_proof(&key(3))
+        .verify::<Sha256>(&root, &key(3), Some(b"updated"))
+        .is_err());
+
+    // The same update of the other tree gives the same root, and restoring the value restores it.
+    reversed.insert(key(3), b"updated");
+    assert_eq!(reversed.root(), updated);
+    tree.insert(key(3), &[3]);
+    assert_eq!(tree.root(), root);
+}
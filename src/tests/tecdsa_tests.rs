@@ -0,0 +1,402 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::FastCryptoError,
+    secp256k1::tecdsa::{
+        DeltaShare, KeyGen, KeyShare, MtaRequest, MtaResponse, PartialSign, PresignatureShare,
+        Presigner,
+    },
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+use signature::Verifier;
+
+/// Run all rounds of presigning among `signers`, passing every message to all of them.
+fn presign(key_shares: &[KeyShare], signers: &[u16], rng: &mut StdRng) -> Vec<PresignatureShare> {
+    let (presigners, requests): (Vec<_>, Vec<_>) = signers
+        .iter()
+        .map(|i| Presigner::new(&key_shares[*i as usize - 1], signers, rng).unwrap())
+        .unzip();
+    let (presigners, responses): (Vec<_>, Vec<_>) = presigners
+        .into_iter()
+        .map(|p| p.respond(&requests, rng).unwrap())
+        .unzip();
+    let responses: Vec<_> = responses.into_iter().flatten().collect();
+    let (presigners, deltas): (Vec<_>, Vec<_>) = presigners
+        .into_iter()
+        .map(|p| p.receive(&responses).unwrap())
+        .unzip();
+    presigners
+        .into_iter()
+        .map(|p| p.finish(&deltas).unwrap())
+        .collect()
+}
+
+fn sign(presignatures: Vec<PresignatureShare>, message: &[u8]) -> Vec<PartialSign> {
+    presignatures
+        .into_iter()
+        .map(|share| share.partial_sign(message))
+        .collect()
+}
+
+#[test]
+fn test_combine_any_threshold_subset() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_gen = KeyGen::new(2, 3, &mut rng).unwrap();
+    let public_key = key_gen.public_key().clone();
+    let key_shares = key_gen.into_shares();
+    let message: &[u8] = b"Hello, world!";
+
+    for signers in [[1, 2], [1, 3], [3, 2]] {
+        let partials = sign(presign(&key_shares, &signers, &mut rng), message);
+        let signature = PartialSign::combine(&partials).unwrap();
+        assert!(public_key.verify(message, &signature).is_ok());
+        assert!(public_key.verify(b"Goodbye, world!", &signature).is_err());
+    }
+
+    // More signers than the threshold also work.
+    let partials = sign(presign(&key_shares, &[1, 2, 3], &mut rng), message);
+    let signature = PartialSign::combine(&partials).unwrap();
+    assert!(public_key.verify(message, &signature).is_ok());
+}
+
+#[test]
+fn test_larger_threshold() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_gen = KeyGen::new(3, 5, &mut rng).unwrap();
+    let public_key = key_gen.public_key().clone();
+    let key_shares = key_gen.into_shares();
+    let message: &[u8] = b"Hello, world!";
+
+    let partials = sign(presign(&key_shares, &[5, 2, 4], &mut rng), message);
+    let signature = PartialSign::combine(&partials).unwrap();
+    assert!(public_key.verify(message, &signature).is_ok());
+}
+
+#[test]
+fn test_single_party() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_gen = KeyGen::new(1, 1, &mut rng).unwrap();
+    let public_key = key_gen.public_key().clone();
+    let key_shares = key_gen.into_shares();
+    let message: &[u8] = b"Hello, world!";
+
+    let partials = sign(presign(&key_shares, &[1], &mut rng), message);
+    let signature = PartialSign::combine(&partials).unwrap();
+    assert!(public_key.verify(message, &signature).is_ok());
+}
+
+#[test]
+fn test_nonces_are_fresh() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_shares = KeyGen::new(2, 2, &mut rng).unwrap().into_shares();
+    let message: &[u8] = b"Hello, world!";
+
+    // The nonce is chosen by the signers, so presigning twice with the same key shares gives
+    // different signatures.
+    let first = PartialSign::combine(&sign(presign(&key_shares, &[1, 2], &mut rng), message));
+    let second = PartialSign::combine(&sign(presign(&key_shares, &[1, 2], &mut rng), message));
+    assert_ne!(first.unwrap(), second.unwrap());
+}
+
+#[test]
+fn test_invalid_parameters() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    assert_eq!(
+        KeyGen::new(0, 3, &mut rng).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        KeyGen::new(4, 3, &mut rng).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    let key_shares = KeyGen::new(2, 3, &mut rng).unwrap().into_shares();
+    for signers in [&[1][..], &[1, 1], &[2, 3], &[0, 1], &[1, 4]] {
+        assert_eq!(
+            Presigner::new(&key_shares[0], signers, &mut rng).err(),
+            Some(FastCryptoError::InvalidInput)
+        );
+    }
+}
+
+#[test]
+fn test_invalid_messages() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_shares = KeyGen::new(2, 3, &mut rng).unwrap().into_shares();
+    let signers = [1, 2];
+    let (first, first_request) = Presigner::new(&key_shares[0], &signers, &mut rng).unwrap();
+    let (second, second_request) = Presigner::new(&key_shares[1], &signers, &mut rng).unwrap();
+    let (_, third_request) = Presigner::new(&key_shares[2], &[2, 3], &mut rng).unwrap();
+
+    // A missing request, a request from a party which is not a signer and a duplicate request.
+    for requests in [
+        vec![first_request.clone()],
+        vec![first_request.clone(), third_request.clone()],
+        vec![second_request.clone(), second_request.clone()],
+    ] {
+        let (first, _) = Presigner::new(&key_shares[0], &signers, &mut rng).unwrap();
+        assert_eq!(
+            first.respond(&requests, &mut rng).err(),
+            Some(FastCryptoError::InvalidInput)
+        );
+    }
+
+    let requests = [first_request, second_request];
+    let (first, first_responses) = first.respond(&requests, &mut rng).unwrap();
+    let (second, _) = second.respond(&requests, &mut rng).unwrap();
+    assert_eq!(first_responses.len(), 1);
+    assert_eq!(first_responses[0].receiver(), 2);
+
+    // The response to the first signer is missing.
+    assert_eq!(
+        first.receive(&first_responses).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+
+    let (_, second_delta) = second.receive(&first_responses).unwrap();
+    assert_eq!(second_delta.sender(), 2);
+
+    // The share of delta of the first signer is missing.
+    assert_eq!(
+        second.finish(&[second_delta]).err(),
+        Some(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_invalid_partial_signatures() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_gen = KeyGen::new(2, 3, &mut rng).unwrap();
+    let public_key = key_gen.public_key().clone();
+    let key_shares = key_gen.into_shares();
+    let message: &[u8] = b"Hello, world!";
+    let partials = sign(presign(&key_shares, &[1, 2], &mut rng), message);
+    let other_partials = sign(presign(&key_shares, &[1, 2], &mut rng), message);
+
+    // Missing partial signatures.
+    assert_eq!(
+        PartialSign::combine(&partials[..1]),
+        Err(FastCryptoError::InvalidInput)
+    );
+    assert_eq!(
+        PartialSign::combine(&[]),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // The same signer twice.
+    assert_eq!(
+        PartialSign::combine(&[partials[0].clone(), partials[0].clone()]),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // Partial signatures from different presignatures.
+    assert_eq!(
+        PartialSign::combine(&[partials[0].clone(), other_partials[1].clone()]),
+        Err(FastCryptoError::InvalidInput)
+    );
+
+    // A partial signature on a different message gives an invalid signature.
+    let mut shares = presign(&key_shares, &[2, 3], &mut rng).into_iter();
+    let first = shares.next().unwrap().partial_sign(message);
+    let second = shares.next().unwrap().partial_sign(b"Goodbye, world!");
+    if let Ok(signature) = PartialSign::combine(&[first, second]) {
+        assert!(public_key.verify(message, &signature).is_err());
+    }
+}
+
+#[test]
+fn test_encodings_round_trip() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_gen = KeyGen::new(2, 3, &mut rng).unwrap();
+    let public_key = key_gen.public_key().clone();
+    let key_shares = key_gen.into_shares();
+    let message: &[u8] = b"Hello, world!";
+    let signers = [1, 3];
+
+    // Every key share and every message is encoded and decoded before it is used.
+    let key_shares: Vec<_> = key_shares
+        .iter()
+        .map(|share| {
+            let bytes = share.to_bytes();
+            let decoded = KeyShare::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded.to_bytes(), bytes);
+            let serialized = bcs::to_bytes(share).unwrap();
+            assert_eq!(
+                bcs::from_bytes::<KeyShare>(&serialized).unwrap().to_bytes(),
+                bytes
+            );
+            decoded
+        })
+        .collect();
+
+    let (presigners, requests): (Vec<_>, Vec<_>) = signers
+        .iter()
+        .map(|i| Presigner::new(&key_shares[*i as usize - 1], &signers, &mut rng).unwrap())
+        .unzip();
+    let requests: Vec<_> = requests.iter().map(round_trip_request).collect();
+    let (presigners, responses): (Vec<_>, Vec<_>) = presigners
+        .into_iter()
+        .map(|p| p.respond(&requests, &mut rng).unwrap())
+        .unzip();
+    let responses: Vec<_> = responses
+        .iter()
+        .flatten()
+        .map(round_trip_response)
+        .collect();
+    let (presigners, deltas): (Vec<_>, Vec<_>) = presigners
+        .into_iter()
+        .map(|p| p.receive(&responses).unwrap())
+        .unzip();
+    let deltas: Vec<_> = deltas.iter().map(round_trip_delta).collect();
+    let presignatures = presigners
+        .into_iter()
+        .map(|p| p.finish(&deltas).unwrap())
+        .collect();
+    let partials: Vec<_> = sign(presignatures, message)
+        .iter()
+        .map(round_trip_partial)
+        .collect();
+
+    let signature = PartialSign::combine(&partials).unwrap();
+    assert!(public_key.verify(message, &signature).is_ok());
+}
+
+fn round_trip_request(request: &MtaRequest) -> MtaRequest {
+    let decoded = MtaRequest::from_bytes(&request.to_bytes()).unwrap();
+    assert_eq!(&decoded, request);
+    let serialized = bcs::to_bytes(request).unwrap();
+    assert_eq!(
+        &bcs::from_bytes::<MtaRequest>(&serialized).unwrap(),
+        request
+    );
+    decoded
+}
+
+fn round_trip_response(response: &MtaResponse) -> MtaResponse {
+    let decoded = MtaResponse::from_bytes(&response.to_bytes()).unwrap();
+    assert_eq!(&decoded, response);
+    let serialized = bcs::to_bytes(response).unwrap();
+    assert_eq!(
+        &bcs::from_bytes::<MtaResponse>(&serialized).unwrap(),
+        response
+    );
+    decoded
+}
+
+fn round_trip_delta(delta: &DeltaShare) -> DeltaShare {
+    let decoded = DeltaShare::from_bytes(&delta.to_bytes()).unwrap();
+    assert_eq!(&decoded, delta);
+    let serialized = bcs::to_bytes(delta).unwrap();
+    assert_eq!(&bcs::from_bytes::<DeltaShare>(&serialized).unwrap(), delta);
+    decoded
+}
+
+fn round_trip_partial(partial: &PartialSign) -> PartialSign {
+    let decoded = PartialSign::from_bytes(&partial.to_bytes()).unwrap();
+    assert_eq!(&decoded, partial);
+    let serialized = bcs::to_bytes(partial).unwrap();
+    assert_eq!(
+        &bcs::from_bytes::<PartialSign>(&serialized).unwrap(),
+        partial
+    );
+    decoded
+}
+
+/// Check that `bytes` with `edit` applied is rejected by `decode`.
+fn assert_rejected<T>(
+    bytes: &[u8],
+    edit: impl FnOnce(&mut Vec<u8>),
+    decode: impl FnOnce(&[u8]) -> Result<T, FastCryptoError>,
+) {
+    let mut bytes = bytes.to_vec();
+    edit(&mut bytes);
+    assert_eq!(decode(&bytes).err(), Some(FastCryptoError::InvalidInput));
+}
+
+#[test]
+fn test_malformed_encodings() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let key_shares = KeyGen::new(2, 3, &mut rng).unwrap().into_shares();
+    let signers = [1, 2];
+    let (first, first_request) = Presigner::new(&key_shares[0], &signers, &mut rng).unwrap();
+    let (second, second_request) = Presigner::new(&key_shares[1], &signers, &mut rng).unwrap();
+    let requests = [first_request.clone(), second_request];
+    let (first, first_responses) = first.respond(&requests, &mut rng).unwrap();
+    let (second, second_responses) = second.respond(&requests, &mut rng).unwrap();
+    let (first, first_delta) = first.receive(&second_responses).unwrap();
+    let (second, second_delta) = second.receive(&first_responses).unwrap();
+    let deltas = [first_delta.clone(), second_delta];
+    let partial = first
+        .finish(&deltas)
+        .unwrap()
+        .partial_sign(b"Hello, world!");
+
+    let key_share = key_shares[0].to_bytes();
+    let request = first_request.to_bytes();
+    let response = first_responses[0].to_bytes();
+    let delta = first_delta.to_bytes();
+    let partial = partial.to_bytes();
+
+    // A missing or a trailing byte.
+    for (bytes, decode) in [
+        (&key_share[..], decode_key_share as fn(&[u8]) -> _),
+        (&request[..], |b| MtaRequest::from_bytes(b).map(|_| ())),
+        (&response[..], |b| MtaResponse::from_bytes(b).map(|_| ())),
+        (&delta[..], |b| DeltaShare::from_bytes(b).map(|_| ())),
+        (&partial[..], |b| PartialSign::from_bytes(b).map(|_| ())),
+    ] {
+        assert_rejected(bytes, |b| b.truncate(b.len() - 1), decode);
+        assert_rejected(bytes, |b| b.push(0), decode);
+        // The sender or signer index is zero.
+        assert_rejected(bytes, |b| b[..2].copy_from_slice(&[0, 0]), decode);
+    }
+
+    // A scalar which is not smaller than the group order.
+    assert_rejected(&key_share, |b| b[6..38].fill(0xff), decode_key_share);
+    assert_rejected(&response, |b| b[37..69].fill(0xff), MtaResponse::from_bytes);
+    assert_rejected(&delta, |b| b[2..].fill(0xff), DeltaShare::from_bytes);
+    let s = partial.len() - 32;
+    assert_rejected(&partial, |b| b[s..].fill(0xff), PartialSign::from_bytes);
+
+    // An invalid point prefix or a point which is not on the curve.
+    assert_rejected(&key_share, |b| b[38] = 0x04, decode_key_share);
+    assert_rejected(&request, |b| b[2] = 0x04, MtaRequest::from_bytes);
+    assert_rejected(&request, |b| b[3..35].fill(0xff), MtaRequest::from_bytes);
+    let last_key = request.len() - 33;
+    assert_rejected(&request, |b| b[last_key] = 0x05, MtaRequest::from_bytes);
+    assert_rejected(&response, |b| b[4] = 0x00, MtaResponse::from_bytes);
+    assert_rejected(&partial, |b| b[s - 33] = 0x04, PartialSign::from_bytes);
+
+    // Invalid indices: a threshold larger than the number of parties, a response to its own
+    // sender, unsorted signers and a signer which is not among the signers.
+    assert_rejected(
+        &key_share,
+        |b| b[2..4].copy_from_slice(&[0, 4]),
+        decode_key_share,
+    );
+    assert_rejected(
+        &response,
+        |b| b[2..4].copy_from_slice(&[0, 1]),
+        MtaResponse::from_bytes,
+    );
+    assert_rejected(
+        &partial,
+        |b| b[4..8].copy_from_slice(&[0, 2, 0, 1]),
+        PartialSign::from_bytes,
+    );
+    assert_rejected(
+        &partial,
+        |b| b[..2].copy_from_slice(&[0, 3]),
+        PartialSign::from_bytes,
+    );
+
+    // Deserialization fails in the same way.
+    let mut serialized = bcs::to_bytes(&first_delta).unwrap();
+    serialized[3..].fill(0xff);
+    assert!(bcs::from_bytes::<DeltaShare>(&serialized).is_err());
+}
+
+fn decode_key_share(bytes: &[u8]) -> Result<(), FastCryptoError> {
+    KeyShare::from_bytes(bytes).map(|_| ())
+}
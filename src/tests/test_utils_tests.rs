@@ -0,0 +1,38 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::{min_pk, min_sig},
+    ed25519::{Ed25519KeyPair, Ed25519Signature},
+    secp256k1::{Secp256k1KeyPair, Secp256k1PublicKey, Secp256k1Signature},
+    traits::{KeyPair, ToFromBytes},
+};
+use proptest::prelude::*;
+use signature::{Signer, Verifier};
+
+proptest! {
+    #[test]
+    fn test_generated_key_pairs_sign_and_verify(
+        ed25519 in any::<Ed25519KeyPair>(),
+        secp256k1 in any::<Secp256k1KeyPair>(),
+        bls_min_sig in any::<min_sig::BLS12381KeyPair>(),
+        bls_min_pk in any::<min_pk::BLS12381KeyPair>(),
+        message in any::<Vec<u8>>(),
+    ) {
+        prop_assert!(ed25519.public().verify(&message, &ed25519.sign(&message)).is_ok());
+        prop_assert!(secp256k1.public().verify(&message, &secp256k1.sign(&message)).is_ok());
+        prop_assert!(bls_min_sig.public().verify(&message, &bls_min_sig.sign(&message)).is_ok());
+        prop_assert!(bls_min_pk.public().verify(&message, &bls_min_pk.sign(&message)).is_ok());
+    }
+
+    #[test]
+    fn test_generated_values_are_valid(
+        pk in any::<Secp256k1PublicKey>(),
+        secp256k1_sig in any::<Secp256k1Signature>(),
+        ed25519_sig in any::<Ed25519Signature>(),
+    ) {
+        prop_assert_eq!(Secp256k1PublicKey::from_bytes(pk.as_ref()).unwrap(), pk);
+        prop_assert!(secp256k1_sig.is_canonical());
+        prop_assert!(ed25519_sig.is_canonical());
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Run in a headless browser with `wasm-pack test --headless --firefox -- --features wasm`. These
+// only run on wasm32, since JsError cannot be constructed on other targets.
+
+use crate::wasm::{ed25519_keygen, ed25519_public_key, ed25519_sign, ed25519_verify};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn test_sign_verify_round_trip() {
+    let private_key = ed25519_keygen();
+    let public_key = ed25519_public_key(&private_key).unwrap();
+    let message: &[u8] = b"Hello, world!";
+
+    let signature = ed25519_sign(&private_key, message).unwrap();
+    assert!(ed25519_verify(&public_key, message, &signature).unwrap());
+    assert!(!ed25519_verify(&public_key, b"Bad message!", &signature).unwrap());
+
+    // A key from a different keygen does not verify the signature.
+    let other_public_key = ed25519_public_key(&ed25519_keygen()).unwrap();
+    assert!(!ed25519_verify(&other_public_key, message, &signature).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_malformed_input() {
+    let private_key = ed25519_keygen();
+    let public_key = ed25519_public_key(&private_key).unwrap();
+    let signature = ed25519_sign(&private_key, b"Hello, world!").unwrap();
+
+    assert!(ed25519_public_key(&private_key[1..]).is_err());
+    assert!(ed25519_sign(&private_key[1..], b"Hello, world!").is_err());
+    assert!(ed25519_verify(&public_key[1..], b"Hello, world!", &signature).is_err());
+    assert!(ed25519_verify(&public_key, b"Hello, world!", &signature[1..]).is_err());
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::FastCryptoError,
+    traits::ToFromBytes,
+    x25519::{diffie_hellman, X25519KeyPair, X25519PrivateKey, X25519PublicKey},
+};
+use rand::{rngs::StdRng, SeedableRng as _};
+
+fn private_key(hex: &str) -> X25519PrivateKey {
+    X25519PrivateKey::from_bytes(&hex::decode(hex).unwrap()).unwrap()
+}
+
+fn public_key(hex: &str) -> X25519PublicKey {
+    X25519PublicKey::from_bytes(&hex::decode(hex).unwrap()).unwrap()
+}
+
+#[test]
+fn test_rfc7748_scalar_multiplication() {
+    // Section 5.2.
+    let sk = private_key("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+    let pk = public_key("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+    assert_eq!(
+        hex::encode(diffie_hellman(&sk, &pk).unwrap().as_bytes()),
+        "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"
+    );
+}
+
+#[test]
+fn test_rfc7748_diffie_hellman() {
+    // Section 6.1.
+    let alice = X25519KeyPair::from(private_key(
+        "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+    ));
+    let bob = X25519KeyPair::from(private_key(
+        "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+    ));
+    assert_eq!(
+        alice.public(),
+        &public_key("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+    );
+    assert_eq!(
+        bob.public(),
+        &public_key("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+    );
+
+    let expected = "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742";
+    let alice_secret = diffie_hellman(alice.private(), bob.public()).unwrap();
+    let bob_secret = diffie_hellman(bob.private(), alice.public()).unwrap();
+    assert_eq!(hex::encode(alice_secret.as_bytes()), expected);
+    assert_eq!(hex::encode(bob_secret.as_bytes()), expected);
+}
+
+#[test]
+fn test_derive_key() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let alice = X25519KeyPair::generate(&mut rng);
+    let bob = X25519KeyPair::generate(&mut rng);
+    let alice_secret = diffie_hellman(alice.private(), bob.public()).unwrap();
+    let bob_secret = diffie_hellman(bob.private(), alice.public()).unwrap();
+
+    let key: [u8; 32] = alice_secret.derive_key(b"salt", b"info").unwrap();
+    assert_eq!(key, bob_secret.derive_key(b"salt", b"info").unwrap());
+    assert_ne!(key, alice_secret.derive_key(b"salt", b"other").unwrap());
+    assert_ne!(key, alice_secret.derive_key(b"other", b"info").unwrap());
+
+    // HKDF-SHA256 outputs at most 255 * 32 bytes.
+    assert!(alice_secret.derive_key::<8160>(b"salt", b"info").is_ok());
+    assert_eq!(
+        alice_secret.derive_key::<8161>(b"salt", b"info"),
+        Err(FastCryptoError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_reject_low_order_points() {
+    // Encodings of points of small order, including non-canonical ones.
+    let low_order_points = [
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800",
+        "5f9c95bca3508c24b1d0b1559c83ef5b04445cc4581c8e86d8224eddd09f1157",
+        "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    ];
+    let kp = X25519KeyPair::generate(&mut StdRng::from_seed([0; 32]));
+    for point in low_order_points {
+        assert_eq!(
+            diffie_hellman(kp.private(), &public_key(point)).err(),
+            Some(FastCryptoError::InvalidInput)
+        );
+    }
+}
+
+#[test]
+fn test_invalid_lengths() {
+    assert!(X25519PublicKey::from_bytes(&[0u8; 31]).is_err());
+    assert!(X25519PrivateKey::from_bytes(&[0u8; 33]).is_err());
+}
+
+#[test]
+fn test_public_key_order() {
+    let low = public_key("0100000000000000000000000000000000000000000000000000000000000080");
+    let high = public_key("0200000000000000000000000000000000000000000000000000000000000000");
+    assert!(low < high);
+    assert_eq!(low.cmp(&low), std::cmp::Ordering::Equal);
+}
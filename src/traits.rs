@@ -4,14 +4,16 @@
 use base64ct::Encoding;
 use eyre::eyre;
 
+use crate::{encoding::EncodeDecode, error::FastCryptoError};
+
+#[cfg(feature = "signing")]
 use rand::{CryptoRng, RngCore};
 
 use serde::{de::DeserializeOwned, Serialize};
 pub use signature::{Error, Signer};
-use std::{
-    fmt::{Debug, Display},
-    str::FromStr,
-};
+use std::fmt::{Debug, Display};
+#[cfg(feature = "signing")]
+use std::str::FromStr;
 
 pub const DEFAULT_DOMAIN: [u8; 16] = [0u8; 16];
 
@@ -40,9 +42,40 @@ pub trait ToFromBytes: AsRef<[u8]> + Debug + Sized {
     fn from_bytes(bytes: &[u8]) -> Result<Self, Error>;
 
     /// Borrow a byte slice representing the serialized form of this key
+    ///
+    /// For signatures, the bytes are only unique, and thus safe to hash or compare, if the
+    /// signature is canonical, e.g. after calling `canonicalize` on it.
     fn as_bytes(&self) -> &[u8] {
         self.as_ref()
     }
+
+    /// Serialize into the canonical byte layout of this type, which is fixed and does not depend
+    /// on a serde backend, so it can be used for formats which must not change, e.g. on chain. It
+    /// is the byte representation returned by [ToFromBytes::as_bytes]:
+    ///
+    /// * Ed25519: public keys are 32-byte compressed points and signatures are `R || S` (64
+    ///   bytes) as in RFC 8032, and private keys are the 32-byte seed.
+    /// * secp256k1: public keys are 33-byte compressed SEC1 points, signatures are
+    ///   `r || s || recovery id` (65 bytes) with big-endian `r` and `s`, and private keys are
+    ///   32-byte big-endian scalars.
+    /// * BLS12-381: points are compressed as in the ZCash serialization format, i.e. 48 bytes
+    ///   for G1 and 96 bytes for G2, and private keys are 32-byte big-endian scalars. Public keys
+    ///   are in G2 and signatures are in G1 in `min_sig`, and the other way around in `min_pk`.
+    fn canonical_serialize(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    /// Parse the output of [ToFromBytes::canonical_serialize]. Only the canonical encoding of a
+    /// value is accepted: this fails with [FastCryptoError::InvalidInput] if [ToFromBytes::from_bytes]
+    /// accepts `bytes` but the value does not serialize back to exactly `bytes`, so every value
+    /// has a single canonical encoding.
+    fn canonical_deserialize(bytes: &[u8]) -> Result<Self, FastCryptoError> {
+        let value = <Self as ToFromBytes>::from_bytes(bytes).map_err(FastCryptoError::from)?;
+        if value.canonical_serialize() != bytes {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(value)
+    }
 }
 
 impl<T: signature::Signature> ToFromBytes for T {
@@ -51,6 +84,25 @@ impl<T: signature::Signature> ToFromBytes for T {
     }
 }
 
+/// Trait impl'd by cryptographic material whose byte representation has a length known at
+/// compile time, e.g. to allocate buffers statically.
+pub trait FixedLength {
+    /// The length of the byte representation.
+    const LENGTH: usize;
+}
+
+/// Check that `bytes` has the length of the byte representation of `T`.
+pub(crate) fn check_length<T: FixedLength>(bytes: &[u8]) -> Result<(), Error> {
+    if bytes.len() != T::LENGTH {
+        return Err(FastCryptoError::InputLengthWrong {
+            expected: T::LENGTH,
+            got: bytes.len(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
 /// Cryptographic material with an immediate conversion to/from Base64 strings.
 ///
 /// This is an [extension trait](https://rust-lang.github.io/rfcs/0445-extension-trait-conventions.html) of `ToFromBytes` above.
@@ -58,6 +110,52 @@ impl<T: signature::Signature> ToFromBytes for T {
 pub trait EncodeDecodeBase64: Sized {
     fn encode_base64(&self) -> String;
     fn decode_base64(value: &str) -> Result<Self, eyre::Report>;
+
+    /// Encode with the URL-safe Base64 alphabet and without padding (RFC 4648, section 5), so the
+    /// output contains none of `+`, `/` and `=`.
+    fn encode_base64_url(&self) -> String {
+        let bytes = base64ct::Base64::decode_vec(&self.encode_base64())
+            .expect("encode_base64 returns valid Base64");
+        base64ct::Base64UrlUnpadded::encode_string(&bytes)
+    }
+
+    /// Decode a value encoded with [EncodeDecodeBase64::encode_base64_url]. Padded input is
+    /// rejected.
+    fn decode_base64_url(value: &str) -> Result<Self, eyre::Report> {
+        let bytes = base64ct::Base64UrlUnpadded::decode_vec(value)
+            .map_err(|e| eyre!("{}", e.to_string()))?;
+        Self::decode_base64(&base64ct::Base64::encode_string(&bytes))
+    }
+
+    /// Encode all `items` with [EncodeDecodeBase64::encode_base64].
+    fn encode_base64_batch(items: &[Self]) -> Vec<String> {
+        items.iter().map(Self::encode_base64).collect()
+    }
+
+    /// Decode all `values` with [EncodeDecodeBase64::decode_base64]. Fails with the position of the
+    /// first value which cannot be decoded together with the reason, which is
+    /// [FastCryptoError::InvalidInput] if no more specific error is known.
+    fn decode_base64_batch<S: AsRef<str>>(
+        values: &[S],
+    ) -> Result<Vec<Self>, (usize, FastCryptoError)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                Self::decode_base64(value.as_ref()).map_err(|e| {
+                    let error = e
+                        .downcast_ref::<FastCryptoError>()
+                        .or_else(|| {
+                            e.downcast_ref::<Error>()
+                                .and_then(FastCryptoError::from_signature_error)
+                        })
+                        .cloned()
+                        .unwrap_or(FastCryptoError::InvalidInput);
+                    (index, error)
+                })
+            })
+            .collect()
+    }
 }
 
 // The Base64ct is not strictly necessary for (PubKey|Signature), but this simplifies things a lot
@@ -72,6 +170,11 @@ impl<T: ToFromBytes> EncodeDecodeBase64 for T {
     }
 }
 
+/// Parse a value from its Base64 encoding, for use in `FromStr` implementations.
+pub(crate) fn from_base64_str<T: ToFromBytes>(value: &str) -> Result<T, FastCryptoError> {
+    T::decode(crate::encoding::Encoding::Base64, value)
+}
+
 /// Trait impl'd by public keys in asymmetric cryptography.
 ///
 /// The trait bounds are implemented so as to be symmetric and equivalent
@@ -86,6 +189,7 @@ pub trait VerifyingKey:
     + Ord // required to put keys in BTreeMap
     + Default // see [#34](https://github.com/MystenLabs/narwhal/issues/34)
     + ToFromBytes
+    + FixedLength
     + signature::Verifier<Self::Sig>
     + for <'a> From<&'a Self::PrivKey> // conversion PrivateKey -> PublicKey
     + Send
@@ -95,7 +199,6 @@ pub trait VerifyingKey:
 {
     type PrivKey: SigningKey<PubKey = Self>;
     type Sig: Authenticator<PubKey = Self>;
-    const LENGTH: usize;
 
     // Expected to be overridden by implementations
     fn verify_batch_empty_fail(msg: &[u8], pks: &[Self], sigs: &[Self::Sig]) -> Result<(), eyre::Report> {
@@ -110,6 +213,32 @@ pub trait VerifyingKey:
             .try_for_each(|(pk, sig)| pk.verify(msg, sig))
             .map_err(|_| eyre!("Signature verification failed"))
     }
+
+    /// Returns true if `sigs[i]` is a valid signature on `msg` by `pks[i]` for all `i`, and false
+    /// if any of them is invalid, if there are no signatures or if the number of signatures and
+    /// keys differ.
+    ///
+    /// Unlike [VerifyingKey::verify_batch_empty_fail], this does not stop at the first invalid
+    /// signature: every signature is verified, so the running time does not reveal how many
+    /// signatures preceded the first invalid one. The time taken by each verification may still
+    /// depend on its signature, e.g. a malformed signature may be rejected faster than a well-formed
+    /// but invalid one.
+    fn verify_batch_constant_time(msg: &[u8], pks: &[Self], sigs: &[Self::Sig]) -> bool {
+        if sigs.is_empty() || pks.len() != sigs.len() {
+            return false;
+        }
+        pks.iter()
+            .zip(sigs)
+            .fold(true, |valid, (pk, sig)| valid & pk.verify(msg, sig).is_ok())
+    }
+
+    /// Return the index of the first key in `pks` under which `sig` is a valid signature on `msg`.
+    /// Fails with [FastCryptoError::InvalidSignature] if there is no such key.
+    fn verify_any(pks: &[Self], msg: &[u8], sig: &Self::Sig) -> Result<usize, FastCryptoError> {
+        pks.iter()
+            .position(|pk| pk.verify(msg, sig).is_ok())
+            .ok_or(FastCryptoError::InvalidSignature)
+    }
 }
 
 /// Trait impl'd by private (secret) keys in asymmetric cryptography.
@@ -117,10 +246,11 @@ pub trait VerifyingKey:
 /// The trait bounds are implemented so as to be symmetric and equivalent
 /// to the ones on its associated types for public key and signature material.
 ///
-pub trait SigningKey: ToFromBytes + Serialize + DeserializeOwned + Send + Sync + 'static {
+pub trait SigningKey:
+    ToFromBytes + FixedLength + Serialize + DeserializeOwned + Send + Sync + 'static
+{
     type PubKey: VerifyingKey<PrivKey = Self>;
     type Sig: Authenticator<PrivKey = Self>;
-    const LENGTH: usize;
 }
 
 /// Trait impl'd by signatures in asymmetric cryptography.
@@ -130,6 +260,9 @@ pub trait SigningKey: ToFromBytes + Serialize + DeserializeOwned + Send + Sync +
 ///
 pub trait Authenticator:
     signature::Signature
+    + std::hash::Hash
+    + Eq
+    + FixedLength
     + Display
     + Default
     + Serialize
@@ -141,11 +274,12 @@ pub trait Authenticator:
 {
     type PubKey: VerifyingKey<Sig = Self>;
     type PrivKey: SigningKey<Sig = Self>;
-    const LENGTH: usize;
 }
 
-/// Trait impl'd by a public / private key pair in asymmetric cryptography.
+/// Trait impl'd by a public / private key pair in asymmetric cryptography. Only available with the
+/// `signing` feature.
 ///
+#[cfg(feature = "signing")]
 pub trait KeyPair:
     Sized + From<Self::PrivKey> + Signer<Self::Sig> + EncodeDecodeBase64 + FromStr
 {
@@ -156,10 +290,53 @@ pub trait KeyPair:
     fn public(&'_ self) -> &'_ Self::PubKey;
     fn private(self) -> Self::PrivKey;
 
+    /// Split this key pair into its public and private key, e.g. to store them separately. The
+    /// key pair can be reassembled with [KeyPair::from_parts].
+    fn into_parts(self) -> (Self::PubKey, Self::PrivKey) {
+        let public = self.public().clone();
+        (public, self.private())
+    }
+
+    /// Reassemble a key pair split with [KeyPair::into_parts]. The public key is derived again from
+    /// the private key, and this fails with [FastCryptoError::InvalidInput] if it differs from
+    /// `public`.
+    fn from_parts(public: Self::PubKey, private: Self::PrivKey) -> Result<Self, FastCryptoError> {
+        let key_pair = Self::from(private);
+        if key_pair.public() != &public {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        Ok(key_pair)
+    }
+
+    /// The encoding of the public key. The public key is derived once when the key pair is
+    /// created, and its encoding is computed on first use and cached, so this is cheap to call
+    /// repeatedly.
+    fn public_key_bytes(&self) -> &[u8] {
+        self.public().as_ref()
+    }
+
     #[cfg(feature = "copy_key")]
     fn copy(&self) -> Self;
 
     fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self;
+
+    /// Generate `n` key pairs. By default this is the same as calling [KeyPair::generate] `n`
+    /// times with the same `rng`, but schemes may override it with a faster batched generation.
+    fn generate_many<R: CryptoRng + RngCore>(n: usize, rng: &mut R) -> Vec<Self> {
+        (0..n).map(|_| Self::generate(rng)).collect()
+    }
+
+    /// Export this key pair as JSON, encrypted with a key derived from `password`. The format is
+    /// described in [crate::keystore].
+    fn to_keystore_json<R: CryptoRng + RngCore>(&self, password: &str, rng: &mut R) -> String {
+        crate::keystore::encrypt(self, password, crate::keystore::DEFAULT_SCRYPT_LOG_N, rng)
+    }
+
+    /// Import a key pair exported by [KeyPair::to_keystore_json]. Fails with
+    /// [FastCryptoError::InvalidPassword] if `password` is not the one it was exported with.
+    fn from_keystore_json(json: &str, password: &str) -> Result<Self, FastCryptoError> {
+        crate::keystore::decrypt(json, password)
+    }
 }
 
 /// Trait impl'd by aggregated signatures in asymmetric cryptography.
@@ -193,3 +370,26 @@ pub trait AggregateAuthenticator:
         messages: &[&[u8]],
     ) -> Result<(), Error>;
 }
+
+/// Trait impl'd by authenticated encryption schemes with associated data (AEAD).
+///
+/// Implementations share the same key and nonce handling, so that callers can swap them
+/// generically.
+///
+pub trait Aead {
+    type Key;
+    type Nonce;
+
+    /// Encrypt `plaintext` and authenticate it together with the associated data `aad`. The
+    /// output is the ciphertext followed by the authentication tag.
+    fn encrypt(key: &Self::Key, nonce: &Self::Nonce, aad: &[u8], plaintext: &[u8]) -> Vec<u8>;
+
+    /// Decrypt a ciphertext produced by [Aead::encrypt]. Fails if the ciphertext, the associated
+    /// data or the authentication tag have been tampered with.
+    fn decrypt(
+        key: &Self::Key,
+        nonce: &Self::Nonce,
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, FastCryptoError>;
+}
@@ -0,0 +1,58 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains [wasm-bindgen](https://rustwasm.github.io/wasm-bindgen/) bindings to
+//! generate Ed25519 keys, sign and verify from JavaScript. Keys, messages and signatures are
+//! passed as byte arrays (`Uint8Array` on the JavaScript side) in the encodings of
+//! [ToFromBytes]. Only Ed25519 is exposed so far.
+//!
+//! This module is only available with the `wasm` feature. The crate needs std, and building it for
+//! wasm32 needs a C compiler for that target, e.g. clang, because blst is always compiled. See the
+//! README for the build command.
+
+use rand::rngs::OsRng;
+use signature::{Signer, Verifier};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    ed25519::{Ed25519KeyPair, Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    traits::{KeyPair, ToFromBytes},
+};
+
+fn to_js_error(e: signature::Error) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// Generate a new Ed25519 private key using the randomness of the environment.
+#[wasm_bindgen]
+pub fn ed25519_keygen() -> Vec<u8> {
+    Ed25519KeyPair::generate(&mut OsRng)
+        .private()
+        .as_ref()
+        .to_vec()
+}
+
+/// The public key of an Ed25519 private key.
+#[wasm_bindgen]
+pub fn ed25519_public_key(private_key: &[u8]) -> Result<Vec<u8>, JsError> {
+    let private_key = Ed25519PrivateKey::from_bytes(private_key).map_err(to_js_error)?;
+    Ok(Ed25519PublicKey::from(&private_key).as_ref().to_vec())
+}
+
+/// Sign a message with an Ed25519 private key.
+#[wasm_bindgen]
+pub fn ed25519_sign(private_key: &[u8], msg: &[u8]) -> Result<Vec<u8>, JsError> {
+    let private_key = Ed25519PrivateKey::from_bytes(private_key).map_err(to_js_error)?;
+    let signature: Ed25519Signature = Ed25519KeyPair::from(private_key).sign(msg);
+    Ok(signature.as_ref().to_vec())
+}
+
+/// Verify an Ed25519 signature. Returns false if the signature is invalid, and fails if the
+/// public key or signature cannot be parsed.
+#[wasm_bindgen]
+pub fn ed25519_verify(public_key: &[u8], msg: &[u8], signature: &[u8]) -> Result<bool, JsError> {
+    let public_key = Ed25519PublicKey::from_bytes(public_key).map_err(to_js_error)?;
+    let signature =
+        <Ed25519Signature as ToFromBytes>::from_bytes(signature).map_err(to_js_error)?;
+    Ok(public_key.verify(msg, &signature).is_ok())
+}
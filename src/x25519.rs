@@ -0,0 +1,218 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains an implementation of the X25519 Diffie-Hellman key agreement from
+//! [RFC 7748](https://www.rfc-editor.org/rfc/rfc7748), built on the Montgomery form of Curve25519
+//! from [curve25519-dalek-ng](https://docs.rs/curve25519-dalek-ng).
+//!
+//! Example:
+//! ```rust
+//! use fastcrypto::x25519::{diffie_hellman, X25519KeyPair};
+//! use rand::thread_rng;
+//! # fn main() {
+//!     let alice = X25519KeyPair::generate(&mut thread_rng());
+//!     let bob = X25519KeyPair::generate(&mut thread_rng());
+//!     let alice_secret = diffie_hellman(alice.private(), bob.public()).unwrap();
+//!     let bob_secret = diffie_hellman(bob.private(), alice.public()).unwrap();
+//!     assert_eq!(
+//!         alice_secret.derive_key::<32>(b"salt", b"channel key").unwrap(),
+//!         bob_secret.derive_key::<32>(b"salt", b"channel key").unwrap()
+//!     );
+//! # }
+//! ```
+
+use crate::{
+    error::FastCryptoError,
+    traits::{check_length, FixedLength, ToFromBytes},
+};
+use curve25519_dalek_ng::{constants::X25519_BASEPOINT, montgomery::MontgomeryPoint, scalar};
+use hkdf::Hkdf;
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use std::fmt::{self, Debug};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub const X25519_PUBLIC_KEY_LENGTH: usize = 32;
+pub const X25519_PRIVATE_KEY_LENGTH: usize = 32;
+pub const X25519_SHARED_SECRET_LENGTH: usize = 32;
+
+/// An X25519 public key, i.e. the u-coordinate of a point on Curve25519.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct X25519PublicKey(MontgomeryPoint);
+
+/// An X25519 private key. Any 32 bytes are a valid key; they are clamped as described in
+/// RFC 7748 when used. The key material is zeroized on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct X25519PrivateKey([u8; X25519_PRIVATE_KEY_LENGTH]);
+
+/// An X25519 key pair.
+#[derive(Debug)]
+pub struct X25519KeyPair {
+    public: X25519PublicKey,
+    private: X25519PrivateKey,
+}
+
+/// The output of a Diffie-Hellman key agreement, either over X25519 or over secp256k1 using
+/// [crate::secp256k1::Secp256k1PrivateKey::diffie_hellman]. It should not be used as a key directly but
+/// passed through [SharedSecret::derive_key]. The secret is zeroized on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct SharedSecret([u8; X25519_SHARED_SECRET_LENGTH]);
+
+/// Compute the shared secret between `my_sk` and `their_pk`.
+///
+/// Fails with [FastCryptoError::InvalidInput] if the shared secret is all zeros, which is the case
+/// exactly when `their_pk` is a point of small order. Rejecting these ensures that both parties
+/// contribute to the shared secret.
+pub fn diffie_hellman(
+    my_sk: &X25519PrivateKey,
+    their_pk: &X25519PublicKey,
+) -> Result<SharedSecret, FastCryptoError> {
+    let shared = SharedSecret(my_sk.clamped_mul(&their_pk.0).to_bytes());
+    if bool::from(shared.0.ct_eq(&[0u8; X25519_SHARED_SECRET_LENGTH])) {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok(shared)
+}
+
+impl X25519PrivateKey {
+    pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; X25519_PRIVATE_KEY_LENGTH];
+        rng.fill_bytes(&mut bytes);
+        X25519PrivateKey(bytes)
+    }
+
+    /// Multiply `point` by the clamped private key.
+    fn clamped_mul(&self, point: &MontgomeryPoint) -> MontgomeryPoint {
+        let mut bytes = self.0;
+        bytes[0] &= 248;
+        bytes[31] &= 127;
+        bytes[31] |= 64;
+        let result = point * scalar::Scalar::from_bits(bytes);
+        bytes.zeroize();
+        result
+    }
+}
+
+impl AsRef<[u8]> for X25519PrivateKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Debug for X25519PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "X25519PrivateKey(<redacted>)")
+    }
+}
+
+impl FixedLength for X25519PrivateKey {
+    const LENGTH: usize = X25519_PRIVATE_KEY_LENGTH;
+}
+
+impl ToFromBytes for X25519PrivateKey {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
+        let mut key = [0u8; X25519_PRIVATE_KEY_LENGTH];
+        key.copy_from_slice(bytes);
+        Ok(X25519PrivateKey(key))
+    }
+}
+
+impl From<&X25519PrivateKey> for X25519PublicKey {
+    fn from(sk: &X25519PrivateKey) -> Self {
+        X25519PublicKey(sk.clamped_mul(&X25519_BASEPOINT))
+    }
+}
+
+#[allow(clippy::derive_hash_xor_eq)] // MontgomeryPoint's PartialEq compares the bytes
+impl std::hash::Hash for X25519PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.as_bytes().hash(state);
+    }
+}
+
+impl PartialOrd for X25519PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for X25519PublicKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.as_bytes().cmp(other.0.as_bytes())
+    }
+}
+
+impl AsRef<[u8]> for X25519PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl FixedLength for X25519PublicKey {
+    const LENGTH: usize = X25519_PUBLIC_KEY_LENGTH;
+}
+
+impl ToFromBytes for X25519PublicKey {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        check_length::<Self>(bytes)?;
+        let mut point = [0u8; X25519_PUBLIC_KEY_LENGTH];
+        point.copy_from_slice(bytes);
+        Ok(X25519PublicKey(MontgomeryPoint(point)))
+    }
+}
+
+impl X25519KeyPair {
+    pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        X25519PrivateKey::generate(rng).into()
+    }
+
+    pub fn public(&self) -> &X25519PublicKey {
+        &self.public
+    }
+
+    pub fn private(&self) -> &X25519PrivateKey {
+        &self.private
+    }
+}
+
+impl From<X25519PrivateKey> for X25519KeyPair {
+    fn from(private: X25519PrivateKey) -> Self {
+        X25519KeyPair {
+            public: (&private).into(),
+            private,
+        }
+    }
+}
+
+impl SharedSecret {
+    pub(crate) fn new(bytes: [u8; X25519_SHARED_SECRET_LENGTH]) -> Self {
+        SharedSecret(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; X25519_SHARED_SECRET_LENGTH] {
+        &self.0
+    }
+
+    /// Derive an `N` byte key from this secret using HKDF-SHA256 with the given `salt` and
+    /// `info`. Fails with [FastCryptoError::InvalidInput] if `N` is larger than HKDF-SHA256 can
+    /// output (8160 bytes).
+    pub fn derive_key<const N: usize>(
+        &self,
+        salt: &[u8],
+        info: &[u8],
+    ) -> Result<[u8; N], FastCryptoError> {
+        let mut okm = [0u8; N];
+        Hkdf::<Sha256>::new(Some(salt), &self.0)
+            .expand(info, &mut okm)
+            .map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(okm)
+    }
+}
+
+impl Debug for SharedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SharedSecret(<redacted>)")
+    }
+}